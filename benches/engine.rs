@@ -0,0 +1,73 @@
+// benches/engine.rs - Bancs d'essai criterion : débit du moteur, génération de cartes, pathfinding
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use final_project::simulation::{
+    bench_engine, run_turns, MapStrategy, MapWorkload, PathWorkload, CROWD_ROBOTS, CROWD_SIZE, CROWD_TURNS,
+    ENGINE_TURNS, MAP_SIZES, PATH_QUERIES, ROBOT_COUNTS,
+};
+
+// Tours par seconde selon la taille de la carte et l'effectif
+fn engine_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("moteur");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(ENGINE_TURNS as u64));
+    for size in MAP_SIZES {
+        for robots in ROBOT_COUNTS {
+            let id = BenchmarkId::new(format!("{}x{}", size, size), format!("{} robots", robots));
+            group.bench_function(id, |b| {
+                b.iter_batched(
+                    || bench_engine(size, robots),
+                    |mut engine| run_turns(&mut engine, ENGINE_TURNS),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+
+    // Grand essaim : gain des décisions parallèles
+    group.throughput(Throughput::Elements(CROWD_TURNS as u64));
+    for parallel in [false, true] {
+        let mode = if parallel { "parallèle" } else { "séquentiel" };
+        let id = BenchmarkId::new(format!("{}x{}", CROWD_SIZE, CROWD_SIZE), format!("{} robots {}", CROWD_ROBOTS, mode));
+        group.bench_function(id, |b| {
+            b.iter_batched(
+                || {
+                    let mut engine = bench_engine(CROWD_SIZE, CROWD_ROBOTS);
+                    engine.parallel = parallel;
+                    engine
+                },
+                |mut engine| run_turns(&mut engine, CROWD_TURNS),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+// Temps d'obtention d'une carte pour chaque source
+fn map_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("carte");
+    for strategy in MapStrategy::ALL {
+        for size in MAP_SIZES {
+            let workload = MapWorkload::new(strategy, size);
+            group.throughput(Throughput::Elements((size * size) as u64));
+            group.bench_with_input(BenchmarkId::new(strategy.label(), size), &workload, |b, workload| {
+                b.iter(|| workload.run())
+            });
+        }
+    }
+    group.finish();
+}
+
+// Latence d'un lot de requêtes de chemin
+fn pathfinding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chemin");
+    group.throughput(Throughput::Elements(PATH_QUERIES as u64));
+    for size in MAP_SIZES {
+        let workload = PathWorkload::new(size, PATH_QUERIES);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &workload, |b, workload| b.iter(|| workload.run()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, engine_throughput, map_generation, pathfinding);
+criterion_main!(benches);
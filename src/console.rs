@@ -0,0 +1,212 @@
+// src/console.rs - Modes console (interactif, automatique, classique) et rapports imprimés, au-dessus de `step()`
+use crate::display::DisplayEngine;
+use crate::simulation::{mission, team_scores, MissionStatus, SimulationEngine};
+use colored::Colorize;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Tour par tour au clavier : ENTER avance, 'r' affiche le rapport, 's' passe en automatique, 'q' quitte
+pub fn run_interactive(engine: &mut SimulationEngine) {
+    println!("{}", "🎮 Mode interactif activé !".bright_green().bold());
+    println!("{}", "Utilisez ENTER pour avancer tour par tour, 'q' pour quitter".bright_yellow());
+
+    loop {
+        if engine.is_finished() {
+            break;
+        }
+
+        display_current_state(engine);
+        DisplayEngine::display_controls();
+
+        print!("\n> ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let command = input.trim().to_lowercase();
+
+        match command.as_str() {
+            "q" | "quit" => {
+                println!("{}", "🏁 Simulation arrêtée par l'utilisateur".bright_red());
+                break;
+            },
+            "r" | "rapport" => {
+                print_detailed_report(engine);
+            },
+            "s" | "auto" => {
+                run_auto(engine);
+            },
+            "" => {
+                // Exécuter un tour
+                engine.step();
+                if engine.is_finished() {
+                    break;
+                }
+            },
+            _ => {
+                println!("{}", "Commande non reconnue. Utilisez ENTER, 'q', 'r', ou 's'".bright_red());
+            }
+        }
+    }
+
+    print_final_report(engine);
+}
+
+/// Mode automatique rapide : la carte est redessinée à chaque tour jusqu'à la fin de la mission
+pub fn run_auto(engine: &mut SimulationEngine) {
+    println!("{}", "🚀 Mode automatique lancé ! (Ctrl+C pour arrêter)".bright_green().bold());
+
+    while !engine.is_finished() {
+        display_current_state(engine);
+        engine.step();
+
+        // Pause entre les tours
+        thread::sleep(Duration::from_millis(100)); // 10 tours/seconde
+    }
+
+    // Fin anticipée (objectifs atteints ou impossibles) ou limite de tours du scénario
+    display_current_state(engine);
+    println!("{}", format!("🏁 Mission terminée au tour {}: {}", engine.turn, engine.mission_status.label()).bright_yellow());
+}
+
+/// Simulation classique sans carte : un point d'étape tous les 10 tours
+pub fn run_classic(engine: &mut SimulationEngine, max_turns: usize) {
+    println!("{}", format!("🎮 Démarrage de la simulation ({} tours max)", max_turns).bright_green());
+
+    for _ in 0..max_turns {
+        engine.step();
+
+        if engine.turn.is_multiple_of(10) {
+            print_status(engine);
+        }
+
+        if engine.is_finished() {
+            break;
+        }
+    }
+
+    println!("{}", "🏁 Simulation terminée !".bright_blue());
+    print_final_report(engine);
+}
+
+/// Affiche l'état actuel de la simulation
+fn display_current_state(engine: &SimulationEngine) {
+    DisplayEngine::clear_screen();
+    DisplayEngine::display_header(engine.turn);
+    DisplayEngine::display_map(&engine.map, &engine.robots, &engine.depots, &engine.teams);
+    DisplayEngine::display_robot_stats(&engine.robots);
+    DisplayEngine::display_events(&engine.events, &engine.environment, engine.turn);
+
+    // Statistiques rapides
+    println!("\n📊 Exploration: {:.1}% ({}/{})",
+             engine.exploration_percentage(), engine.explored_cell_count(), engine.width * engine.height);
+    println!("🎯 Mission: {}", engine.mission_status.label());
+}
+
+fn print_status(engine: &SimulationEngine) {
+    println!("\n{}", format!("📊 Tour {} - État de la simulation", engine.turn).bright_cyan());
+
+    for robot in &engine.robots {
+        let total_resources: u32 = robot.inventory.values().sum();
+        println!(
+            "🤖 Robot {} ({:?}): Position({},{}) Énergie:{} Ressources:{}",
+            robot.id,
+            robot.behavior,
+            robot.position.x,
+            robot.position.y,
+            robot.energy,
+            total_resources
+        );
+    }
+}
+
+// Chiffres communs au rapport détaillé et au rapport final
+fn print_totals(engine: &SimulationEngine) {
+    let stats = engine.stats();
+    println!("⚡ Énergie collectée: {}", stats.energy_collected);
+    println!("🪨 Mineraux collectés: {} (dont {} livrés)", stats.minerals_collected, stats.minerals_delivered);
+    println!("🔬 Données scientifiques: {}", stats.science_data);
+    println!("🧪 Sites scientifiques analysés: {}", stats.science_sites);
+    println!("📡 Messages transmis: {}", stats.messages_delivered);
+    println!("🔧 Pannes de modules: {} │ Réparations: {}", stats.module_failures, stats.module_repairs);
+    println!("🔄 Changements de rôle: {} │ ⛓️  Chaînes de relais: {}", stats.role_changes, stats.relay_chains);
+    println!("🏠 Dépôts: {}", engine.depot_summary());
+    println!("🌪️  Tempêtes: {} │ ☄️  Impacts de météorites: {}", stats.storms, stats.meteor_impacts);
+    println!("🔄 Tours exécutés: {}", stats.turn);
+    println!("🗺️  Exploration: {:.1}% ({}/{})",
+             stats.exploration_percentage, stats.explored_cells, engine.width * engine.height);
+}
+
+fn print_detailed_report(engine: &SimulationEngine) {
+    DisplayEngine::clear_screen();
+    println!("{}", "📋 RAPPORT DÉTAILLÉ".bright_yellow().bold());
+    println!("{}", "====================".bright_yellow());
+
+    // Statistiques globales
+    print_totals(engine);
+
+    // Détail par robot
+    println!("\n{}", "📋 Détail par robot:".bright_cyan());
+    DisplayEngine::display_robot_stats(&engine.robots);
+
+    println!("\n{}", "Appuyez sur ENTER pour continuer...".bright_white());
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+}
+
+pub fn print_final_report(engine: &SimulationEngine) {
+    println!("\n{}", "📋 RAPPORT FINAL".bright_yellow().bold());
+    println!("{}", "================".bright_yellow());
+
+    print_totals(engine);
+    for chain in &engine.relays.chains {
+        println!("⛓️  {}", chain.describe());
+    }
+
+    if engine.teams.len() > 1 {
+        print_team_scores(engine);
+    }
+    print_mission_summary(engine);
+
+    println!("\n{}", "🎯 Merci d'avoir utilisé EREEA !".bright_green().bold());
+}
+
+/// Classement des essaims en compétition
+pub fn print_team_scores(engine: &SimulationEngine) {
+    println!("\n{}", "🏆 ÉQUIPES".bright_yellow().bold());
+    println!("{:>4} │ {:<12} │ {:>8} │ {:>8} │ {:>5} │ {:>6} │ Points", "Rang", "Équipe", "Exploré", "Mineraux", "Sites", "Robots");
+    for (rank, score) in team_scores(engine).iter().enumerate() {
+        let team = &engine.teams[score.team];
+        let line = format!(
+            "{:>4} │ {:<12} │ {:>8} │ {:>8} │ {:>5} │ {:>6} │ {}",
+            rank + 1,
+            team.name,
+            score.explored,
+            score.minerals,
+            score.science_sites,
+            score.alive,
+            score.points
+        );
+        println!("{}", DisplayEngine::team_colored(&line, team.color));
+    }
+}
+
+/// Résumé de mission : état final et avancement de chaque objectif
+pub fn print_mission_summary(engine: &SimulationEngine) {
+    println!("\n{}", "🎯 MISSION".bright_yellow().bold());
+    let status = engine.mission_status.label();
+    match engine.mission_status {
+        MissionStatus::Reussie => println!("État: {}", status.bright_green()),
+        MissionStatus::Echouee(_) => println!("État: {}", status.bright_red()),
+        MissionStatus::EnCours => println!("État: {}", status.bright_yellow()),
+    }
+
+    let progress = mission::objective_progress(&engine.objectives, engine);
+    if progress.is_empty() {
+        println!("  Aucun objectif (limite de {} tours)", engine.objectives.max_turns);
+    }
+    for objective in progress {
+        println!("  {}", objective.describe());
+    }
+}
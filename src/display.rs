@@ -1,6 +1,8 @@
 // src/display.rs - Module d'affichage de la simulation
 use crate::{Cell, TerrainType, ResourceType, Position};
+use crate::{Grid, Topology};
 use crate::robot::Robot;
+use crate::simulation::{Depot, DepotKind, Environment, EventLog, Team, TeamColor};
 use colored::Colorize;
 use std::collections::HashMap;
 
@@ -8,15 +10,15 @@ pub struct DisplayEngine;
 
 impl DisplayEngine {
     /// Affiche la carte complète avec robots et ressources
-    pub fn display_map(map: &Vec<Vec<Cell>>, robots: &Vec<Robot>) {
-        let height = map.len();
-        let width = if height > 0 { map[0].len() } else { 0 };
+    pub fn display_map(map: &Grid<Cell>, robots: &[Robot], depots: &[Depot], teams: &[Team]) {
+        let width = map.width();
 
         // Créer une map des positions des robots
         let mut robot_positions: HashMap<Position, &Robot> = HashMap::new();
         for robot in robots {
             robot_positions.insert(robot.position, robot);
         }
+        let depot_positions: HashMap<Position, &Depot> = depots.iter().map(|depot| (depot.position, depot)).collect();
 
         // Afficher l'en-tête
         print!("   ");
@@ -26,7 +28,7 @@ impl DisplayEngine {
         println!();
 
         // Afficher chaque ligne
-        for (y, row) in map.iter().enumerate() {
+        for (y, row) in map.rows().enumerate() {
             print!("{:2} ", y);
 
             for (x, cell) in row.iter().enumerate() {
@@ -34,8 +36,10 @@ impl DisplayEngine {
 
                 // Vérifier s'il y a un robot à cette position
                 if let Some(robot) = robot_positions.get(&pos) {
-                    let robot_symbol = Self::get_robot_symbol(robot);
+                    let robot_symbol = Self::get_robot_symbol(robot, teams);
                     print!("{}", robot_symbol);
+                } else if let Some(depot) = depot_positions.get(&pos) {
+                    print!("{}", Self::get_depot_symbol(depot, teams));
                 } else {
                     let cell_symbol = Self::get_cell_symbol(cell);
                     print!("{}", cell_symbol);
@@ -44,12 +48,45 @@ impl DisplayEngine {
             println!();
         }
 
+        let topology = map.topology();
+        if topology != Topology::Bornee {
+            println!("{}", format!("↔ Topologie: {} (les bords reliés se prolongent de l'autre côté)", topology.label()).bright_black());
+        }
+
         // Afficher la légende
         Self::display_legend();
+        if teams.len() > 1 {
+            Self::display_team_legend(teams);
+        }
+    }
+
+    /// Texte à la couleur d'une équipe
+    pub fn team_colored(text: &str, color: TeamColor) -> String {
+        match color {
+            TeamColor::Vert => text.bright_green().to_string(),
+            TeamColor::Rouge => text.bright_red().to_string(),
+            TeamColor::Bleu => text.bright_blue().to_string(),
+            TeamColor::Jaune => text.bright_yellow().to_string(),
+            TeamColor::Cyan => text.bright_cyan().to_string(),
+            TeamColor::Magenta => text.bright_magenta().to_string(),
+        }
     }
 
-    /// Obtient le symbole d'un robot avec couleur
-    fn get_robot_symbol(robot: &Robot) -> String {
+    /// Équipes en compétition et leur couleur
+    fn display_team_legend(teams: &[Team]) {
+        println!("\n🏆 Équipes (robots et dépôts à leur couleur):");
+        for team in teams {
+            println!("  {} - {}", Self::team_colored("■", team.color), team.name);
+        }
+    }
+
+    /// Obtient le symbole d'un robot avec couleur (celle de son équipe en compétition)
+    fn get_robot_symbol(robot: &Robot, teams: &[Team]) -> String {
+        if teams.len() > 1 {
+            let symbol = format!("{}{}", Self::role_letter(robot.behavior), robot.id);
+            return Self::team_colored(&symbol, teams[robot.team].color);
+        }
+
         match robot.behavior {
             crate::RobotBehavior::Explorateur => {
                 format!("E{}", robot.id).bright_green().to_string()
@@ -63,16 +100,36 @@ impl DisplayEngine {
         }
     }
 
+    fn role_letter(behavior: crate::RobotBehavior) -> char {
+        match behavior {
+            crate::RobotBehavior::Explorateur => 'E',
+            crate::RobotBehavior::Collecteur => 'C',
+            crate::RobotBehavior::Scientifique => 'S',
+        }
+    }
+
+    /// Obtient le symbole d'un dépôt avec couleur
+    fn get_depot_symbol(depot: &Depot, teams: &[Team]) -> String {
+        let symbol = format!("{}{}", depot.kind.symbol(), depot.id);
+        if teams.len() > 1 {
+            return Self::team_colored(&symbol, teams[depot.team].color);
+        }
+        match depot.kind {
+            DepotKind::Station => symbol.bright_magenta().bold().to_string(),
+            DepotKind::Avantposte => symbol.magenta().to_string(),
+        }
+    }
+
     /// Obtient le symbole d'une cellule avec couleur
     fn get_cell_symbol(cell: &Cell) -> String {
         // Priorité : ressources > terrain exploré > terrain normal
         if !cell.resources.is_empty() {
             // Afficher la ressource la plus importante
-            if cell.resources.contains_key(&ResourceType::LieuxInteret) {
+            if cell.resources.contains(ResourceType::LieuxInteret) {
                 "🔬".bright_magenta().to_string()
-            } else if cell.resources.contains_key(&ResourceType::Energie) {
+            } else if cell.resources.contains(ResourceType::Energie) {
                 "⚡".bright_cyan().to_string()
-            } else if cell.resources.contains_key(&ResourceType::Mineraux) {
+            } else if cell.resources.contains(ResourceType::Mineraux) {
                 "💎".bright_red().to_string()
             } else {
                 Self::get_terrain_symbol(&cell.terrain, cell.explored)
@@ -91,7 +148,7 @@ impl DisplayEngine {
             TerrainType::Obstacle => "#",
         };
 
-        let colored_symbol = match terrain {
+        match terrain {
             TerrainType::Plaine => {
                 if explored {
                     symbol.bright_white().to_string()
@@ -102,9 +159,7 @@ impl DisplayEngine {
             TerrainType::Montagne => symbol.bright_black().to_string(),
             TerrainType::Cratere => symbol.yellow().to_string(),
             TerrainType::Obstacle => symbol.red().to_string(),
-        };
-
-        colored_symbol
+        }
     }
 
     /// Affiche la légende
@@ -115,6 +170,10 @@ impl DisplayEngine {
         println!("  {} - Collecteur", "C#".bright_yellow());
         println!("  {} - Scientifique", "S#".bright_blue());
 
+        println!("\n🏠 Dépôts:");
+        println!("  {} - Station", "H#".bright_magenta().bold());
+        println!("  {} - Avant-poste", "h#".magenta());
+
         println!("\n🗺️  Terrains:");
         println!("  {} - Plaine", ".".white());
         println!("  {} - Plaine explorée", "·".bright_white());
@@ -129,7 +188,7 @@ impl DisplayEngine {
     }
 
     /// Affiche les statistiques des robots
-    pub fn display_robot_stats(robots: &Vec<Robot>) {
+    pub fn display_robot_stats(robots: &[Robot]) {
         println!("\n{}", "=== ÉTAT DES ROBOTS ===".bright_cyan().bold());
 
         for robot in robots {
@@ -151,7 +210,7 @@ impl DisplayEngine {
                 total_resources
             );
 
-            // Détail des ressources si le robot en a
+            // Détail des ressources si le robot en a, toujours dans le même ordre
             if total_resources > 0 {
                 print!("   💼 ");
                 for (resource_type, amount) in &robot.inventory {
@@ -162,8 +221,32 @@ impl DisplayEngine {
                     };
                     print!("{}{} ", resource_symbol, amount);
                 }
-                println!();
+                println!("(masse {}/{})", robot.cargo_mass(), robot.max_inventory);
             }
+
+            if let Some(change) = &robot.role_change {
+                println!("   🔄 {:?} → {:?} au tour {} : {}", change.from, change.to, change.turn, change.reason);
+            }
+
+            // Modules usés, avec leur santé
+            let worn: Vec<String> = robot
+                .module_condition
+                .iter()
+                .map(|(module, condition)| format!("{:?} {}%", module, condition.health))
+                .collect();
+            if !worn.is_empty() {
+                println!("   🔧 {}", worn.join(" │ ").bright_red());
+            }
+        }
+    }
+
+    /// Affiche le cycle jour/nuit et les derniers événements
+    pub fn display_events(events: &EventLog, environment: &Environment, turn: usize) {
+        let period = if environment.is_night(turn) { "🌙 Nuit".blue() } else { "☀️  Jour".yellow() };
+        println!("\n{} │ 🌪️  Tempêtes actives: {}", period, environment.storms.len());
+
+        for event in events.recent(5) {
+            println!("  {}", event.describe().bright_black());
         }
     }
 
@@ -0,0 +1,396 @@
+// src/grid.rs - Grille 2D à stockage contigu (carte de la planète)
+use crate::{Direction, Position};
+use serde::{Deserialize, Serialize};
+use std::ops::{Index, IndexMut};
+
+/// Comportement des bords de la carte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Topology {
+    /// Bords infranchissables
+    #[default]
+    Bornee,
+    /// Bouclage est-ouest (longitude), pôles infranchissables
+    Cylindre,
+    /// Bouclage sur les deux axes
+    Tore,
+}
+
+impl Topology {
+    pub fn wraps_x(&self) -> bool {
+        matches!(self, Topology::Cylindre | Topology::Tore)
+    }
+
+    pub fn wraps_y(&self) -> bool {
+        matches!(self, Topology::Tore)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Topology::Bornee => "bornée",
+            Topology::Cylindre => "cylindre",
+            Topology::Tore => "tore",
+        }
+    }
+}
+
+/// Grille rectangulaire stockée ligne par ligne dans un seul Vec
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawGrid<T>", bound(deserialize = "T: Deserialize<'de>"))]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    topology: Topology,
+    cells: Vec<T>,
+}
+
+// Forme sérialisée, vérifiée à la désérialisation
+#[derive(Deserialize)]
+struct RawGrid<T> {
+    width: usize,
+    height: usize,
+    #[serde(default)]
+    topology: Topology,
+    cells: Vec<T>,
+}
+
+impl<T> TryFrom<RawGrid<T>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(raw: RawGrid<T>) -> Result<Self, Self::Error> {
+        if raw.width.checked_mul(raw.height) != Some(raw.cells.len()) {
+            return Err(format!(
+                "grille {}x{} mais {} cellules",
+                raw.width,
+                raw.height,
+                raw.cells.len()
+            ));
+        }
+
+        Ok(Grid { width: raw.width, height: raw.height, topology: raw.topology, cells: raw.cells })
+    }
+}
+
+/// Décalages des 4 voisins (N, E, S, O)
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Décalages des 8 voisins (cardinaux puis diagonaux)
+const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1), (1, 0), (0, 1), (-1, 0),
+    (1, -1), (1, 1), (-1, 1), (-1, -1),
+];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { width, height, topology: Topology::Bornee, cells: vec![fill; width * height] }
+    }
+
+    /// Construit la grille en appelant `f` pour chaque position, ligne par ligne
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Position) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(Position::new(x, y)));
+            }
+        }
+
+        Grid { width, height, topology: Topology::Bornee, cells }
+    }
+
+    /// Convertit des lignes ; `None` si elles n'ont pas toutes la même largeur
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        let cells = rows.into_iter().flatten().collect();
+        Some(Grid { width, height, topology: Topology::Bornee, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    fn index_of(&self, pos: Position) -> Option<usize> {
+        self.contains(pos).then(|| pos.y * self.width + pos.x)
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.index_of(pos).map(move |i| &mut self.cells[i])
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Parcourt toutes les cellules avec leur position, ligne par ligne
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (Position::new(i % width, i / width), cell))
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Position décalée de (dx, dy) selon la topologie, `None` au-delà d'un bord
+    pub fn offset(&self, pos: Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let x = wrap_axis(pos.x, dx, self.width, self.topology.wraps_x())?;
+        let y = wrap_axis(pos.y, dy, self.height, self.topology.wraps_y())?;
+        Some(Position::new(x, y))
+    }
+
+    // Voisins distincts de `pos` (une petite grille bouclée ramène parfois sur la même case)
+    fn distinct_neighbors<const N: usize>(&self, pos: Position, offsets: &[(isize, isize); N]) -> impl Iterator<Item = Position> + use<N, T> {
+        let mut found = [None; N];
+        for (i, &offset) in offsets.iter().enumerate() {
+            let candidate = self.offset(pos, offset).filter(|&n| n != pos);
+            if !found[..i].contains(&candidate) {
+                found[i] = candidate;
+            }
+        }
+        found.into_iter().flatten()
+    }
+
+    /// Case voisine dans une direction selon la topologie, `None` au-delà d'un bord
+    pub fn neighbor(&self, pos: Position, direction: Direction) -> Option<Position> {
+        self.offset(pos, direction.offset()).filter(|&n| n != pos)
+    }
+
+    /// Voisins orthogonaux présents sur la grille
+    pub fn neighbors4(&self, pos: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.distinct_neighbors(pos, &OFFSETS_4)
+    }
+
+    /// Voisins orthogonaux et diagonaux présents sur la grille
+    pub fn neighbors8(&self, pos: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.distinct_neighbors(pos, &OFFSETS_8)
+    }
+
+    /// Plus court déplacement signé de `from` vers `to`, en passant par les bords bouclés
+    pub fn delta(&self, from: Position, to: Position) -> (isize, isize) {
+        (
+            axis_delta(from.x, to.x, self.width, self.topology.wraps_x()),
+            axis_delta(from.y, to.y, self.height, self.topology.wraps_y()),
+        )
+    }
+
+    /// Distance en déplacements orthogonaux
+    pub fn manhattan_distance(&self, from: Position, to: Position) -> usize {
+        let (dx, dy) = self.delta(from, to);
+        dx.unsigned_abs() + dy.unsigned_abs()
+    }
+
+    /// Distance en déplacements à 8 directions
+    pub fn chebyshev_distance(&self, from: Position, to: Position) -> usize {
+        let (dx, dy) = self.delta(from, to);
+        dx.unsigned_abs().max(dy.unsigned_abs())
+    }
+
+    /// Direction à prendre pour se rapprocher de `to` (`None` si déjà sur place)
+    pub fn direction_toward(&self, from: Position, to: Position) -> Option<Direction> {
+        let (dx, dy) = self.delta(from, to);
+        Direction::from_offset(dx, dy)
+    }
+
+    /// Cellules du rectangle [origin, origin + size[, tronqué aux bords de la grille
+    pub fn region(&self, origin: Position, width: usize, height: usize) -> impl Iterator<Item = (Position, &T)> {
+        let x_end = origin.x.saturating_add(width).min(self.width);
+        let y_end = origin.y.saturating_add(height).min(self.height);
+
+        (origin.y..y_end).flat_map(move |y| {
+            (origin.x..x_end).map(move |x| {
+                let pos = Position::new(x, y);
+                (pos, &self.cells[y * self.width + x])
+            })
+        })
+    }
+}
+
+// Coordonnée décalée sur un axe : bouclée modulo `len` ou bornée à [0, len[
+fn wrap_axis(value: usize, delta: isize, len: usize, wraps: bool) -> Option<usize> {
+    if wraps {
+        Some((value as isize + delta).rem_euclid(len as isize) as usize)
+    } else {
+        value.checked_add_signed(delta).filter(|&v| v < len)
+    }
+}
+
+// Écart signé le plus court sur un axe
+fn axis_delta(from: usize, to: usize, len: usize, wraps: bool) -> isize {
+    let direct = to as isize - from as isize;
+    if !wraps || len == 0 {
+        return direct;
+    }
+    let len = len as isize;
+    let forward = direct.rem_euclid(len);
+    if forward > len / 2 { forward - len } else { forward }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        self.get(pos).unwrap_or_else(|| {
+            panic!("position ({},{}) hors de la grille {}x{}", pos.x, pos.y, self.width, self.height)
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!("position ({},{}) hors de la grille {}x{}", pos.x, pos.y, width, height)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(topology: Topology) -> Grid<u8> {
+        Grid::new(5, 4, 0).with_topology(topology)
+    }
+
+    fn sorted(positions: impl Iterator<Item = Position>) -> Vec<(usize, usize)> {
+        let mut positions: Vec<(usize, usize)> = positions.map(|pos| (pos.x, pos.y)).collect();
+        positions.sort();
+        positions
+    }
+
+    #[test]
+    fn offset_wraps_according_to_topology() {
+        let corner = Position::new(0, 0);
+        let bornee = grid(Topology::Bornee);
+        assert_eq!(bornee.offset(corner, (-1, 0)), None);
+        assert_eq!(bornee.offset(corner, (0, -1)), None);
+        assert_eq!(bornee.offset(corner, (2, 3)), Some(Position::new(2, 3)));
+        assert_eq!(bornee.offset(corner, (5, 0)), None);
+
+        let cylindre = grid(Topology::Cylindre);
+        assert_eq!(cylindre.offset(corner, (-1, 0)), Some(Position::new(4, 0)));
+        assert_eq!(cylindre.offset(corner, (7, 0)), Some(Position::new(2, 0)));
+        assert_eq!(cylindre.offset(corner, (0, -1)), None);
+
+        let tore = grid(Topology::Tore);
+        assert_eq!(tore.offset(corner, (-1, -1)), Some(Position::new(4, 3)));
+        assert_eq!(tore.offset(Position::new(4, 3), (1, 1)), Some(corner));
+
+        // Une position hors de la grille n'a pas de voisin, même sur un tore
+        assert_eq!(tore.offset(Position::new(5, 0), (-1, 0)), None);
+    }
+
+    #[test]
+    fn neighbors_at_edges_and_corners() {
+        let bornee = grid(Topology::Bornee);
+        assert_eq!(sorted(bornee.neighbors4(Position::new(0, 0))), [(0, 1), (1, 0)]);
+        assert_eq!(sorted(bornee.neighbors8(Position::new(0, 0))), [(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(bornee.neighbors4(Position::new(2, 0)).count(), 3);
+        assert_eq!(bornee.neighbors8(Position::new(2, 0)).count(), 5);
+        assert_eq!(bornee.neighbors8(Position::new(2, 2)).count(), 8);
+
+        let cylindre = grid(Topology::Cylindre);
+        assert_eq!(sorted(cylindre.neighbors4(Position::new(0, 0))), [(0, 1), (1, 0), (4, 0)]);
+        assert_eq!(cylindre.neighbors8(Position::new(4, 3)).count(), 5);
+
+        let tore = grid(Topology::Tore);
+        assert_eq!(tore.neighbors4(Position::new(0, 0)).count(), 4);
+        assert_eq!(tore.neighbors8(Position::new(4, 3)).count(), 8);
+
+        // Sur un tore de 2x2, les voisins opposés se confondent
+        let tiny = Grid::new(2, 2, 0).with_topology(Topology::Tore);
+        assert_eq!(sorted(tiny.neighbors4(Position::new(0, 0))), [(0, 1), (1, 0)]);
+        assert_eq!(sorted(tiny.neighbors8(Position::new(0, 0))), [(0, 1), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn region_is_clipped_to_the_grid() {
+        let grid = Grid::from_fn(5, 4, |pos| pos.y * 10 + pos.x);
+        let region: Vec<usize> = grid.region(Position::new(3, 2), 4, 4).map(|(_, &value)| value).collect();
+        assert_eq!(region, [23, 24, 33, 34]);
+        assert_eq!(grid.region(Position::new(1, 1), 2, 1).map(|(pos, _)| (pos.x, pos.y)).collect::<Vec<_>>(), [(1, 1), (2, 1)]);
+        assert_eq!(grid.region(Position::new(5, 0), 3, 3).count(), 0);
+        assert_eq!(grid.region(Position::new(0, 0), usize::MAX, usize::MAX).count(), 20);
+    }
+
+    #[test]
+    fn delta_and_direction_take_the_short_way_around() {
+        let (west, east) = (Position::new(0, 1), Position::new(4, 1));
+
+        let bornee = grid(Topology::Bornee);
+        assert_eq!(bornee.delta(west, east), (4, 0));
+        assert_eq!(bornee.direction_toward(west, east), Some(Direction::Est));
+
+        let cylindre = grid(Topology::Cylindre);
+        assert_eq!(cylindre.delta(west, east), (-1, 0));
+        assert_eq!(cylindre.direction_toward(west, east), Some(Direction::Ouest));
+        assert_eq!(cylindre.manhattan_distance(west, east), 1);
+        assert_eq!(cylindre.direction_toward(Position::new(0, 0), Position::new(4, 3)), Some(Direction::SudOuest));
+
+        let tore = grid(Topology::Tore);
+        assert_eq!(tore.delta(Position::new(0, 0), Position::new(4, 3)), (-1, -1));
+        assert_eq!(tore.direction_toward(Position::new(0, 0), Position::new(4, 3)), Some(Direction::NordOuest));
+        assert_eq!(tore.chebyshev_distance(Position::new(0, 0), Position::new(4, 3)), 1);
+        assert_eq!(tore.direction_toward(east, east), None);
+    }
+
+    #[test]
+    fn deserialization_checks_the_cell_count() {
+        let grid: Grid<u8> = serde_json::from_str(r#"{"width": 2, "height": 2, "topology": "tore", "cells": [1, 2, 3, 4]}"#).unwrap();
+        assert_eq!(grid.topology(), Topology::Tore);
+        assert_eq!(grid[Position::new(1, 1)], 4);
+
+        let error = serde_json::from_str::<Grid<u8>>(r#"{"width": 2, "height": 2, "cells": [1, 2, 3]}"#).unwrap_err();
+        assert!(error.to_string().contains("grille 2x2 mais 3 cellules"));
+        assert!(serde_json::from_str::<Grid<u8>>(r#"{"width": 2, "height": 2, "cells": [1, 2, 3, 4, 5]}"#).is_err());
+    }
+}
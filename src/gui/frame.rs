@@ -0,0 +1,322 @@
+// src/gui/frame.rs - Image de l'interface en mémoire et rendu différentiel vers le terminal
+use crossterm::{
+    cursor,
+    queue,
+    style::{Color, Colors, Print, ResetColor, SetBackgroundColor, SetColors, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// Dessine dans une `Frame` avec les mêmes commandes crossterm qu'un `execute!` sur le terminal
+macro_rules! draw {
+    ($frame:expr, $($command:expr),+ $(,)?) => {{
+        $( $crate::gui::frame::FrameCommand::apply($command, &mut *$frame); )+
+    }};
+}
+pub(crate) use draw;
+
+// Case recouverte par la moitié droite d'un caractère double largeur
+const COVERED: char = '\0';
+
+/// Une case de l'écran : caractère (et son sélecteur de variante éventuel) et couleurs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StyledCell {
+    pub symbol: char,
+    pub mark: Option<char>,
+    pub fg: Color,
+    pub bg: Color,
+}
+
+impl StyledCell {
+    pub const BLANK: StyledCell = StyledCell { symbol: ' ', mark: None, fg: Color::Reset, bg: Color::Reset };
+
+    fn is_wide(&self) -> bool {
+        is_wide(self.symbol) || (self.mark == Some(EMOJI_PRESENTATION) && is_emoji_capable(self.symbol))
+    }
+}
+
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+// Emoji et idéogrammes : deux colonnes
+fn is_wide(c: char) -> bool {
+    matches!(c as u32, 0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFF00..=0xFF60 | 0x1F000..=0x1FAFF)
+}
+
+// Symboles étroits par défaut, élargis par le sélecteur U+FE0F (☀️, ⛓️...)
+fn is_emoji_capable(c: char) -> bool {
+    matches!(c as u32, 0x2190..=0x21FF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF)
+}
+
+// Sélecteurs de variante, liant et accents combinants : aucune colonne
+fn is_zero_width(c: char) -> bool {
+    matches!(c as u32, 0x0300..=0x036F | 0x200B..=0x200D | 0xFE00..=0xFE0F)
+}
+
+/// Colonnes occupées par un texte, selon les mêmes règles que `Frame::print`
+pub fn text_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut previous: Option<char> = None;
+    for c in text.chars() {
+        if is_zero_width(c) {
+            if c == EMOJI_PRESENTATION && previous.is_some_and(|p| is_emoji_capable(p) && !is_wide(p)) {
+                width += 1;
+            }
+            continue;
+        }
+        width += if is_wide(c) { 2 } else { 1 };
+        previous = Some(c);
+    }
+    width
+}
+
+/// Image complète de l'écran, composée à chaque rafraîchissement puis comparée à la précédente
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    width: u16,
+    height: u16,
+    cells: Vec<StyledCell>,
+    cursor: (u16, u16),
+    fg: Color,
+    bg: Color,
+}
+
+impl Frame {
+    pub fn new(width: u16, height: u16) -> Self {
+        Frame {
+            width,
+            height,
+            cells: vec![StyledCell::BLANK; width as usize * height as usize],
+            cursor: (0, 0),
+            fg: Color::Reset,
+            bg: Color::Reset,
+        }
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Position d'écriture (colonne, ligne)
+    pub fn position(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+    pub fn cell(&self, column: u16, row: u16) -> Option<&StyledCell> {
+        (column < self.width && row < self.height).then(|| &self.cells[row as usize * self.width as usize + column as usize])
+    }
+
+    fn cell_mut(&mut self, column: u16, row: u16) -> Option<&mut StyledCell> {
+        (column < self.width && row < self.height).then(|| &mut self.cells[row as usize * self.width as usize + column as usize])
+    }
+
+    /// Écrit du texte à la position courante avec les couleurs courantes ; ce qui dépasse est coupé
+    pub fn print(&mut self, text: &str) {
+        for c in text.chars() {
+            let (column, row) = self.cursor;
+            if is_zero_width(c) {
+                self.attach_mark(c);
+                continue;
+            }
+            let wide = is_wide(c);
+            let (fg, bg) = (self.fg, self.bg);
+            if let Some(cell) = self.cell_mut(column, row) {
+                *cell = StyledCell { symbol: c, mark: None, fg, bg };
+            }
+            if wide && let Some(cell) = self.cell_mut(column + 1, row) {
+                *cell = StyledCell { symbol: COVERED, mark: None, fg, bg };
+            }
+            self.cursor.0 = column.saturating_add(if wide { 2 } else { 1 });
+        }
+    }
+
+    // Rattache un caractère sans largeur à la case précédente ; U+FE0F peut l'élargir
+    fn attach_mark(&mut self, mark: char) {
+        let (column, row) = self.cursor;
+        let Some(previous) = column.checked_sub(1) else { return };
+        let start = match self.cell(previous, row) {
+            Some(cell) if cell.symbol == COVERED => previous - 1,
+            Some(_) => previous,
+            None => return,
+        };
+        let Some(cell) = self.cell_mut(start, row) else { return };
+        let widened = !is_wide(cell.symbol) && mark == EMOJI_PRESENTATION && is_emoji_capable(cell.symbol);
+        cell.mark = Some(mark);
+        let (fg, bg) = (cell.fg, cell.bg);
+        if widened {
+            if let Some(cell) = self.cell_mut(start + 1, row) {
+                *cell = StyledCell { symbol: COVERED, mark: None, fg, bg };
+            }
+            self.cursor.0 = column.saturating_add(1);
+        }
+    }
+
+    pub fn move_to(&mut self, column: u16, row: u16) {
+        self.cursor = (column, row);
+    }
+
+    pub fn next_line(&mut self, lines: u16) {
+        self.cursor = (0, self.cursor.1.saturating_add(lines));
+    }
+
+    pub fn set_colors(&mut self, fg: Option<Color>, bg: Option<Color>) {
+        if let Some(fg) = fg {
+            self.fg = fg;
+        }
+        if let Some(bg) = bg {
+            self.bg = bg;
+        }
+    }
+}
+
+/// Commande crossterm rejouée dans une `Frame` (voir `draw!`)
+pub trait FrameCommand {
+    fn apply(self, frame: &mut Frame);
+}
+
+impl<T: Display> FrameCommand for Print<T> {
+    fn apply(self, frame: &mut Frame) {
+        frame.print(&self.0.to_string());
+    }
+}
+
+impl FrameCommand for SetForegroundColor {
+    fn apply(self, frame: &mut Frame) {
+        frame.set_colors(Some(self.0), None);
+    }
+}
+
+impl FrameCommand for SetBackgroundColor {
+    fn apply(self, frame: &mut Frame) {
+        frame.set_colors(None, Some(self.0));
+    }
+}
+
+impl FrameCommand for ResetColor {
+    fn apply(self, frame: &mut Frame) {
+        frame.set_colors(Some(Color::Reset), Some(Color::Reset));
+    }
+}
+
+impl FrameCommand for cursor::MoveTo {
+    fn apply(self, frame: &mut Frame) {
+        frame.move_to(self.0, self.1);
+    }
+}
+
+impl FrameCommand for cursor::MoveToNextLine {
+    fn apply(self, frame: &mut Frame) {
+        frame.next_line(self.0);
+    }
+}
+
+/// Envoie au terminal seulement les cases qui ont changé depuis l'image précédente
+#[derive(Debug, Default)]
+pub struct Renderer {
+    previous: Option<Frame>,
+    /// Octets écrits pour la dernière image
+    pub last_frame_bytes: usize,
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Oublie l'image affichée (terminal redimensionné ou effacé) : la suivante est redessinée en entier
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// Écrit les différences en un seul envoi ; renvoie le nombre d'octets écrits
+    pub fn present(&mut self, frame: Frame, out: &mut impl Write) -> io::Result<usize> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let previous = match self.previous.take() {
+            Some(previous) if previous.size() == frame.size() => previous,
+            // Écran effacé : seules les cases non vides restent à écrire
+            _ => {
+                queue!(buffer, ResetColor, Clear(ClearType::All))?;
+                Frame::new(frame.width, frame.height)
+            }
+        };
+
+        // Position et couleurs connues du terminal, pour n'envoyer que les changements
+        let mut cursor: Option<(u16, u16)> = None;
+        let mut colors: Option<(Color, Color)> = None;
+        for row in 0..frame.height {
+            for column in 0..frame.width {
+                let cell = frame.cell(column, row).copied().unwrap_or(StyledCell::BLANK);
+                if cell.symbol == COVERED || previous.cell(column, row) == Some(&cell) {
+                    continue;
+                }
+                if cursor != Some((column, row)) {
+                    queue!(buffer, cursor::MoveTo(column, row))?;
+                }
+                if colors != Some((cell.fg, cell.bg)) {
+                    queue!(buffer, SetColors(Colors::new(cell.fg, cell.bg)))?;
+                    colors = Some((cell.fg, cell.bg));
+                }
+                queue!(buffer, Print(cell.symbol))?;
+                if let Some(mark) = cell.mark {
+                    queue!(buffer, Print(mark))?;
+                }
+                // Largeur d'un emoji incertaine selon le terminal : on repositionne après
+                cursor = if cell.is_wide() || cell.mark.is_some() { None } else { Some((column + 1, row)) };
+            }
+        }
+        if colors.is_some() {
+            queue!(buffer, ResetColor)?;
+        }
+
+        out.write_all(&buffer)?;
+        out.flush()?;
+        self.previous = Some(frame);
+        self.last_frame_bytes = buffer.len();
+        Ok(buffer.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emoji_take_two_columns_and_keep_their_selector() {
+        let mut frame = Frame::new(12, 2);
+        draw!(&mut frame, SetForegroundColor(Color::Yellow), Print("☀️ Jour"), ResetColor, cursor::MoveToNextLine(1), Print("🏆x"));
+
+        assert_eq!(frame.cell(0, 0).unwrap().mark, Some('\u{FE0F}'));
+        assert_eq!(frame.cell(0, 0).unwrap().fg, Color::Yellow);
+        assert_eq!(frame.cell(3, 0).unwrap().symbol, 'J');
+        assert_eq!(frame.cell(2, 1).unwrap().symbol, 'x');
+        assert_eq!(frame.position(), (3, 1));
+
+        assert_eq!(text_width("☀️ Jour"), 7);
+        assert_eq!(text_width("🎞️  REPLAY"), 10);
+        assert_eq!(text_width("Écran │ é"), 9);
+    }
+
+    #[test]
+    fn only_changed_cells_are_written_again() {
+        let compose = |marker: &str| {
+            let mut frame = Frame::new(80, 30);
+            for row in 0..30 {
+                frame.move_to(0, row);
+                draw!(&mut frame, SetBackgroundColor(Color::DarkGreen), Print("·".repeat(80)), ResetColor);
+            }
+            draw!(&mut frame, cursor::MoveTo(40, 15), SetForegroundColor(Color::Red), Print(marker));
+            frame
+        };
+        let mut renderer = Renderer::new();
+        let mut screen = Vec::new();
+
+        let full = renderer.present(compose("E"), &mut screen).unwrap();
+        let unchanged = renderer.present(compose("E"), &mut screen).unwrap();
+        let one_cell = renderer.present(compose("C"), &mut screen).unwrap();
+        assert_eq!(unchanged, 0);
+        assert!(one_cell > 0 && one_cell * 100 < full, "{} octets contre {}", one_cell, full);
+
+        renderer.invalidate();
+        assert_eq!(renderer.present(compose("C"), &mut screen).unwrap(), full);
+    }
+}
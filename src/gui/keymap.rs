@@ -0,0 +1,384 @@
+// src/gui/keymap.rs - Touches de l'interface temps réel et du replay : une seule table pour le clavier et l'aide affichée
+use crossterm::event::KeyCode;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Délais entre deux tours des préréglages [1-6], en millisecondes
+pub const SPEED_PRESETS: [u64; 6] = [1000, 2000, 3000, 5000, 500, 10000];
+
+/// Action de l'interface déclenchée au clavier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    Quit,
+    TogglePause,
+    Step,
+    SpeedUp,
+    SlowDown,
+    /// Index dans `SPEED_PRESETS`
+    SpeedPreset(usize),
+    ToggleAutoPause,
+    ToggleAutoMode,
+    ToggleMapView,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+    Zoom,
+    ToggleFollow,
+    /// Carte de chaleur suivante (puis retour à la carte seule)
+    CycleOverlay,
+    ExportOverlay,
+    SelectNext,
+    SelectPrevious,
+    SelectById,
+    TogglePilot,
+    PilotNorth,
+    PilotSouth,
+    PilotWest,
+    PilotEast,
+    PilotCollect,
+    PilotAnalyze,
+    PilotCommunicate,
+    PilotWait,
+    ReplayBack,
+    ReplayForward,
+    ReplayStart,
+    ReplayEnd,
+    /// Saisie d'un numéro de tour
+    ReplayJump,
+}
+
+/// Ligne de l'aide où l'action apparaît
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyGroup {
+    Simulation,
+    Carte,
+    Robots,
+    /// Actives seulement pendant la téléopération, prioritaires sur les autres
+    Pilotage,
+    /// Actives seulement dans la visionneuse de replay, prioritaires sur les autres
+    Replay,
+}
+
+impl KeyGroup {
+    // Pilotage et replay forment chacun une couche à part, qui masque la couche générale
+    fn layer(&self) -> Option<KeyGroup> {
+        matches!(self, KeyGroup::Pilotage | KeyGroup::Replay).then_some(*self)
+    }
+}
+
+/// Actions générales reprises par la visionneuse de replay
+const REPLAY_VIEWER_ACTIONS: [KeyAction; 12] = [
+    KeyAction::Quit,
+    KeyAction::TogglePause,
+    KeyAction::SpeedUp,
+    KeyAction::SlowDown,
+    KeyAction::ToggleMapView,
+    KeyAction::ScrollUp,
+    KeyAction::ScrollDown,
+    KeyAction::ScrollLeft,
+    KeyAction::ScrollRight,
+    KeyAction::Zoom,
+    KeyAction::CycleOverlay,
+    KeyAction::ExportOverlay,
+];
+
+// Définition d'une action : nom dans le fichier de configuration, libellé d'aide, touches par défaut.
+// Les actions voisines qui partagent un libellé sont réunies dans l'aide ("[I/J/K/L] Défiler").
+struct ActionSpec {
+    action: KeyAction,
+    group: KeyGroup,
+    name: &'static str,
+    label: &'static str,
+    keys: &'static [KeyCode],
+}
+
+const SPEED_LABEL: &str = "Vitesse 1s/2s/3s/5s/0.5s/10s";
+
+const ACTIONS: [ActionSpec; 39] = [
+    ActionSpec { action: KeyAction::Quit, group: KeyGroup::Simulation, name: "quit", label: "Quitter", keys: &[KeyCode::Char('q'), KeyCode::Esc] },
+    ActionSpec { action: KeyAction::TogglePause, group: KeyGroup::Simulation, name: "pause", label: "Pause", keys: &[KeyCode::Char(' ')] },
+    ActionSpec { action: KeyAction::Step, group: KeyGroup::Simulation, name: "step", label: "Tour suivant (en pause)", keys: &[KeyCode::Enter] },
+    ActionSpec { action: KeyAction::SpeedUp, group: KeyGroup::Simulation, name: "speed_up", label: "Accélérer", keys: &[KeyCode::Char('+'), KeyCode::Up] },
+    ActionSpec { action: KeyAction::SlowDown, group: KeyGroup::Simulation, name: "slow_down", label: "Ralentir", keys: &[KeyCode::Char('-'), KeyCode::Down] },
+    ActionSpec { action: KeyAction::SpeedPreset(0), group: KeyGroup::Simulation, name: "speed_1", label: SPEED_LABEL, keys: &[KeyCode::Char('1')] },
+    ActionSpec { action: KeyAction::SpeedPreset(1), group: KeyGroup::Simulation, name: "speed_2", label: SPEED_LABEL, keys: &[KeyCode::Char('2')] },
+    ActionSpec { action: KeyAction::SpeedPreset(2), group: KeyGroup::Simulation, name: "speed_3", label: SPEED_LABEL, keys: &[KeyCode::Char('3')] },
+    ActionSpec { action: KeyAction::SpeedPreset(3), group: KeyGroup::Simulation, name: "speed_4", label: SPEED_LABEL, keys: &[KeyCode::Char('4')] },
+    ActionSpec { action: KeyAction::SpeedPreset(4), group: KeyGroup::Simulation, name: "speed_5", label: SPEED_LABEL, keys: &[KeyCode::Char('5')] },
+    ActionSpec { action: KeyAction::SpeedPreset(5), group: KeyGroup::Simulation, name: "speed_6", label: SPEED_LABEL, keys: &[KeyCode::Char('6')] },
+    ActionSpec { action: KeyAction::ToggleAutoPause, group: KeyGroup::Simulation, name: "auto_pause", label: "Pause auto (perte d'un robot, site analysé)", keys: &[KeyCode::Char('p')] },
+    ActionSpec { action: KeyAction::ToggleAutoMode, group: KeyGroup::Simulation, name: "auto_mode", label: "Auto/Manuel", keys: &[KeyCode::Char('a')] },
+    ActionSpec { action: KeyAction::ToggleMapView, group: KeyGroup::Carte, name: "map_view", label: "Terrain/Relief", keys: &[KeyCode::Char('h')] },
+    ActionSpec { action: KeyAction::ScrollUp, group: KeyGroup::Carte, name: "scroll_up", label: "Défiler", keys: &[KeyCode::Char('i')] },
+    ActionSpec { action: KeyAction::ScrollLeft, group: KeyGroup::Carte, name: "scroll_left", label: "Défiler", keys: &[KeyCode::Char('j')] },
+    ActionSpec { action: KeyAction::ScrollDown, group: KeyGroup::Carte, name: "scroll_down", label: "Défiler", keys: &[KeyCode::Char('k')] },
+    ActionSpec { action: KeyAction::ScrollRight, group: KeyGroup::Carte, name: "scroll_right", label: "Défiler", keys: &[KeyCode::Char('l')] },
+    ActionSpec { action: KeyAction::Zoom, group: KeyGroup::Carte, name: "zoom", label: "Zoom", keys: &[KeyCode::Char('z')] },
+    ActionSpec { action: KeyAction::ToggleFollow, group: KeyGroup::Carte, name: "follow", label: "Suivre le robot", keys: &[KeyCode::Char('f')] },
+    ActionSpec { action: KeyAction::CycleOverlay, group: KeyGroup::Carte, name: "overlay", label: "Chaleur", keys: &[KeyCode::Char('v')] },
+    ActionSpec { action: KeyAction::ExportOverlay, group: KeyGroup::Carte, name: "export_overlay", label: "Exporter (CSV)", keys: &[KeyCode::Char('x')] },
+    ActionSpec { action: KeyAction::SelectNext, group: KeyGroup::Robots, name: "select_next", label: "Choisir un robot", keys: &[KeyCode::Tab, KeyCode::Right] },
+    ActionSpec { action: KeyAction::SelectPrevious, group: KeyGroup::Robots, name: "select_previous", label: "Choisir un robot", keys: &[KeyCode::BackTab, KeyCode::Left] },
+    ActionSpec { action: KeyAction::SelectById, group: KeyGroup::Robots, name: "select_by_id", label: "Robot par numéro", keys: &[KeyCode::Char('#')] },
+    ActionSpec { action: KeyAction::TogglePilot, group: KeyGroup::Robots, name: "pilot", label: "Manuel/Autonome", keys: &[KeyCode::Char('m')] },
+    ActionSpec { action: KeyAction::PilotNorth, group: KeyGroup::Pilotage, name: "pilot_north", label: "Avancer", keys: &[KeyCode::Up] },
+    ActionSpec { action: KeyAction::PilotSouth, group: KeyGroup::Pilotage, name: "pilot_south", label: "Avancer", keys: &[KeyCode::Down] },
+    ActionSpec { action: KeyAction::PilotWest, group: KeyGroup::Pilotage, name: "pilot_west", label: "Avancer", keys: &[KeyCode::Left] },
+    ActionSpec { action: KeyAction::PilotEast, group: KeyGroup::Pilotage, name: "pilot_east", label: "Avancer", keys: &[KeyCode::Right] },
+    ActionSpec { action: KeyAction::PilotCollect, group: KeyGroup::Pilotage, name: "pilot_collect", label: "Collecter", keys: &[KeyCode::Char('c')] },
+    ActionSpec { action: KeyAction::PilotAnalyze, group: KeyGroup::Pilotage, name: "pilot_analyze", label: "Étudier", keys: &[KeyCode::Char('e')] },
+    ActionSpec { action: KeyAction::PilotCommunicate, group: KeyGroup::Pilotage, name: "pilot_communicate", label: "Transmettre", keys: &[KeyCode::Char('t')] },
+    ActionSpec { action: KeyAction::PilotWait, group: KeyGroup::Pilotage, name: "pilot_wait", label: "Attendre", keys: &[KeyCode::Char('w')] },
+    ActionSpec { action: KeyAction::ReplayBack, group: KeyGroup::Replay, name: "replay_back", label: "Tour précédent/suivant", keys: &[KeyCode::Left] },
+    ActionSpec { action: KeyAction::ReplayForward, group: KeyGroup::Replay, name: "replay_forward", label: "Tour précédent/suivant", keys: &[KeyCode::Right] },
+    ActionSpec { action: KeyAction::ReplayStart, group: KeyGroup::Replay, name: "replay_start", label: "Aller au début/à la fin", keys: &[KeyCode::Home] },
+    ActionSpec { action: KeyAction::ReplayEnd, group: KeyGroup::Replay, name: "replay_end", label: "Aller au début/à la fin", keys: &[KeyCode::End] },
+    ActionSpec { action: KeyAction::ReplayJump, group: KeyGroup::Replay, name: "replay_jump", label: "Aller au tour N (chiffres + ENTER)", keys: &[KeyCode::Char('g')] },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyMapError {
+    UnknownAction(String),
+    UnknownKey { action: String, key: String },
+    /// Deux actions actives en même temps sur la même touche
+    Conflict { key: String, first: String, second: String },
+}
+
+impl fmt::Display for KeyMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyMapError::UnknownAction(action) => write!(f, "action inconnue '{}'", action),
+            KeyMapError::UnknownKey { action, key } => write!(f, "touche inconnue '{}' pour '{}'", key, action),
+            KeyMapError::Conflict { key, first, second } => {
+                write!(f, "touche {} attribuée à la fois à '{}' et à '{}'", key, first, second)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyMapError {}
+
+/// Disposition du clavier : touches de chaque action de `ACTIONS`, dans le même ordre
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMap {
+    keys: Vec<Vec<KeyCode>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap { keys: ACTIONS.iter().map(|spec| spec.keys.to_vec()).collect() }
+    }
+}
+
+impl KeyMap {
+    /// Disposition par défaut, modifiée par la table `[keys]` d'un scénario (`pause = ["p"]`) :
+    /// les touches données remplacent celles de l'action
+    pub fn from_config(config: &BTreeMap<String, Vec<String>>) -> Result<KeyMap, KeyMapError> {
+        let mut keymap = KeyMap::default();
+        for (name, keys) in config {
+            let index = ACTIONS
+                .iter()
+                .position(|spec| spec.name == name)
+                .ok_or_else(|| KeyMapError::UnknownAction(name.clone()))?;
+            keymap.keys[index] = keys
+                .iter()
+                .map(|key| parse_key(key).ok_or_else(|| KeyMapError::UnknownKey { action: name.clone(), key: key.clone() }))
+                .collect::<Result<_, _>>()?;
+        }
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    // Pilotage et replay à part : leurs touches masquent volontairement celles des autres groupes
+    fn check_conflicts(&self) -> Result<(), KeyMapError> {
+        let mut seen: Vec<(KeyCode, Option<KeyGroup>, usize)> = Vec::new();
+        for (index, keys) in self.keys.iter().enumerate() {
+            let layer = ACTIONS[index].group.layer();
+            for key in keys {
+                if let Some((_, _, other)) = seen.iter().find(|(seen_key, seen_layer, _)| seen_key == key && *seen_layer == layer) {
+                    return Err(KeyMapError::Conflict {
+                        key: key_label(*key),
+                        first: ACTIONS[*other].name.to_string(),
+                        second: ACTIONS[index].name.to_string(),
+                    });
+                }
+                seen.push((*key, layer, index));
+            }
+        }
+        Ok(())
+    }
+
+    // Première action retenue par `keep` à laquelle la touche est attribuée
+    fn find(&self, key: KeyCode, keep: impl Fn(&ActionSpec) -> bool) -> Option<KeyAction> {
+        ACTIONS
+            .iter()
+            .zip(&self.keys)
+            .find(|(spec, keys)| keep(spec) && keys.contains(&key))
+            .map(|(spec, _)| spec.action)
+    }
+
+    /// Action d'une touche ; pendant la téléopération, les touches de pilotage passent en premier
+    pub fn action(&self, key: KeyCode, piloting: bool) -> Option<KeyAction> {
+        let pilot = piloting.then(|| self.find(key, |spec| spec.group == KeyGroup::Pilotage)).flatten();
+        pilot.or_else(|| self.find(key, |spec| spec.group.layer().is_none()))
+    }
+
+    /// Action d'une touche dans la visionneuse de replay : ses touches propres, puis les actions générales qu'elle reprend
+    pub fn replay_action(&self, key: KeyCode) -> Option<KeyAction> {
+        self.find(key, |spec| spec.group == KeyGroup::Replay)
+            .or_else(|| self.find(key, |spec| REPLAY_VIEWER_ACTIONS.contains(&spec.action)))
+    }
+
+    /// Aide d'un groupe : "[touches] libellé" pour chaque libellé, dans l'ordre de la table
+    pub fn help(&self, group: KeyGroup) -> Vec<String> {
+        self.help_where(|spec| spec.group == group)
+    }
+
+    /// Aide de la visionneuse de replay : actions générales reprises, puis celles du replay
+    pub fn replay_help(&self) -> Vec<String> {
+        self.help_where(|spec| spec.group == KeyGroup::Replay || REPLAY_VIEWER_ACTIONS.contains(&spec.action))
+    }
+
+    fn help_where(&self, keep: impl Fn(&ActionSpec) -> bool) -> Vec<String> {
+        let mut entries: Vec<(&str, Vec<String>)> = Vec::new();
+        for (spec, keys) in ACTIONS.iter().zip(&self.keys).filter(|(spec, _)| keep(spec)) {
+            let labels = keys.iter().map(|key| key_label(*key));
+            match entries.last_mut() {
+                Some((label, shown)) if *label == spec.label => shown.extend(labels),
+                _ => entries.push((spec.label, labels.collect())),
+            }
+        }
+        entries
+            .into_iter()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(label, keys)| format!("[{}] {}", keys.join("/"), label))
+            .collect()
+    }
+}
+
+// Noms acceptés dans le fichier de configuration (français ou anglais), sinon un caractère seul
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let key = match name.to_lowercase().as_str() {
+        "espace" | "space" => KeyCode::Char(' '),
+        "entree" | "entrée" | "enter" => KeyCode::Enter,
+        "echap" | "échap" | "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "maj+tab" | "backtab" => KeyCode::BackTab,
+        "haut" | "up" => KeyCode::Up,
+        "bas" | "down" => KeyCode::Down,
+        "gauche" | "left" => KeyCode::Left,
+        "droite" | "right" => KeyCode::Right,
+        "debut" | "début" | "home" => KeyCode::Home,
+        "fin" | "end" => KeyCode::End,
+        "pageprec" | "pageup" => KeyCode::PageUp,
+        "pagesuiv" | "pagedown" => KeyCode::PageDown,
+        "retour" | "backspace" => KeyCode::Backspace,
+        other => {
+            if let Some(number) = other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok())
+                && (1..=12).contains(&number)
+            {
+                return Some(KeyCode::F(number));
+            }
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some(key)
+}
+
+/// Nom d'une touche dans l'aide affichée
+pub fn key_label(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "ESPACE".to_string(),
+        KeyCode::Char(c) => c.to_uppercase().to_string(),
+        KeyCode::Enter => "ENTER".to_string(),
+        KeyCode::Esc => "ESC".to_string(),
+        KeyCode::Tab => "TAB".to_string(),
+        KeyCode::BackTab => "MAJ+TAB".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Home => "DÉBUT".to_string(),
+        KeyCode::End => "FIN".to_string(),
+        KeyCode::PageUp => "PAGEPREC".to_string(),
+        KeyCode::PageDown => "PAGESUIV".to_string(),
+        KeyCode::Backspace => "RETOUR".to_string(),
+        KeyCode::F(number) => format!("F{}", number),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(action, keys)| (action.to_string(), keys.iter().map(|key| key.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn default_keys_resolve_with_pilot_keys_first() {
+        let keymap = KeyMap::default();
+        assert_eq!(keymap.check_conflicts(), Ok(()));
+        assert_eq!(keymap.action(KeyCode::Char('3'), false), Some(KeyAction::SpeedPreset(2)));
+        assert_eq!(keymap.action(KeyCode::Up, false), Some(KeyAction::SpeedUp));
+        assert_eq!(keymap.action(KeyCode::Up, true), Some(KeyAction::PilotNorth));
+        assert_eq!(keymap.action(KeyCode::Char('c'), false), None);
+        assert_eq!(keymap.action(KeyCode::Char(' '), true), Some(KeyAction::TogglePause));
+
+        let help = keymap.help(KeyGroup::Simulation);
+        assert!(help.contains(&"[1/2/3/4/5/6] Vitesse 1s/2s/3s/5s/0.5s/10s".to_string()));
+        assert!(help.contains(&"[Q/ESC] Quitter".to_string()));
+        assert_eq!(keymap.help(KeyGroup::Carte)[1], "[I/J/K/L] Défiler");
+    }
+
+    #[test]
+    fn replay_keys_mask_selection_and_skip_live_only_actions() {
+        let keymap = KeyMap::default();
+        assert_eq!(keymap.action(KeyCode::Right, false), Some(KeyAction::SelectNext));
+        assert_eq!(keymap.action(KeyCode::Char('g'), false), None);
+        assert_eq!(keymap.replay_action(KeyCode::Right), Some(KeyAction::ReplayForward));
+        assert_eq!(keymap.replay_action(KeyCode::Char('g')), Some(KeyAction::ReplayJump));
+        assert_eq!(keymap.replay_action(KeyCode::Char('z')), Some(KeyAction::Zoom));
+        assert_eq!(keymap.replay_action(KeyCode::Enter), None);
+        assert_eq!(keymap.replay_action(KeyCode::Char('m')), None);
+
+        let help = keymap.replay_help();
+        assert!(help.contains(&"[←/→] Tour précédent/suivant".to_string()));
+        assert!(help.contains(&"[DÉBUT/FIN] Aller au début/à la fin".to_string()));
+        assert!(!help.iter().any(|entry| entry.contains("Auto/Manuel")));
+
+        let remapped = KeyMap::from_config(&config(&[("replay_jump", &["t"]), ("quit", &["F10"])])).unwrap();
+        assert_eq!(remapped.replay_action(KeyCode::Char('t')), Some(KeyAction::ReplayJump));
+        assert_eq!(remapped.replay_action(KeyCode::Char('q')), None);
+        assert!(remapped.replay_help().contains(&"[F10] Quitter".to_string()));
+    }
+
+    #[test]
+    fn config_replaces_keys_and_help_follows() {
+        let keymap = KeyMap::from_config(&config(&[("auto_pause", &["F2"]), ("scroll_up", &["haut"]), ("speed_up", &["+"])])).unwrap();
+        assert_eq!(keymap.action(KeyCode::F(2), false), Some(KeyAction::ToggleAutoPause));
+        assert_eq!(keymap.action(KeyCode::Char('p'), false), None);
+        assert_eq!(keymap.action(KeyCode::Up, false), Some(KeyAction::ScrollUp));
+        assert!(keymap.help(KeyGroup::Simulation).contains(&"[F2] Pause auto (perte d'un robot, site analysé)".to_string()));
+        assert_eq!(keymap.help(KeyGroup::Carte)[1], "[↑/J/K/L] Défiler");
+    }
+
+    #[test]
+    fn invalid_config_is_rejected() {
+        assert_eq!(KeyMap::from_config(&config(&[("voler", &["v"])])), Err(KeyMapError::UnknownAction("voler".to_string())));
+        assert!(matches!(KeyMap::from_config(&config(&[("zoom", &["ctrl+z"])])), Err(KeyMapError::UnknownKey { .. })));
+        // 'h' sert déjà à changer de vue
+        assert!(matches!(KeyMap::from_config(&config(&[("zoom", &["h"])])), Err(KeyMapError::Conflict { .. })));
+        // Une touche de pilotage peut masquer une touche générale
+        assert!(KeyMap::from_config(&config(&[("pilot_wait", &["a"])])).is_ok());
+    }
+}
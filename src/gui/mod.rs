@@ -0,0 +1,1321 @@
+use crate::{Cell, Direction, TerrainType, ResourceType, Position};
+use crate::{Grid, Topology};
+use crate::map::{SpatialIndex, MAX_ELEVATION};
+use crate::robot::{Robot, RobotAction};
+use crate::simulation::{
+    export_heat_maps, objective_progress, team_scores, Command, Depot, DepotKind, Environment, EventKind, EventLog, HeatLayer,
+    HeatMap, MissionStatus, ReplayPlayer, SimulationEngine, Team, TeamColor, ROLE_CHANGE_NOTICE_TURNS,
+};
+mod frame;
+mod keymap;
+
+pub use keymap::{KeyAction, KeyGroup, KeyMap, KeyMapError, SPEED_PRESETS};
+use frame::{draw, text_width, Frame, Renderer};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    cursor,
+    style::{Color, Print, ResetColor, SetForegroundColor, SetBackgroundColor},
+};
+use std::io::{self, stdout};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Palette du relief, des cuvettes aux sommets
+const ELEVATION_COLORS: [Color; 8] = [
+    Color::DarkBlue,
+    Color::Blue,
+    Color::DarkGreen,
+    Color::Green,
+    Color::DarkYellow,
+    Color::Yellow,
+    Color::Red,
+    Color::White,
+];
+
+/// Dégradé des cartes de chaleur, du plus froid au plus chaud
+const HEAT_COLORS: [Color; 6] = [Color::DarkBlue, Color::Blue, Color::Cyan, Color::Green, Color::Yellow, Color::Red];
+
+/// Pas de [+/-] et bornes du délai entre deux tours, en millisecondes
+const SPEED_STEP: u64 = 200;
+const MIN_SPEED: u64 = 100;
+const MAX_SPEED: u64 = 10000;
+
+/// Nombre d'événements récents affichés sous la carte
+const EVENTS_SHOWN: usize = 4;
+
+/// Largeur du panneau du robot sélectionné, à droite de la carte quand le terminal le permet
+const PANEL_WIDTH: u16 = 40;
+
+/// Colonnes prises par les numéros de ligne et les bordures de la carte
+const MAP_MARGIN: usize = 7;
+/// Lignes de l'interface hors carte, sans compter les robots, relais et réaffectations
+/// (en-tête 6, cadre de la carte 5, journal 5, titre des robots 2, légende et contrôles 12, marge 1)
+const INTERFACE_ROWS: usize = 31;
+/// Fenêtre minimale, même dans un terminal minuscule
+const MIN_VIEW_COLUMNS: usize = 20;
+const MIN_VIEW_ROWS: usize = 5;
+/// Cases par caractère, de la vue détaillée à la vue d'ensemble
+const ZOOM_LEVELS: [usize; 4] = [1, 2, 4, 8];
+
+/// Couche affichée sur la carte
+#[derive(Clone, Copy, PartialEq)]
+enum MapView {
+    Terrain,
+    Elevation,
+}
+
+/// Partie de la carte affichée : origine en cases, taille en caractères, cases résumées par caractère
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Viewport {
+    x: usize,
+    y: usize,
+    columns: usize,
+    rows: usize,
+    zoom: usize,
+    /// Recentrée à chaque image sur le robot sélectionné
+    follow: bool,
+}
+
+impl Viewport {
+    fn new() -> Self {
+        Viewport { x: 0, y: 0, columns: MIN_VIEW_COLUMNS, rows: MIN_VIEW_ROWS, zoom: 1, follow: false }
+    }
+
+    /// Adapte la taille à la place disponible et garde la fenêtre dans la carte
+    fn fit(&mut self, width: usize, height: usize, max_columns: usize, max_rows: usize) {
+        self.columns = width.div_ceil(self.zoom).min(max_columns).max(1);
+        self.rows = height.div_ceil(self.zoom).min(max_rows).max(1);
+        self.x = self.x.min(width.saturating_sub(self.columns * self.zoom));
+        self.y = self.y.min(height.saturating_sub(self.rows * self.zoom));
+    }
+
+    /// Place la case au centre (la fenêtre est ramenée dans la carte au prochain `fit`)
+    fn center_on(&mut self, pos: Position) {
+        self.x = pos.x.saturating_sub(self.columns * self.zoom / 2);
+        self.y = pos.y.saturating_sub(self.rows * self.zoom / 2);
+    }
+
+    /// Défilement d'un quart de fenêtre ; interrompt le suivi
+    fn scroll(&mut self, dx: isize, dy: isize) {
+        self.x = self.x.saturating_add_signed(dx * (self.columns * self.zoom / 4).max(1) as isize);
+        self.y = self.y.saturating_add_signed(dy * (self.rows * self.zoom / 4).max(1) as isize);
+        self.follow = false;
+    }
+
+    /// Niveau de zoom suivant, autour du même centre
+    fn cycle_zoom(&mut self) {
+        let center = Position::new(self.x + self.columns * self.zoom / 2, self.y + self.rows * self.zoom / 2);
+        let level = ZOOM_LEVELS.iter().position(|zoom| *zoom == self.zoom).unwrap_or(0);
+        self.zoom = ZOOM_LEVELS[(level + 1) % ZOOM_LEVELS.len()];
+        self.center_on(center);
+    }
+
+    fn shows_all(&self, width: usize, height: usize) -> bool {
+        self.zoom == 1 && self.columns >= width && self.rows >= height
+    }
+
+    /// Dernière case visible (coin inférieur droit)
+    fn last_cell(&self, width: usize, height: usize) -> (usize, usize) {
+        ((self.x + self.columns * self.zoom).min(width) - 1, (self.y + self.rows * self.zoom).min(height) - 1)
+    }
+}
+
+/// Caractère affiché pour une case ou un bloc de cases
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Glyph {
+    symbol: &'static str,
+    fg: Color,
+    bg: Option<Color>,
+}
+
+// Ce qu'il faut pour dessiner une case de la carte
+struct MapScene<'a> {
+    map: &'a Grid<Cell>,
+    robots: &'a [Robot],
+    robot_positions: &'a SpatialIndex,
+    depots: &'a [Depot],
+    teams: &'a [Team],
+    environment: &'a Environment,
+    selected: Option<usize>,
+    view: MapView,
+    heat: Option<&'a HeatMap>,
+}
+
+impl MapScene<'_> {
+    /// Bloc de `zoom`×`zoom` cases : l'élément le plus important s'il y en a un (robot, base,
+    /// ressource), sinon le sol le plus répandu
+    fn block_glyph(&self, origin: Position, zoom: usize) -> Glyph {
+        let mut feature: Option<(u8, Glyph)> = None;
+        let mut ground: Vec<(Glyph, usize)> = Vec::new();
+        for dy in 0..zoom {
+            for dx in 0..zoom {
+                let pos = Position::new(origin.x + dx, origin.y + dy);
+                let Some(cell) = self.map.get(pos) else { continue };
+                match self.cell_glyph(pos, cell) {
+                    (0, glyph) => match ground.iter_mut().find(|(seen, _)| *seen == glyph) {
+                        Some((_, count)) => *count += 1,
+                        None => ground.push((glyph, 1)),
+                    },
+                    (rank, glyph) => {
+                        if feature.is_none_or(|(best, _)| rank > best) {
+                            feature = Some((rank, glyph));
+                        }
+                    }
+                }
+            }
+        }
+        let (rank, mut glyph) = feature
+            .or_else(|| ground.iter().max_by_key(|(_, count)| *count).map(|(glyph, _)| (0, *glyph)))
+            .unwrap_or((0, Glyph { symbol: " ", fg: Color::Reset, bg: None }));
+        // Carte de chaleur : fond selon la valeur la plus forte du bloc, robots et bases gardent le leur
+        if let Some(heat) = self.heat
+            && rank < 5
+            && let Some(color) = heat_color(heat, origin, zoom)
+        {
+            glyph.bg = Some(color);
+        }
+        glyph
+    }
+
+    // Glyphe d'une case et son importance (0 : simple sol)
+    fn cell_glyph(&self, pos: Position, cell: &Cell) -> (u8, Glyph) {
+        // Priorité 1: Robots (toujours visibles)
+        // Plusieurs robots sur la case : le dernier de la liste, comme au tour précédent
+        if let Some(robot) = self.robot_positions.at(pos).max().map(|index| &self.robots[index]) {
+            // En compétition, le fond donne l'équipe et la lettre le rôle ; le robot sélectionné ressort en blanc
+            let (rank, bg) = if self.selected == Some(robot.id) {
+                (7, Color::White)
+            } else if self.teams.len() > 1 {
+                (6, team_color(self.teams[robot.team].color))
+            } else {
+                (6, role_color(robot.behavior))
+            };
+            return (rank, Glyph { symbol: role_letter(robot.behavior), fg: Color::Black, bg: Some(bg) });
+        }
+
+        // Priorité 2: Dépôts (réseau de stations et avant-postes)
+        if let Some(depot) = self.depots.iter().find(|depot| depot.position == pos) {
+            let bg = match depot.kind {
+                DepotKind::Station => Color::Magenta,
+                DepotKind::Avantposte => Color::DarkMagenta,
+            };
+            let fg = if self.teams.len() > 1 { team_color(self.teams[depot.team].color) } else { Color::White };
+            return (5, Glyph { symbol: depot.kind.symbol(), fg, bg: Some(bg) });
+        }
+
+        // Vue relief : couleur de fond selon l'élévation
+        if self.view == MapView::Elevation {
+            let symbol = if cell.is_passable() { " " } else { "#" };
+            return (0, Glyph { symbol, fg: Color::Black, bg: Some(elevation_color(cell.elevation)) });
+        }
+
+        // Tempête de poussière : fond ocre sous le symbole
+        let bg = self.environment.in_storm(self.map, pos).then_some(Color::DarkYellow);
+
+        // Priorité 3: Ressources importantes seulement
+        if cell.resources.contains(ResourceType::LieuxInteret) {
+            return (4, Glyph { symbol: "*", fg: Color::Magenta, bg });
+        }
+        if cell.resources.amount(ResourceType::Energie) > 30 {
+            return (3, Glyph { symbol: "E", fg: Color::Cyan, bg });
+        }
+        if cell.resources.amount(ResourceType::Mineraux) > 40 {
+            return (2, Glyph { symbol: "M", fg: Color::Red, bg });
+        }
+
+        // Priorité 4: Terrain simplifié
+        let (symbol, fg) = match cell.terrain {
+            TerrainType::Obstacle => ("#", Color::Red),
+            TerrainType::Montagne => ("^", Color::DarkGrey),
+            TerrainType::Cratere => ("o", Color::Yellow),
+            TerrainType::Plaine if cell.explored => ("·", Color::DarkGrey),
+            TerrainType::Plaine => (" ", Color::Reset),
+        };
+        (0, Glyph { symbol, fg, bg })
+    }
+}
+
+// Carte de chaleur affichée, recalculée seulement quand la couche, le tour ou le journal change
+// (une commande passée en pause ajoute un événement sans changer de tour)
+struct HeatCache {
+    turn: usize,
+    events: usize,
+    map: HeatMap,
+}
+
+pub struct GuiEngine {
+    last_update: Instant,
+    paused: bool,
+    speed: u64, // millisecondes entre les updates
+    map_view: MapView,
+    /// Robot sélectionné (identifiant), détaillé dans le panneau latéral
+    selected: Option<usize>,
+    /// Saisie en cours d'un numéro de robot pour [#]
+    id_input: Option<String>,
+    viewport: Viewport,
+    renderer: Renderer,
+    keymap: KeyMap,
+    /// Met la simulation en pause à la perte d'un robot ou à la fin de l'analyse d'un site
+    auto_pause: bool,
+    /// Carte de chaleur superposée à la carte
+    overlay: Option<HeatLayer>,
+    heat: Option<HeatCache>,
+    /// Résultat du dernier export, affiché avec les contrôles
+    notice: Option<String>,
+}
+
+impl Default for GuiEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GuiEngine {
+    pub fn new() -> Self {
+        GuiEngine {
+            last_update: Instant::now(),
+            paused: false,
+            speed: 4000, // 4 secondes par défaut pour laisser le temps de voir
+            map_view: MapView::Terrain,
+            selected: None,
+            id_input: None,
+            viewport: Viewport::new(),
+            renderer: Renderer::new(),
+            keymap: KeyMap::default(),
+            auto_pause: false,
+            overlay: None,
+            heat: None,
+            notice: None,
+        }
+    }
+
+    /// Touches de l'interface temps réel (voir `KeyMap::from_config`)
+    pub fn set_keymap(&mut self, keymap: KeyMap) {
+        self.keymap = keymap;
+    }
+
+    pub fn run_gui_simulation(&mut self, simulation: &mut crate::simulation::SimulationEngine) -> io::Result<()> {
+        // Initialiser le terminal
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, cursor::Hide)?;
+        self.renderer.invalidate();
+
+        let mut auto_mode = true;
+
+        loop {
+            // Gestion des événements clavier - check moins souvent pour réduire le scintillement
+            let input = if event::poll(Duration::from_millis(100))? { Some(event::read()?) } else { None };
+            // Terminal redimensionné : la fenêtre de carte suit, l'écran est redessiné en entier
+            if let Some(Event::Resize(..)) = input {
+                self.renderer.invalidate();
+            }
+            if let Some(Event::Key(key)) = input
+                && key.kind == KeyEventKind::Press
+            {
+                let piloting = self.selected.is_some_and(|id| simulation.is_piloted(id));
+                if let Some(input) = &mut self.id_input {
+                    match key.code {
+                        KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+                        KeyCode::Backspace => {
+                            input.pop();
+                        },
+                        KeyCode::Enter => {
+                            if let Ok(id) = input.parse::<usize>()
+                                && simulation.robot(id).is_some()
+                            {
+                                self.selected = Some(id);
+                            }
+                            self.id_input = None;
+                        },
+                        KeyCode::Esc => self.id_input = None,
+                        _ => {}
+                    }
+                } else if let Some(action) = self.keymap.action(key.code, piloting) {
+                    match action {
+                        KeyAction::Quit => break,
+                        KeyAction::TogglePause => self.paused = !self.paused,
+                        KeyAction::Step if self.paused => self.play_turn(simulation),
+                        KeyAction::Step => {}
+                        KeyAction::SpeedUp => self.speed = self.speed.saturating_sub(SPEED_STEP).max(MIN_SPEED),
+                        KeyAction::SlowDown => self.speed = (self.speed + SPEED_STEP).min(MAX_SPEED),
+                        KeyAction::SpeedPreset(index) => self.speed = SPEED_PRESETS[index],
+                        KeyAction::ToggleAutoPause => self.auto_pause = !self.auto_pause,
+                        KeyAction::ToggleAutoMode => {
+                            auto_mode = !auto_mode;
+                            self.paused = !auto_mode;
+                        },
+                        KeyAction::ToggleMapView => self.toggle_map_view(),
+                        KeyAction::ScrollUp => self.viewport.scroll(0, -1),
+                        KeyAction::ScrollDown => self.viewport.scroll(0, 1),
+                        KeyAction::ScrollLeft => self.viewport.scroll(-1, 0),
+                        KeyAction::ScrollRight => self.viewport.scroll(1, 0),
+                        KeyAction::Zoom => self.viewport.cycle_zoom(),
+                        KeyAction::ToggleFollow => self.viewport.follow = !self.viewport.follow,
+                        KeyAction::CycleOverlay => self.overlay = HeatLayer::next(self.overlay),
+                        KeyAction::ExportOverlay => self.export_heat_maps(simulation),
+                        KeyAction::SelectNext => self.cycle_selection(simulation, 1),
+                        KeyAction::SelectPrevious => self.cycle_selection(simulation, -1),
+                        KeyAction::SelectById => self.id_input = Some(String::new()),
+                        KeyAction::TogglePilot => {
+                            if let Some(id) = self.selected {
+                                let command = if piloting { Command::ReleaseControl(id) } else { Command::TakeControl(id) };
+                                let _ = simulation.command(command);
+                            }
+                        },
+                        // Téléopération : le robot sélectionné n'agit que sur ordre
+                        KeyAction::PilotNorth => self.order_move(simulation, Direction::Nord),
+                        KeyAction::PilotSouth => self.order_move(simulation, Direction::Sud),
+                        KeyAction::PilotWest => self.order_move(simulation, Direction::Ouest),
+                        KeyAction::PilotEast => self.order_move(simulation, Direction::Est),
+                        KeyAction::PilotCollect => self.order(simulation, RobotAction::Collect),
+                        KeyAction::PilotAnalyze => self.order(simulation, RobotAction::Analyze),
+                        KeyAction::PilotCommunicate => {
+                            let listeners = self.selected.map(|id| simulation.reachable_teammates(id)).unwrap_or_default();
+                            self.order(simulation, RobotAction::Communicate(listeners));
+                        },
+                        KeyAction::PilotWait => self.order(simulation, RobotAction::Wait),
+                        // Réservées à la visionneuse de replay
+                        KeyAction::ReplayBack
+                        | KeyAction::ReplayForward
+                        | KeyAction::ReplayStart
+                        | KeyAction::ReplayEnd
+                        | KeyAction::ReplayJump => {}
+                    }
+                }
+            }
+
+            // Mettre à jour la simulation si ce n'est pas en pause
+            if !self.paused && self.last_update.elapsed() >= Duration::from_millis(self.speed) {
+                self.play_turn(simulation);
+                self.last_update = Instant::now();
+            }
+
+            // Mission terminée (objectifs atteints ou impossibles) : on fige la simulation
+            if simulation.is_finished() {
+                self.paused = true;
+            }
+
+            // Dessiner l'interface : seules les cases modifiées partent vers le terminal
+            let (columns, rows) = crossterm::terminal::size()?;
+            self.update_viewport(simulation, columns, rows);
+            self.refresh_heat(simulation);
+            let mut frame = Frame::new(columns, rows);
+            self.draw_interface(&mut frame, simulation, auto_mode);
+            self.renderer.present(frame, &mut stdout().lock())?;
+        }
+
+        // Nettoyer le terminal
+        execute!(stdout(), cursor::Show, LeaveAlternateScreen)?;
+        disable_raw_mode()?;
+
+        Ok(())
+    }
+
+    /// Visionneuse de replay : lecture/pause, pas à pas, saut à un tour et vitesse
+    pub fn run_replay_viewer(&mut self, player: &mut ReplayPlayer) -> io::Result<()> {
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, cursor::Hide)?;
+        self.renderer.invalidate();
+
+        self.paused = true;
+        self.speed = 500;
+        // Saisie en cours du numéro de tour pour [G]
+        let mut jump_input: Option<String> = None;
+
+        loop {
+            let input = if event::poll(Duration::from_millis(50))? { Some(event::read()?) } else { None };
+            if let Some(Event::Resize(..)) = input {
+                self.renderer.invalidate();
+            }
+            if let Some(Event::Key(key)) = input
+                && key.kind == KeyEventKind::Press
+            {
+                if let Some(input) = &mut jump_input {
+                    match key.code {
+                        KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+                        KeyCode::Backspace => {
+                            input.pop();
+                        },
+                        KeyCode::Enter => {
+                            if let Ok(target) = input.parse::<usize>() {
+                                player.seek(target);
+                            }
+                            jump_input = None;
+                        },
+                        KeyCode::Esc => jump_input = None,
+                        _ => {}
+                    }
+                } else if let Some(action) = self.keymap.replay_action(key.code) {
+                    match action {
+                        KeyAction::Quit => break,
+                        KeyAction::TogglePause => self.paused = !self.paused,
+                        KeyAction::ReplayForward => {
+                            self.paused = true;
+                            player.step_forward();
+                        },
+                        KeyAction::ReplayBack => {
+                            self.paused = true;
+                            player.step_back();
+                        },
+                        KeyAction::ReplayStart => player.seek(0),
+                        KeyAction::ReplayEnd => player.seek(player.total_turns()),
+                        KeyAction::ReplayJump => {
+                            self.paused = true;
+                            jump_input = Some(String::new());
+                        },
+                        KeyAction::ToggleMapView => self.toggle_map_view(),
+                        KeyAction::ScrollUp => self.viewport.scroll(0, -1),
+                        KeyAction::ScrollDown => self.viewport.scroll(0, 1),
+                        KeyAction::ScrollLeft => self.viewport.scroll(-1, 0),
+                        KeyAction::ScrollRight => self.viewport.scroll(1, 0),
+                        KeyAction::Zoom => self.viewport.cycle_zoom(),
+                        KeyAction::CycleOverlay => self.overlay = HeatLayer::next(self.overlay),
+                        KeyAction::ExportOverlay => self.export_heat_maps(player.engine()),
+                        KeyAction::SpeedUp => self.speed = self.speed.saturating_sub(SPEED_STEP).max(MIN_SPEED),
+                        KeyAction::SlowDown => self.speed = (self.speed + SPEED_STEP).min(MAX_SPEED),
+                        _ => {}
+                    }
+                }
+            }
+
+            if !self.paused && self.last_update.elapsed() >= Duration::from_millis(self.speed) {
+                if !player.step_forward() {
+                    self.paused = true;
+                }
+                self.last_update = Instant::now();
+            }
+
+            let (columns, rows) = crossterm::terminal::size()?;
+            self.update_viewport(player.engine(), columns, rows);
+            self.refresh_heat(player.engine());
+            let mut frame = Frame::new(columns, rows);
+            self.draw_replay_header(&mut frame, player, jump_input.as_deref());
+            let engine = player.engine();
+            self.draw_environment(&mut frame, engine);
+            self.draw_team_scores(&mut frame, engine);
+            self.draw_map(&mut frame, engine, None);
+            self.draw_events(&mut frame, &engine.events);
+            self.draw_robot_stats(&mut frame, &engine.robots, &engine.teams, engine.turn);
+            self.draw_replay_controls(&mut frame);
+            self.renderer.present(frame, &mut stdout().lock())?;
+        }
+
+        execute!(stdout(), cursor::Show, LeaveAlternateScreen)?;
+        disable_raw_mode()?;
+
+        Ok(())
+    }
+
+    /// Joue un tour ; en pause auto, s'arrête sur la perte d'un robot ou un site terminé
+    fn play_turn(&mut self, simulation: &mut SimulationEngine) {
+        let before = simulation.events.total();
+        simulation.execute_gui_turn();
+        let fresh = simulation.events.total() - before;
+        if self.auto_pause
+            && simulation.events.recent(fresh).any(|event| matches!(event.kind, EventKind::Perte | EventKind::Science))
+        {
+            self.paused = true;
+        }
+    }
+
+    /// Exporte en CSV la couche affichée, ou toutes les couches sans superposition
+    fn export_heat_maps(&mut self, simulation: &SimulationEngine) {
+        let layers = match self.overlay {
+            Some(layer) => vec![layer],
+            None => HeatLayer::ALL.to_vec(),
+        };
+        self.notice = Some(match export_heat_maps(simulation, &layers, Path::new(".")) {
+            Ok(paths) => {
+                let names: Vec<String> = paths.iter().filter_map(|path| path.file_name()).map(|name| name.to_string_lossy().into_owned()).collect();
+                format!("Exporté: {}", names.join(", "))
+            },
+            Err(e) => format!("Export impossible: {}", e),
+        });
+    }
+
+    /// Sélectionne le robot suivant (`step` = 1) ou précédent (-1) dans l'ordre de la flotte
+    fn cycle_selection(&mut self, simulation: &SimulationEngine, step: isize) {
+        let count = simulation.robots.len() as isize;
+        if count == 0 {
+            return;
+        }
+        let current = self.selected.and_then(|id| simulation.robots.iter().position(|robot| robot.id == id));
+        let next = match current {
+            Some(index) => (index as isize + step).rem_euclid(count),
+            None if step > 0 => 0,
+            None => count - 1,
+        };
+        self.selected = Some(simulation.robots[next as usize].id);
+    }
+
+    /// Ordre au robot piloté ; en pause, le tour est joué aussitôt
+    fn order(&mut self, simulation: &mut SimulationEngine, action: RobotAction) {
+        let Some(id) = self.selected else { return };
+        if simulation.command(Command::Order { id, action }).is_ok() && self.paused {
+            self.play_turn(simulation);
+        }
+    }
+
+    fn order_move(&mut self, simulation: &mut SimulationEngine, direction: Direction) {
+        let Some(robot) = self.selected.and_then(|id| simulation.robot(id)) else { return };
+        let action = match simulation.map.neighbor(robot.position, direction) {
+            Some(target) => RobotAction::Move(target),
+            None => RobotAction::Blocked(direction),
+        };
+        self.order(simulation, action);
+    }
+
+    /// Fenêtre de carte ajustée au terminal (place laissée au panneau du robot sélectionné) ;
+    /// en mode suivi, centrée sur ce robot
+    fn update_viewport(&mut self, simulation: &SimulationEngine, columns: u16, rows: u16) {
+        let panel = PANEL_WIDTH as usize + 1;
+        let beside = self.selected.is_some() && columns as usize >= MAP_MARGIN + MIN_VIEW_COLUMNS + panel;
+        let reserved = MAP_MARGIN + if beside { panel } else { 0 };
+        let max_columns = (columns as usize).saturating_sub(reserved).max(MIN_VIEW_COLUMNS);
+
+        let role_changes = simulation.robots.iter().filter(|robot| recent_role_change(robot, simulation.turn).is_some()).count();
+        let interface = INTERFACE_ROWS
+            + usize::from(simulation.teams.len() > 1)
+            + usize::from(self.overlay.is_some())
+            + simulation.relays.chains.len()
+            + simulation.robots.len().div_ceil(4)
+            + role_changes;
+        let max_rows = (rows as usize).saturating_sub(interface).max(MIN_VIEW_ROWS);
+
+        let (width, height) = (simulation.map.width(), simulation.map.height());
+        self.viewport.fit(width, height, max_columns, max_rows);
+        if self.viewport.follow
+            && let Some(robot) = self.selected.and_then(|id| simulation.robot(id))
+        {
+            self.viewport.center_on(robot.position);
+            self.viewport.fit(width, height, max_columns, max_rows);
+        }
+    }
+
+    /// Recalcule la carte de chaleur si la couche choisie ou l'état affiché a changé
+    fn refresh_heat(&mut self, simulation: &SimulationEngine) {
+        let Some(layer) = self.overlay else {
+            self.heat = None;
+            return;
+        };
+        let (turn, events) = (simulation.turn, simulation.events.total());
+        let fresh = self.heat.as_ref().is_some_and(|cache| cache.map.layer == layer && cache.turn == turn && cache.events == events);
+        if !fresh {
+            self.heat = Some(HeatCache { turn, events, map: HeatMap::compute(simulation, layer) });
+        }
+    }
+
+    /// Bascule entre la vue terrain et la carte de chaleur du relief
+    fn toggle_map_view(&mut self) {
+        self.map_view = match self.map_view {
+            MapView::Terrain => MapView::Elevation,
+            MapView::Elevation => MapView::Terrain,
+        };
+    }
+
+    /// Compose l'interface temps réel dans l'image
+    fn draw_interface(&self, frame: &mut Frame, simulation: &SimulationEngine, auto_mode: bool) {
+        // En-tête avec bordure
+        self.draw_header(frame, simulation.turn, auto_mode);
+        self.draw_mission(frame, simulation);
+        self.draw_environment(frame, simulation);
+        self.draw_team_scores(frame, simulation);
+
+        // Carte avec bordure, et le robot sélectionné à sa droite si la place le permet
+        let (_, map_top) = frame.position();
+        self.draw_map(frame, simulation, self.selected);
+        let (_, map_bottom) = frame.position();
+        let panel = self.selected.and_then(|id| simulation.robot(id)).map(|robot| robot_panel(simulation, robot));
+        let column = (self.viewport.columns + MAP_MARGIN + 1) as u16;
+        let (columns, _) = frame.size();
+        let beside = column + PANEL_WIDTH <= columns;
+        if let Some(lines) = &panel
+            && beside
+            && lines.len() as u16 <= map_bottom - map_top
+        {
+            for (row, (color, line)) in lines.iter().enumerate() {
+                draw!(frame,
+                    cursor::MoveTo(column, map_top + row as u16),
+                    SetForegroundColor(*color),
+                    Print(line),
+                    ResetColor
+                );
+            }
+            draw!(frame, cursor::MoveTo(0, map_bottom));
+        } else if let Some(lines) = &panel {
+            for (color, line) in lines {
+                draw!(frame,
+                    SetForegroundColor(*color),
+                    Print(line),
+                    ResetColor,
+                    cursor::MoveToNextLine(1)
+                );
+            }
+        }
+        self.draw_events(frame, &simulation.events);
+
+        // Statistiques des robots
+        self.draw_robot_stats(frame, &simulation.robots, &simulation.teams, simulation.turn);
+
+        // Contrôles
+        let piloting = self.selected.is_some_and(|id| simulation.is_piloted(id));
+        self.draw_controls(frame, piloting);
+    }
+
+    fn draw_header(&self, frame: &mut Frame, turn: usize, auto_mode: bool) {
+        // Octets envoyés au terminal pour l'image précédente (seules les cases modifiées partent)
+        let status = format!(" Tour: {:6} │ Mode: {:6} │ Vitesse: {:4}ms │ État: {:5} │ Écran: {:6}o ",
+                             turn,
+                             if auto_mode { "AUTO" } else { "MANUEL" },
+                             self.speed,
+                             if self.paused { "PAUSE" } else { "ACTIF" },
+                             self.renderer.last_frame_bytes);
+        draw_banner(frame, Color::Cyan, "🚀 EREEA - SIMULATION TEMPS RÉEL 🚀", &status);
+    }
+
+    fn draw_map(&self, frame: &mut Frame, simulation: &SimulationEngine, selected: Option<usize>) {
+        let map = &simulation.map;
+        let heat = self.heat.as_ref().map(|cache| &cache.map);
+        let scene = MapScene {
+            map,
+            robots: &simulation.robots,
+            robot_positions: &simulation.robot_positions,
+            depots: &simulation.depots,
+            teams: &simulation.teams,
+            environment: &simulation.environment,
+            selected,
+            view: self.map_view,
+            heat,
+        };
+        let viewport = self.viewport;
+        let columns = viewport.columns;
+
+        // Coordonnées de la carte tous les 10 caractères
+        draw!(frame, Print("     "));
+        for column in (0..columns).step_by(10) {
+            let x = viewport.x + column * viewport.zoom;
+            draw!(frame, SetForegroundColor(Color::DarkGrey), Print(&format!("{:<10}", x)), ResetColor);
+        }
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Bords reliés (topologie bouclée) dessinés en pointillés
+        let topology = map.topology();
+        let side = if topology.wraps_x() { "┆" } else { "│" };
+        let edge = if topology.wraps_y() { "┄" } else { "─" };
+
+        // Bordure supérieure
+        draw!(frame, Print("     "));
+        let top_border = "┌".to_string() + &edge.repeat(columns) + "┐";
+        draw!(frame, SetForegroundColor(Color::White), Print(&top_border), ResetColor);
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Afficher la fenêtre ligne par ligne ; zoomé, chaque caractère résume un bloc de cases
+        for row in 0..viewport.rows {
+            let y = viewport.y + row * viewport.zoom;
+            // Numéro de ligne tous les 5
+            if row % 5 == 0 {
+                draw!(frame, SetForegroundColor(Color::DarkGrey), Print(&format!("{:3} ", y)), ResetColor);
+            } else {
+                draw!(frame, Print("    "));
+            }
+            draw!(frame, SetForegroundColor(Color::White), Print(side), ResetColor);
+
+            for column in 0..columns {
+                let glyph = scene.block_glyph(Position::new(viewport.x + column * viewport.zoom, y), viewport.zoom);
+                if let Some(bg) = glyph.bg {
+                    draw!(frame, SetBackgroundColor(bg));
+                }
+                draw!(frame, SetForegroundColor(glyph.fg), Print(glyph.symbol), ResetColor);
+            }
+
+            draw!(frame,
+                SetForegroundColor(Color::White),
+                Print(side),
+                ResetColor
+            );
+            draw!(frame, cursor::MoveToNextLine(1));
+        }
+
+        // Bordure inférieure
+        draw!(frame, Print("     "));
+        let bottom_border = "└".to_string() + &edge.repeat(columns) + "┘";
+        draw!(frame, SetForegroundColor(Color::White), Print(&bottom_border), ResetColor);
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Partie visible quand la carte déborde du terminal
+        if !viewport.shows_all(map.width(), map.height()) {
+            let (right, bottom) = viewport.last_cell(map.width(), map.height());
+            let follow = if viewport.follow { " │ Suivi du robot sélectionné" } else { "" };
+            draw!(frame,
+                SetForegroundColor(Color::DarkGrey),
+                Print(&format!(
+                    "     Vue ({},{})-({},{}) sur {}x{} │ Zoom x{}{}",
+                    viewport.x, viewport.y, right, bottom, map.width(), map.height(), viewport.zoom, follow
+                )),
+                ResetColor
+            );
+            draw!(frame, cursor::MoveToNextLine(1));
+        }
+
+        if let Some(heat) = heat {
+            draw!(frame, SetForegroundColor(Color::DarkGrey), Print(format!("     Chaleur: {} │ 0 ", heat.layer.label())), ResetColor);
+            for color in HEAT_COLORS {
+                draw!(frame, SetBackgroundColor(color), Print("  "), ResetColor);
+            }
+            draw!(frame, SetForegroundColor(Color::DarkGrey), Print(format!(" {}", heat.max)), ResetColor);
+            draw!(frame, cursor::MoveToNextLine(1));
+        }
+
+        if topology != Topology::Bornee {
+            draw!(frame,
+                SetForegroundColor(Color::DarkGrey),
+                Print(&format!("     Topologie: {} (bords en pointillés reliés)", topology.label())),
+                ResetColor
+            );
+            draw!(frame, cursor::MoveToNextLine(1));
+        }
+    }
+
+    fn draw_robot_stats(&self, frame: &mut Frame, robots: &[Robot], teams: &[Team], turn: usize) {
+        draw!(frame, cursor::MoveToNextLine(1));
+        draw!(frame, SetForegroundColor(Color::Cyan), Print("═══ ÉTAT DES ROBOTS ═══"), ResetColor);
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Afficher les robots sur 4 colonnes pour économiser l'espace avec plus de robots
+        let robots_per_row = 4;
+        for chunk in robots.chunks(robots_per_row) {
+            for (i, robot) in chunk.iter().enumerate() {
+                let total_resources: u32 = robot.inventory.values().sum();
+                let energy_bar = self.create_simple_energy_bar(robot.energy);
+                let color = if teams.len() > 1 { team_color(teams[robot.team].color) } else { role_color(robot.behavior) };
+                // Un '*' après le rôle signale une réaffectation récente
+                let changed = if recent_role_change(robot, turn).is_some() { "*" } else { "" };
+
+                // Un '!' signale un module hors d'usage
+                let broken = if robot.broken_modules().is_empty() { "" } else { "!" };
+                let stats = format!("R{:2}[{}{}]({:2},{:2}){}R:{}{}",
+                                    robot.id, role_letter(robot.behavior), changed, robot.position.x, robot.position.y,
+                                    energy_bar, total_resources, broken);
+
+                draw!(frame, SetForegroundColor(color), Print(&format!("{:<22}", stats)), ResetColor);
+
+                // Espace entre les colonnes
+                if i < chunk.len() - 1 {
+                    draw!(frame, Print(" "));
+                }
+            }
+            draw!(frame, cursor::MoveToNextLine(1));
+        }
+
+        for robot in robots {
+            if let Some(change) = recent_role_change(robot, turn) {
+                draw!(frame,
+                    Print(&format!("🔄 R{} ", robot.id)),
+                    SetForegroundColor(role_color(change.from)), Print(role_letter(change.from)), ResetColor,
+                    Print("→"),
+                    SetForegroundColor(role_color(change.to)), Print(role_letter(change.to)), ResetColor,
+                    Print(&format!(" T{} : {}", change.turn, change.reason)),
+                    cursor::MoveToNextLine(1)
+                );
+            }
+        }
+    }
+
+    fn draw_mission(&self, frame: &mut Frame, simulation: &crate::simulation::SimulationEngine) {
+        let color = match simulation.mission_status {
+            MissionStatus::EnCours => Color::Yellow,
+            MissionStatus::Reussie => Color::Green,
+            MissionStatus::Echouee(_) => Color::Red,
+        };
+
+        let objectives: Vec<String> = objective_progress(&simulation.objectives, simulation)
+            .iter()
+            .map(|objective| objective.describe())
+            .collect();
+
+        draw!(frame,
+            SetForegroundColor(color),
+            Print(&format!("🎯 Mission: {}", simulation.mission_status.label())),
+            ResetColor,
+            Print(&format!("  {}", objectives.join(" │ ")))
+        );
+        draw!(frame, cursor::MoveToNextLine(1));
+    }
+
+    /// Score de chaque équipe en compétition, du meilleur au moins bon
+    fn draw_team_scores(&self, frame: &mut Frame, simulation: &SimulationEngine) {
+        if simulation.teams.len() < 2 {
+            return;
+        }
+
+        draw!(frame, Print("🏆"));
+        for score in team_scores(simulation) {
+            let team = &simulation.teams[score.team];
+            draw!(frame,
+                Print(" "),
+                SetBackgroundColor(team_color(team.color)),
+                Print(" "),
+                ResetColor,
+                Print(&format!(
+                    " {}: {} pts ({} cases, {} mineraux, {} sites, {} robots) │",
+                    team.name, score.points, score.explored, score.minerals, score.science_sites, score.alive
+                ))
+            );
+        }
+        draw!(frame, cursor::MoveToNextLine(1));
+    }
+
+    /// Cycle jour/nuit et phénomènes en cours
+    fn draw_environment(&self, frame: &mut Frame, simulation: &SimulationEngine) {
+        let environment = &simulation.environment;
+        let (icon, period, color) = if environment.is_night(simulation.turn) {
+            ("🌙", "Nuit", Color::DarkBlue)
+        } else {
+            ("☀️ ", "Jour", Color::Yellow)
+        };
+
+        draw!(frame,
+            SetForegroundColor(color),
+            Print(&format!("{} {}", icon, period)),
+            ResetColor,
+            Print(&format!(
+                "  │ 🌪️  Tempêtes actives: {} │ ☄️  Impacts: {}",
+                environment.storms.len(),
+                environment.meteor_impacts
+            ))
+        );
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        for chain in &simulation.relays.chains {
+            draw!(frame,
+                SetForegroundColor(Color::DarkYellow),
+                Print(&format!("⛓️  {}", chain.describe())),
+                ResetColor,
+                cursor::MoveToNextLine(1)
+            );
+        }
+    }
+
+    /// Derniers événements du journal
+    fn draw_events(&self, frame: &mut Frame, events: &EventLog) {
+        draw!(frame, SetForegroundColor(Color::Cyan), Print("═══ ÉVÉNEMENTS ═══"), ResetColor);
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        for event in events.recent(EVENTS_SHOWN) {
+            draw!(frame,
+                Print(&event.describe())
+            );
+            draw!(frame, cursor::MoveToNextLine(1));
+        }
+    }
+
+    fn draw_replay_header(&self, frame: &mut Frame, player: &ReplayPlayer, jump_input: Option<&str>) {
+        let state = match jump_input {
+            Some(input) => format!("ALLER À: {}_", input),
+            None if player.is_at_end() => "FIN".to_string(),
+            None if self.paused => "PAUSE".to_string(),
+            None => "LECTURE".to_string(),
+        };
+        let status = format!(" Tour: {:6}/{:<6} │ Vitesse: {:4}ms │ État: {} ",
+                             player.position(),
+                             player.total_turns(),
+                             self.speed,
+                             state);
+        draw_banner(frame, Color::Magenta, "🎞️  EREEA - REPLAY 🎞️", &status);
+    }
+
+    fn draw_replay_controls(&self, frame: &mut Frame) {
+        draw!(frame, cursor::MoveToNextLine(1));
+        draw!(frame, SetForegroundColor(Color::Cyan), Print("═══ CONTRÔLES REPLAY ═══"), ResetColor);
+        draw!(frame, cursor::MoveToNextLine(1));
+        if let Some(notice) = &self.notice {
+            draw!(frame, SetForegroundColor(Color::Green), Print(format!(" {}", notice)), ResetColor);
+        }
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Aide tirée de la disposition du clavier, comme pour la simulation en direct
+        for line in help_lines(&self.keymap.replay_help(), frame.size().0 as usize) {
+            draw!(frame, Print(&line), cursor::MoveToNextLine(1));
+        }
+    }
+
+    fn create_simple_energy_bar(&self, energy: u32) -> String {
+        let bar_length = 5; // Plus court pour économiser l'espace
+        let filled = (energy * bar_length / 100).min(bar_length);
+        let empty = bar_length - filled;
+
+        format!("[{}{}]",
+                "█".repeat(filled as usize),
+                "░".repeat(empty as usize))
+    }
+
+    fn draw_controls(&self, frame: &mut Frame, piloting: bool) {
+        draw!(frame, cursor::MoveToNextLine(1));
+        draw!(frame, SetForegroundColor(Color::Yellow), Print("═══ LÉGENDE ═══"), ResetColor);
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Légende des robots sur une ligne
+        draw!(frame, 
+            SetBackgroundColor(Color::Green), SetForegroundColor(Color::Black), Print("E"), ResetColor,
+            Print(" Explorateur │ "),
+            SetBackgroundColor(Color::Yellow), SetForegroundColor(Color::Black), Print("C"), ResetColor,
+            Print(" Collecteur │ "),
+            SetBackgroundColor(Color::Blue), SetForegroundColor(Color::White), Print("S"), ResetColor,
+            Print(" Scientifique")
+        );
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        if self.map_view == MapView::Elevation {
+            self.draw_elevation_legend(frame);
+        } else {
+            self.draw_terrain_legend(frame);
+        }
+
+        draw!(frame, SetForegroundColor(Color::Cyan), Print("═══ CONTRÔLES ═══"), ResetColor);
+        if self.auto_pause {
+            draw!(frame, SetForegroundColor(Color::Yellow), Print(" Pause auto active"), ResetColor);
+        }
+        if let Some(notice) = &self.notice {
+            draw!(frame, SetForegroundColor(Color::Green), Print(format!(" {}", notice)), ResetColor);
+        }
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Aide tirée de la disposition du clavier : elle reste juste après un remappage
+        let width = frame.size().0 as usize;
+        for group in [KeyGroup::Simulation, KeyGroup::Carte] {
+            for line in help_lines(&self.keymap.help(group), width) {
+                draw!(frame, Print(&line), cursor::MoveToNextLine(1));
+            }
+        }
+        match &self.id_input {
+            Some(input) => {
+                draw!(frame, Print(format!("Robot n°: {}_ (ENTER valide, ESC annule)", input)), cursor::MoveToNextLine(1));
+            },
+            None => {
+                for line in help_lines(&self.keymap.help(KeyGroup::Robots), width) {
+                    draw!(frame, Print(&line), cursor::MoveToNextLine(1));
+                }
+            },
+        }
+        if piloting {
+            let mut pilot = self.keymap.help(KeyGroup::Pilotage);
+            if let Some(first) = pilot.first_mut() {
+                first.insert_str(0, "PILOTAGE: ");
+            }
+            for line in help_lines(&pilot, width) {
+                draw!(frame, SetForegroundColor(Color::Magenta), Print(&line), ResetColor, cursor::MoveToNextLine(1));
+            }
+        }
+    }
+
+    fn draw_terrain_legend(&self, frame: &mut Frame) {
+        // Terrains sur une ligne
+        draw!(frame, 
+            SetForegroundColor(Color::Red), Print("#"), ResetColor, Print(" Obstacle │ "),
+            SetForegroundColor(Color::DarkGrey), Print("^"), ResetColor, Print(" Montagne │ "),
+            SetForegroundColor(Color::Yellow), Print("o"), ResetColor, Print(" Cratère │ "),
+            SetForegroundColor(Color::DarkGrey), Print("·"), ResetColor, Print(" Exploré │ "),
+            SetBackgroundColor(Color::DarkYellow), Print(" "), ResetColor, Print(" Tempête")
+        );
+        draw!(frame, cursor::MoveToNextLine(1));
+
+        // Ressources sur une ligne
+        draw!(frame, 
+            SetForegroundColor(Color::Cyan), Print("E"), ResetColor, Print(" Énergie(>30) │ "),
+            SetForegroundColor(Color::Red), Print("M"), ResetColor, Print(" Mineraux(>40) │ "),
+            SetForegroundColor(Color::Magenta), Print("*"), ResetColor, Print(" Site scientifique │ "),
+            SetBackgroundColor(Color::Magenta), Print("H"), ResetColor, Print(" Station │ "),
+            SetBackgroundColor(Color::DarkMagenta), Print("h"), ResetColor, Print(" Avant-poste")
+        );
+        draw!(frame, cursor::MoveToNextLine(1));
+    }
+
+    fn draw_elevation_legend(&self, frame: &mut Frame) {
+        // Dégradé du relief, une couleur par tranche
+        draw!(frame, Print("Relief: 0 "));
+        for color in ELEVATION_COLORS {
+            draw!(frame, SetBackgroundColor(color), Print("  "), ResetColor);
+        }
+        draw!(frame, Print(" 100 │ # Falaise/obstacle"));
+        draw!(frame, cursor::MoveToNextLine(1));
+    }
+}
+
+/// Octets envoyés au terminal par image de l'interface temps réel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderCost {
+    /// Chaque image redessinée en entier
+    pub full_redraw: usize,
+    /// Seules les cases modifiées depuis l'image précédente
+    pub incremental: usize,
+}
+
+/// Joue `turns` tours en composant l'interface dans un écran virtuel de `columns`×`rows`
+/// et compare les deux manières de l'envoyer au terminal (moyenne par image)
+pub fn measure_render_cost(simulation: &mut SimulationEngine, turns: usize, columns: u16, rows: u16) -> io::Result<RenderCost> {
+    let mut gui = GuiEngine::new();
+    let mut screen = io::sink();
+    let (mut full_redraw, mut incremental) = (0, 0);
+    for _ in 0..turns {
+        gui.update_viewport(simulation, columns, rows);
+        let mut frame = Frame::new(columns, rows);
+        gui.draw_interface(&mut frame, simulation, true);
+        full_redraw += Renderer::new().present(frame.clone(), &mut screen)?;
+        incremental += gui.renderer.present(frame, &mut screen)?;
+        simulation.step();
+    }
+    let frames = turns.max(1);
+    Ok(RenderCost { full_redraw: full_redraw / frames, incremental: incremental / frames })
+}
+
+// En-tête encadré sur toute la largeur de l'image (au moins celle de la ligne d'état) :
+// titre centré, puis ligne d'état
+fn draw_banner(frame: &mut Frame, color: Color, title: &str, status: &str) {
+    let inner = (frame.size().0 as usize).saturating_sub(2).max(text_width(status));
+    let padded = |text: &str, centered: bool| {
+        let free = inner.saturating_sub(text_width(text));
+        let left = if centered { free / 2 } else { 0 };
+        format!("║{}{}{}║", " ".repeat(left), text, " ".repeat(free - left))
+    };
+    let lines = [
+        format!("╔{}╗", "═".repeat(inner)),
+        padded(title, true),
+        padded(status, false),
+        format!("╚{}╝", "═".repeat(inner)),
+    ];
+    for line in &lines {
+        draw!(frame, SetForegroundColor(color), Print(line), ResetColor);
+        draw!(frame, cursor::MoveToNextLine(1));
+    }
+}
+
+/// Entrées d'aide séparées par " │ ", passées à la ligne avant `width` colonnes
+fn help_lines(entries: &[String], width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for entry in entries {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 3 + entry.chars().count() <= width => {
+                line.push_str(" │ ");
+                line.push_str(entry);
+            },
+            _ => lines.push(entry.clone()),
+        }
+    }
+    lines
+}
+
+// Couleur d'un bloc de la carte de chaleur : valeur la plus forte du bloc rapportée au maximum de la couche ;
+// rien sans donnée, ni pour un compte nul (sauf le dernier passage, où 0 signifie « à l'instant »)
+fn heat_color(heat: &HeatMap, origin: Position, zoom: usize) -> Option<Color> {
+    let value = heat.values.region(origin, zoom, zoom).filter_map(|(_, value)| *value).max()?;
+    if value == 0 && heat.layer != HeatLayer::DernierPassage {
+        return None;
+    }
+    let band = value as usize * (HEAT_COLORS.len() - 1) / heat.max.max(1) as usize;
+    Some(HEAT_COLORS[band.min(HEAT_COLORS.len() - 1)])
+}
+
+fn elevation_color(elevation: u8) -> Color {
+    let band = elevation as usize * ELEVATION_COLORS.len() / (MAX_ELEVATION as usize + 1);
+    ELEVATION_COLORS[band.min(ELEVATION_COLORS.len() - 1)]
+}
+
+// Extension pour SimulationEngine
+impl crate::simulation::SimulationEngine {
+    pub fn execute_gui_turn(&mut self) {
+        if !self.is_finished() {
+            self.step();
+        }
+    }
+}
+
+/// Fiche complète d'un robot pour le panneau latéral : une ligne colorée par entrée
+fn robot_panel(simulation: &SimulationEngine, robot: &Robot) -> Vec<(Color, String)> {
+    let mut lines = vec![(Color::Cyan, format!("═══ ROBOT {} ═══", robot.id))];
+    if simulation.is_piloted(robot.id) {
+        lines.push((Color::Magenta, "Pilotage: MANUEL".to_string()));
+    } else {
+        lines.push((Color::Green, "Pilotage: AUTONOME".to_string()));
+    }
+    let team = &simulation.teams[robot.team];
+    lines.push((role_color(robot.behavior), format!("Rôle: {:?} │ Équipe: {}", robot.behavior, team.name)));
+    lines.push((Color::White, format!("Position: ({},{})", robot.position.x, robot.position.y)));
+    let filled = (robot.energy / 10).min(10) as usize;
+    lines.push((Color::Yellow, format!("Énergie: [{}{}] {}", "█".repeat(filled), "░".repeat(10 - filled), robot.energy)));
+
+    lines.push((Color::Cyan, "Modules:".to_string()));
+    for module in &robot.modules {
+        let condition = robot.condition(*module);
+        let (color, state) = if condition.is_broken() {
+            (Color::Red, "HORS D'USAGE".to_string())
+        } else if condition.offline_turns > 0 {
+            (Color::DarkYellow, format!("{}% hors ligne {}t", condition.health, condition.offline_turns))
+        } else {
+            (Color::White, format!("{}%", condition.health))
+        };
+        lines.push((color, format!("  {:?}: {}", module, state)));
+    }
+
+    lines.push((Color::Cyan, format!("Chargement: {}/{}", robot.cargo_mass(), robot.max_inventory)));
+    if robot.inventory.values().all(|amount| *amount == 0) {
+        lines.push((Color::DarkGrey, "  vide".to_string()));
+    }
+    for (resource, amount) in robot.inventory.iter().filter(|(_, amount)| **amount > 0) {
+        lines.push((Color::White, format!("  {:?}: {}", resource, amount)));
+    }
+
+    if let Some(action) = simulation.planned_action(robot.id) {
+        lines.push((Color::White, format!("Plan: {}", describe_action(&action))));
+    }
+    if let Some(relay) = &robot.relay {
+        lines.push((Color::DarkYellow, format!("Relais: chaîne {}, poste ({},{})", relay.chain, relay.post.x, relay.post.y)));
+    }
+    lines.push((Color::White, format!("Radio: {} coéquipier(s) à portée", simulation.reachable_teammates(robot.id).len())));
+    lines
+}
+
+fn describe_action(action: &RobotAction) -> String {
+    match action {
+        RobotAction::Move(pos) => format!("aller en ({},{})", pos.x, pos.y),
+        RobotAction::Collect => "collecter".to_string(),
+        RobotAction::Analyze => "analyser".to_string(),
+        RobotAction::Communicate(targets) => format!("transmettre à {} robot(s)", targets.len()),
+        RobotAction::Wait => "attendre".to_string(),
+        RobotAction::Blocked(direction) => format!("bloqué vers {:?}", direction),
+        RobotAction::Repair(id) => format!("réparer R{}", id),
+        RobotAction::Drop(resource) => format!("déposer {:?}", resource),
+        RobotAction::Transfer { to, resource, amount } => format!("céder {} {:?} à R{}", amount, resource, to),
+        RobotAction::ShareEnergy { to, amount } => format!("céder {} d'énergie à R{}", amount, to),
+        RobotAction::BuildOutpost => "construire un avant-poste".to_string(),
+    }
+}
+
+fn recent_role_change(robot: &Robot, turn: usize) -> Option<&crate::robot::RoleChange> {
+    robot.role_change.as_ref().filter(|change| turn <= change.turn + ROLE_CHANGE_NOTICE_TURNS)
+}
+
+fn role_letter(behavior: crate::RobotBehavior) -> &'static str {
+    match behavior {
+        crate::RobotBehavior::Explorateur => "E",
+        crate::RobotBehavior::Collecteur => "C",
+        crate::RobotBehavior::Scientifique => "S",
+    }
+}
+
+fn role_color(behavior: crate::RobotBehavior) -> Color {
+    match behavior {
+        crate::RobotBehavior::Explorateur => Color::Green,
+        crate::RobotBehavior::Collecteur => Color::Yellow,
+        crate::RobotBehavior::Scientifique => Color::Blue,
+    }
+}
+
+fn team_color(color: TeamColor) -> Color {
+    match color {
+        TeamColor::Vert => Color::Green,
+        TeamColor::Rouge => Color::Red,
+        TeamColor::Bleu => Color::Blue,
+        TeamColor::Jaune => Color::Yellow,
+        TeamColor::Cyan => Color::Cyan,
+        TeamColor::Magenta => Color::Magenta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RobotBehavior;
+    use crate::simulation::EnvironmentConfig;
+
+    #[test]
+    fn viewport_stays_inside_the_map_and_zooms_around_its_center() {
+        let mut viewport = Viewport::new();
+        viewport.fit(200, 100, 60, 20);
+        assert_eq!((viewport.columns, viewport.rows), (60, 20));
+
+        viewport.center_on(Position::new(199, 99));
+        viewport.fit(200, 100, 60, 20);
+        assert_eq!((viewport.x, viewport.y), (140, 80));
+        assert_eq!(viewport.last_cell(200, 100), (199, 99));
+
+        viewport.cycle_zoom();
+        viewport.fit(200, 100, 60, 20);
+        assert_eq!(viewport.zoom, 2);
+        assert_eq!((viewport.x, viewport.y), (80, 60));
+
+        // Vue d'ensemble : la carte entière tient dans moins de caractères
+        viewport.zoom = 8;
+        viewport.fit(200, 100, 60, 20);
+        assert_eq!((viewport.columns, viewport.rows, viewport.x, viewport.y), (25, 13, 0, 0));
+    }
+
+    #[test]
+    fn incremental_rendering_writes_far_less_than_full_redraws() {
+        let mut simulation = SimulationEngine::builder()
+            .generated_map(3, 60, 20)
+            .robot(Robot::new(1, Position::new(2, 2), RobotBehavior::Explorateur))
+            .robot(Robot::new(2, Position::new(4, 3), RobotBehavior::Collecteur))
+            .build()
+            .unwrap();
+
+        let cost = measure_render_cost(&mut simulation, 20, 120, 50).unwrap();
+        assert!(cost.incremental * 4 < cost.full_redraw, "{:?}", cost);
+    }
+
+    #[test]
+    fn banner_spans_the_frame_width() {
+        let mut frame = Frame::new(100, 6);
+        draw_banner(&mut frame, Color::Cyan, "🚀 TITRE 🚀", " Tour: 1 ");
+        for row in 0..4 {
+            assert_ne!(frame.cell(99, row).unwrap().symbol, ' ', "ligne {}", row);
+        }
+        assert_eq!(frame.cell(99, 0).unwrap().symbol, '╗');
+        assert_eq!(frame.cell(99, 2).unwrap().symbol, '║');
+        assert_eq!(frame.position(), (0, 4));
+
+        // Plus étroite que la ligne d'état : le cadre l'entoure quand même, l'image coupe le reste
+        let mut narrow = Frame::new(10, 6);
+        draw_banner(&mut narrow, Color::Cyan, "TITRE", " Tour: 123456 ");
+        assert_eq!(narrow.cell(9, 0).unwrap().symbol, '═');
+    }
+
+    #[test]
+    fn zoomed_block_shows_its_most_important_content() {
+        let mut map = Grid::new(8, 8, Cell::new(TerrainType::Plaine));
+        map[Position::new(1, 0)].terrain = TerrainType::Obstacle;
+        map[Position::new(6, 6)].add_resource(ResourceType::Mineraux, 50);
+        let robot = Robot::new(3, Position::new(5, 5), RobotBehavior::Explorateur);
+        let environment = Environment::new(EnvironmentConfig::default(), 0);
+        let robot_positions = SpatialIndex::of_positions(&map, [robot.position]);
+        let scene = MapScene {
+            map: &map,
+            robots: std::slice::from_ref(&robot),
+            robot_positions: &robot_positions,
+            depots: &[],
+            teams: &[],
+            environment: &environment,
+            selected: None,
+            view: MapView::Terrain,
+            heat: None,
+        };
+
+        // Plaine majoritaire malgré un obstacle ; le robot l'emporte sur le gisement
+        assert_eq!(scene.block_glyph(Position::new(0, 0), 4).symbol, " ");
+        assert_eq!(scene.block_glyph(Position::new(4, 4), 4).symbol, "E");
+        assert_eq!(scene.block_glyph(Position::new(6, 6), 2).symbol, "M");
+
+        // Carte de chaleur : le bloc prend la couleur de sa case la plus chaude, sauf sous un robot
+        let mut values = Grid::new(8, 8, Some(0));
+        values[Position::new(1, 1)] = Some(1);
+        values[Position::new(2, 3)] = Some(5);
+        values[Position::new(5, 5)] = Some(5);
+        let heat = HeatMap { layer: HeatLayer::Passages, values, max: 5 };
+        let scene = MapScene { heat: Some(&heat), ..scene };
+        assert_eq!(scene.block_glyph(Position::new(0, 0), 1).bg, None);
+        assert_eq!(scene.block_glyph(Position::new(1, 1), 1).bg, Some(HEAT_COLORS[1]));
+        assert_eq!(scene.block_glyph(Position::new(0, 0), 4).bg, Some(Color::Red));
+        assert_eq!(scene.block_glyph(Position::new(4, 4), 4).bg, Some(role_color(RobotBehavior::Explorateur)));
+    }
+}
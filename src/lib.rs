@@ -1,8 +1,55 @@
-// src/lib.rs - Types de base pour EREEA
-use std::collections::HashMap;
+// src/lib.rs - Bibliothèque EREEA : types de base, cartes, robots, moteur et rendu
+//! Essaim de Robots pour l'Exploration Astrobiologique.
+//!
+//! - [`map`] : génération, chargement et sauvegarde des cartes, pathfinding, index spatial
+//! - [`robot`] : robots, modules, cargaison et comportements
+//! - [`simulation`] : moteur, scénarios, équipes, environnement, replays
+//! - [`display`] et [`gui`] : rendu console et interface temps réel
+//! - [`console`] : modes interactif, automatique et classique, rapports imprimés
+//!
+//! Le moteur se construit avec [`simulation::SimulationEngine::builder`], avance d'un tour avec
+//! `step()` et s'interroge par ses champs publics, `robot`, `cell` et `stats`. Le code appelant peut
+//! aussi intervenir (`command`) et suivre le journal (`subscribe`) :
+//!
+//! ```
+//! use final_project::{Position, ResourceType, RobotBehavior};
+//! use final_project::robot::Robot;
+//! use final_project::simulation::{Command, SimulationEngine};
+//!
+//! let mut engine = SimulationEngine::builder()
+//!     .generated_map(42, 30, 12)
+//!     .robot(Robot::new(1, Position::new(3, 3), RobotBehavior::Explorateur))
+//!     .robot(Robot::new(2, Position::new(4, 3), RobotBehavior::Collecteur))
+//!     .build()
+//!     .expect("robots et station sur la carte");
+//!
+//! engine.subscribe(|event| println!("{}", event.describe()));
+//! let deposit = Command::SetResource { position: Position::new(4, 3), resource: ResourceType::Mineraux, amount: 30 };
+//! engine.command(deposit).expect("case sur la carte");
+//! for _ in 0..20 {
+//!     engine.step();
+//! }
+//! assert_eq!(engine.turn, 20);
+//! assert_eq!(engine.robot(1).map(|robot| robot.behavior), Some(RobotBehavior::Explorateur));
+//! println!("{:.1}% exploré", engine.stats().exploration_percentage);
+//! ```
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub mod grid;
+pub use grid::{Grid, Topology};
+
+pub mod console;
+pub mod display;
+pub mod gui;
+pub mod map;
+pub mod robot;
+pub mod simulation;
+pub mod utils;
 
 // Types de terrain sur la planète
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TerrainType {
     Plaine,
     Montagne,
@@ -10,16 +57,36 @@ pub enum TerrainType {
     Obstacle,
 }
 
+impl TerrainType {
+    /// Élévation typique (0-100) quand le relief n'est pas connu (cartes sans élévation)
+    pub fn default_elevation(&self) -> u8 {
+        match self {
+            TerrainType::Cratere => 20,
+            TerrainType::Plaine => 40,
+            TerrainType::Montagne => 70,
+            TerrainType::Obstacle => 85,
+        }
+    }
+}
+
 // Types de ressources à collecter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ResourceType {
     Energie,
     Mineraux,
     LieuxInteret, // Sites scientifiques
 }
 
+impl ResourceType {
+    pub const ALL: [ResourceType; 3] = [
+        ResourceType::Energie,
+        ResourceType::Mineraux,
+        ResourceType::LieuxInteret,
+    ];
+}
+
 // Position sur la carte
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)] // Ajout de Hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)] // Ajout de Hash
 pub struct Position {
     pub x: usize,
     pub y: usize,
@@ -35,13 +102,172 @@ impl Position {
         let dy = (self.y as f64 - other.y as f64).abs();
         (dx * dx + dy * dy).sqrt()
     }
+
+    /// Distance en déplacements orthogonaux
+    pub fn manhattan_distance(&self, other: &Position) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance en déplacements à 8 directions
+    pub fn chebyshev_distance(&self, other: &Position) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Décale la position ; `None` si une coordonnée devient négative
+    pub fn offset(&self, dx: isize, dy: isize) -> Option<Position> {
+        Some(Position::new(
+            self.x.checked_add_signed(dx)?,
+            self.y.checked_add_signed(dy)?,
+        ))
+    }
+
+    /// Case voisine dans une direction, sans borne supérieure
+    pub fn step(&self, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.offset();
+        self.offset(dx, dy)
+    }
+
+    /// Case voisine dans une direction, `None` si elle sort d'une carte width x height
+    pub fn checked_neighbor(&self, direction: Direction, width: usize, height: usize) -> Option<Position> {
+        self.step(direction).filter(|pos| pos.x < width && pos.y < height)
+    }
+}
+
+// Directions de déplacement (y croît vers le sud)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    Nord,
+    NordEst,
+    Est,
+    SudEst,
+    Sud,
+    SudOuest,
+    Ouest,
+    NordOuest,
+}
+
+impl Direction {
+    pub const CARDINALES: [Direction; 4] = [Direction::Nord, Direction::Est, Direction::Sud, Direction::Ouest];
+    pub const TOUTES: [Direction; 8] = [
+        Direction::Nord,
+        Direction::NordEst,
+        Direction::Est,
+        Direction::SudEst,
+        Direction::Sud,
+        Direction::SudOuest,
+        Direction::Ouest,
+        Direction::NordOuest,
+    ];
+
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Nord => (0, -1),
+            Direction::NordEst => (1, -1),
+            Direction::Est => (1, 0),
+            Direction::SudEst => (1, 1),
+            Direction::Sud => (0, 1),
+            Direction::SudOuest => (-1, 1),
+            Direction::Ouest => (-1, 0),
+            Direction::NordOuest => (-1, -1),
+        }
+    }
+
+    pub fn from_offset(dx: isize, dy: isize) -> Option<Direction> {
+        Direction::TOUTES
+            .into_iter()
+            .find(|d| d.offset() == (dx.signum(), dy.signum()))
+    }
+
+    pub fn opposite(&self) -> Direction {
+        let (dx, dy) = self.offset();
+        Direction::from_offset(-dx, -dy).expect("toute direction a un opposé")
+    }
+}
+
+// Ressources d'une cellule : un compteur par type, sans allocation
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<ResourceType, u32>", into = "BTreeMap<ResourceType, u32>")]
+pub struct Resources {
+    amounts: [u32; 3],
+}
+
+impl Resources {
+    fn slot(resource_type: ResourceType) -> usize {
+        match resource_type {
+            ResourceType::Energie => 0,
+            ResourceType::Mineraux => 1,
+            ResourceType::LieuxInteret => 2,
+        }
+    }
+
+    pub fn amount(&self, resource_type: ResourceType) -> u32 {
+        self.amounts[Self::slot(resource_type)]
+    }
+
+    pub fn contains(&self, resource_type: ResourceType) -> bool {
+        self.amount(resource_type) > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.amounts.iter().all(|&amount| amount == 0)
+    }
+
+    pub fn add(&mut self, resource_type: ResourceType, amount: u32) {
+        let slot = &mut self.amounts[Self::slot(resource_type)];
+        *slot = slot.saturating_add(amount);
+    }
+
+    /// Retire jusqu'à `amount` unités et rend la quantité réellement retirée
+    pub fn take(&mut self, resource_type: ResourceType, amount: u32) -> u32 {
+        let slot = &mut self.amounts[Self::slot(resource_type)];
+        let taken = amount.min(*slot);
+        *slot -= taken;
+        taken
+    }
+
+    /// Vide un type de ressource et rend la quantité retirée
+    pub fn remove(&mut self, resource_type: ResourceType) -> u32 {
+        std::mem::take(&mut self.amounts[Self::slot(resource_type)])
+    }
+
+    /// Ressources présentes (quantité non nulle), dans l'ordre de `ResourceType::ALL`
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceType, u32)> + '_ {
+        ResourceType::ALL
+            .into_iter()
+            .map(|r| (r, self.amount(r)))
+            .filter(|&(_, amount)| amount > 0)
+    }
+}
+
+impl fmt::Debug for Resources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl From<BTreeMap<ResourceType, u32>> for Resources {
+    fn from(map: BTreeMap<ResourceType, u32>) -> Self {
+        let mut resources = Resources::default();
+        for (resource_type, amount) in map {
+            resources.add(resource_type, amount);
+        }
+        resources
+    }
+}
+
+impl From<Resources> for BTreeMap<ResourceType, u32> {
+    fn from(resources: Resources) -> Self {
+        resources.iter().collect()
+    }
 }
 
 // Cellule de la carte
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cell {
     pub terrain: TerrainType,
-    pub resources: HashMap<ResourceType, u32>,
+    #[serde(default)]
+    pub elevation: u8, // 0 à 100
+    pub resources: Resources,
     pub explored: bool,
     pub occupied_by: Option<usize>, // ID du robot présent
 }
@@ -50,14 +276,15 @@ impl Cell {
     pub fn new(terrain: TerrainType) -> Self {
         Cell {
             terrain,
-            resources: HashMap::new(),
+            elevation: terrain.default_elevation(),
+            resources: Resources::default(),
             explored: false,
             occupied_by: None,
         }
     }
 
     pub fn add_resource(&mut self, resource_type: ResourceType, amount: u32) {
-        *self.resources.entry(resource_type).or_insert(0) += amount;
+        self.resources.add(resource_type, amount);
     }
 
     pub fn is_passable(&self) -> bool {
@@ -66,7 +293,7 @@ impl Cell {
 }
 
 // Modules spécialisés des robots
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum RobotModule {
     AnalyseChimique,
     ImageHauteResolution,
@@ -78,9 +305,53 @@ pub enum RobotModule {
 }
 
 // Comportements des robots
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RobotBehavior {
     Explorateur,  // Explore les zones inconnues
     Collecteur,   // Collecte les ressources
     Scientifique, // Analyse les lieux d'intérêt
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_and_step_stop_at_zero() {
+        let origin = Position::new(0, 0);
+        assert_eq!(origin.offset(-1, 0), None);
+        assert_eq!(origin.offset(0, -1), None);
+        assert_eq!(origin.offset(3, 2), Some(Position::new(3, 2)));
+        assert_eq!(Position::new(3, 2).offset(-3, -2), Some(origin));
+
+        assert_eq!(origin.step(Direction::Nord), None);
+        assert_eq!(origin.step(Direction::SudEst), Some(Position::new(1, 1)));
+        // Sans borne supérieure
+        assert_eq!(Position::new(usize::MAX - 1, 0).step(Direction::Est), Some(Position::new(usize::MAX, 0)));
+    }
+
+    #[test]
+    fn checked_neighbor_stays_on_the_map() {
+        let corner = Position::new(4, 3);
+        assert_eq!(corner.checked_neighbor(Direction::Est, 5, 4), None);
+        assert_eq!(corner.checked_neighbor(Direction::Sud, 5, 4), None);
+        assert_eq!(corner.checked_neighbor(Direction::NordOuest, 5, 4), Some(Position::new(3, 2)));
+        assert_eq!(Position::new(0, 2).checked_neighbor(Direction::Ouest, 5, 4), None);
+
+        for direction in Direction::TOUTES {
+            let (dx, dy) = direction.offset();
+            assert_eq!(Direction::from_offset(dx * 3, dy * 3), Some(direction));
+            assert_eq!(direction.opposite().opposite(), direction);
+        }
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Position::new(1, 2), Position::new(4, 6));
+        assert_eq!(a.distance_to(&b), 5.0);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(b.manhattan_distance(&a), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.chebyshev_distance(&a), 0);
+    }
+}
@@ -1,22 +1,28 @@
-// src/main.rs - Point d'entrée du projet EREEA avec interface
-mod map;
-mod robot;
-mod simulation;
-mod utils;
-mod display;
-mod gui;
-
-// Import des types depuis lib.rs - CORRIGÉ pour final_project
-use final_project::{Position, TerrainType, Cell, ResourceType, RobotBehavior};
-use map::MapGenerator;
-use robot::Robot;
-use simulation::SimulationEngine;
-use utils::NoiseGenerator;
-use gui::GuiEngine;
+// src/main.rs - Point d'entrée du projet EREEA : menu et modes de lancement au-dessus de la bibliothèque
+use final_project::{Position, TerrainType, Cell, ResourceType};
+use final_project::console;
+use final_project::display::DisplayEngine;
+use final_project::gui::{measure_render_cost, GuiEngine, KeyMap};
+use final_project::simulation::{
+    bench_engine, print_bench_table, run_benchmarks, team_scores, MapSource, MissionStatus, Replay, ReplayPlayer, Scenario,
+    SimulationEngine,
+};
+use final_project::utils::NoiseGenerator;
 use colored::Colorize;
+use std::collections::BTreeMap;
 use std::io::{self, Write};
+use std::path::Path;
+
+// Fichier dans lequel les modes GUI et automatique enregistrent leur replay
+const REPLAY_FILE: &str = "ereea_replay.json";
 
 fn main() {
+    // `ereea bench` : mesures sans interface ni question, pour comparer deux versions
+    if std::env::args().nth(1).as_deref() == Some("bench") {
+        run_bench_mode();
+        return;
+    }
+
     // Bannière de démarrage
     println!("{}", "🚀 EREEA - Essaim de Robots pour l'Exploration Astrobiologique".bright_blue().bold());
     println!("{}", "   Projet Rust - Simulation de robots autonomes".bright_cyan());
@@ -30,9 +36,12 @@ fn main() {
         println!("3. 🚀 Mode automatique rapide");
         println!("4. 📊 Mode classique (50 tours)");
         println!("5. 🧪 Tests et démonstration");
-        println!("6. ❌ Quitter");
+        println!("6. 🎞️  Revoir un replay");
+        println!("7. 📜 Charger un scénario (GUI)");
+        println!("8. 📈 Mode batch (plusieurs graines)");
+        println!("9. ❌ Quitter");
 
-        print!("\nChoisissez un mode (1-6): ");
+        print!("\nChoisissez un mode (1-9): ");
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
@@ -60,54 +69,249 @@ fn main() {
                 break;
             },
             "6" => {
+                launch_replay_mode();
+                break;
+            },
+            "7" => {
+                launch_scenario_mode();
+                break;
+            },
+            "8" => {
+                launch_batch_mode();
+                break;
+            },
+            "9" => {
                 println!("{}", "👋 Au revoir !".bright_green());
                 break;
             },
             _ => {
-                println!("{}", "❌ Choix invalide. Entrez un numéro entre 1 et 6.".bright_red());
+                println!("{}", "❌ Choix invalide. Entrez un numéro entre 1 et 9.".bright_red());
             }
         }
     }
 }
 
+fn run_bench_mode() {
+    println!("{}", "📈 Mode bench : compiler en --release pour des chiffres représentatifs".bright_cyan());
+    let rows = run_benchmarks();
+    print_bench_table(&rows);
+
+    // Volume envoyé au terminal par l'interface temps réel, sur un écran virtuel de 160x50
+    let mut engine = bench_engine(100, 50);
+    match measure_render_cost(&mut engine, 50, 160, 50) {
+        Ok(cost) => println!(
+            "📺 Rendu GUI 160x50: {} octets/image en redessin complet, {} en différentiel (-{:.0}%)",
+            cost.full_redraw,
+            cost.incremental,
+            100.0 - cost.incremental as f64 * 100.0 / cost.full_redraw.max(1) as f64
+        ),
+        Err(e) => println!("{}", format!("❌ Mesure du rendu impossible: {}", e).bright_red()),
+    }
+}
+
 fn launch_gui_mode() {
     println!("{}", "🖥️  Mode GUI temps réel sélectionné !".bright_green().bold());
     println!("{}", "Lancement de l'interface graphique...".bright_cyan());
 
     // Génération de la carte et des robots
-    let (game_map, robots) = create_simulation_environment();
-    let simulation = SimulationEngine::new(game_map, robots);
+    let simulation = create_simulation_environment();
+    run_gui(simulation, &Scenario::bundled_default().keys);
+}
+
+fn launch_scenario_mode() {
+    println!("{}", "📜 Mode scénario sélectionné !".bright_green().bold());
+
+    print!("Fichier de scénario (.toml): ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+
+    let scenario = match Scenario::load(Path::new(input.trim())) {
+        Ok(scenario) => scenario,
+        Err(e) => {
+            println!("{}", format!("❌ Impossible de charger le scénario: {}", e).bright_red());
+            return;
+        }
+    };
+    let Some(simulation) = build_scenario(&scenario) else { return };
+
+    run_gui(simulation, &scenario.keys);
+}
+
+fn launch_batch_mode() {
+    println!("{}", "📈 Mode batch sélectionné !".bright_green().bold());
+
+    print!("Nombre de simulations [10]: ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let runs = input.trim().parse::<u64>().unwrap_or(10).max(1);
+
+    print!("Fichier de scénario (.toml) [défaut]: ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let mut scenario = match input.trim() {
+        "" => Scenario::bundled_default(),
+        path => match Scenario::load(Path::new(path)) {
+            Ok(scenario) => scenario,
+            Err(e) => {
+                println!("{}", format!("❌ Impossible de charger le scénario: {}", e).bright_red());
+                return;
+            }
+        },
+    };
+    let first_seed = match scenario.map {
+        MapSource::Generator { seed, .. } => seed,
+        MapSource::File { .. } => 0,
+    };
+
+    println!("\n📜 Scénario: {} ({} simulations sans affichage)", scenario.name, runs);
+    println!("{:>6} │ {:>5} │ {:>12} │ {:>8} │ {:>5} │ {:>6} │ Mission",
+             "Graine", "Tours", "Exploration", "Mineraux", "Sites", "Robots");
+    println!("{}", "─".repeat(80));
+
+    let mut successes = 0;
+    // Victoires de chaque équipe en compétition
+    let mut wins = vec![0; scenario.teams.len()];
+    for seed in first_seed..first_seed + runs {
+        if let MapSource::Generator { seed: scenario_seed, .. } = &mut scenario.map {
+            *scenario_seed = seed;
+        }
+
+        let mut simulation = match scenario.build_engine() {
+            Ok(simulation) => simulation,
+            Err(e) => {
+                println!("{:>6} │ {}", seed, format!("❌ {}", e).bright_red());
+                continue;
+            }
+        };
+        simulation.run_headless();
+
+        if simulation.mission_status == MissionStatus::Reussie {
+            successes += 1;
+        }
+
+        let status = simulation.mission_status.label();
+        println!("{:>6} │ {:>5} │ {:>11.1}% │ {:>8} │ {:>5} │ {:>6} │ {}",
+                 seed,
+                 simulation.turn,
+                 simulation.exploration_percentage(),
+                 simulation.delivered(ResourceType::Mineraux),
+                 simulation.science_sites_completed,
+                 simulation.alive_robot_count(),
+                 if simulation.mission_status == MissionStatus::Reussie { status.bright_green() } else { status.bright_red() });
+
+        if simulation.teams.len() > 1 {
+            let scores = team_scores(&simulation);
+            let ranking: Vec<String> = scores
+                .iter()
+                .map(|score| format!("{} {}", simulation.teams[score.team].name, score.points))
+                .collect();
+            println!("       │ 🏆 {}", ranking.join(" > "));
+            wins[scores[0].team] += 1;
+        }
+    }
+
+    println!("{}", "─".repeat(80));
+    println!("🎯 Missions réussies: {}/{}", successes, runs);
+    for (team, count) in scenario.teams.iter().zip(&wins) {
+        println!("🏆 {}: {} victoire(s)", team.name, count);
+    }
+}
+
+fn run_gui(mut simulation: SimulationEngine, keys: &BTreeMap<String, Vec<String>>) {
+    simulation.start_recording();
 
     // Créer l'interface GUI
-    let mut gui = GuiEngine::new(simulation.width, simulation.height);
+    let mut gui = GuiEngine::new();
+    match KeyMap::from_config(keys) {
+        Ok(keymap) => gui.set_keymap(keymap),
+        Err(e) => println!("{}", format!("❌ Touches du scénario ignorées ({}), disposition par défaut", e).bright_red()),
+    }
 
     // Lancer la simulation GUI
-    match gui.run_gui_simulation(simulation) {
+    match gui.run_gui_simulation(&mut simulation) {
         Ok(_) => println!("{}", "✅ Simulation GUI terminée avec succès !".bright_green()),
         Err(e) => println!("{}", format!("❌ Erreur GUI: {}", e).bright_red()),
     }
+
+    save_replay(&mut simulation);
+}
+
+fn launch_replay_mode() {
+    println!("{}", "🎞️  Mode replay sélectionné !".bright_green().bold());
+
+    print!("Fichier de replay [{}]: ", REPLAY_FILE);
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let path = match input.trim() {
+        "" => REPLAY_FILE,
+        other => other,
+    };
+
+    let replay = match Replay::load(Path::new(path)) {
+        Ok(replay) => replay,
+        Err(e) => {
+            println!("{}", format!("❌ Impossible de charger le replay: {}", e).bright_red());
+            return;
+        }
+    };
+
+    println!("✅ Replay chargé: {} tours", replay.turn_count());
+    let mut player = match ReplayPlayer::new(replay) {
+        Ok(player) => player,
+        Err(e) => {
+            println!("{}", format!("❌ Replay incohérent: {}", e).bright_red());
+            return;
+        }
+    };
+    let mut gui = GuiEngine::new();
+    if let Ok(keymap) = KeyMap::from_config(&Scenario::bundled_default().keys) {
+        gui.set_keymap(keymap);
+    }
+
+    if let Err(e) = gui.run_replay_viewer(&mut player) {
+        println!("{}", format!("❌ Erreur GUI: {}", e).bright_red());
+    }
+}
+
+fn save_replay(simulation: &mut SimulationEngine) {
+    if let Some(replay) = simulation.take_recording() {
+        match replay.save(Path::new(REPLAY_FILE)) {
+            Ok(_) => println!("🎞️  Replay enregistré dans {} ({} tours)", REPLAY_FILE, replay.turn_count()),
+            Err(e) => println!("{}", format!("❌ Échec de l'enregistrement du replay: {}", e).bright_red()),
+        }
+    }
 }
 
 fn launch_interactive_mode() {
     println!("{}", "🎮 Mode interactif sélectionné !".bright_green().bold());
 
     // Génération de la carte et des robots
-    let (game_map, robots) = create_simulation_environment();
+    let mut simulation = create_simulation_environment();
 
     // Lancer la simulation interactive
-    let mut simulation = SimulationEngine::new(game_map, robots);
-    simulation.run_interactive();
+    console::run_interactive(&mut simulation);
 }
 
 fn launch_auto_mode() {
     println!("{}", "🚀 Mode automatique sélectionné !".bright_green().bold());
 
     // Génération de la carte et des robots
-    let (game_map, robots) = create_simulation_environment();
+    let mut simulation = create_simulation_environment();
 
     // Lancer la simulation automatique
-    let mut simulation = SimulationEngine::new(game_map, robots);
-    simulation.run_auto_mode();
+    simulation.start_recording();
+    console::run_auto(&mut simulation);
+    console::print_final_report(&simulation);
+
+    save_replay(&mut simulation);
 }
 
 fn launch_classic_mode() {
@@ -122,7 +326,7 @@ fn launch_classic_mode() {
     println!("🗺️  Terrain généré: {:?}", test_terrain);
 
     // Génération de la carte et des robots
-    let (game_map, robots) = create_simulation_environment();
+    let mut simulation = create_simulation_environment();
 
     // Test des types de base
     println!("\n{}", "=== Tests des types de base ===".bright_yellow());
@@ -145,8 +349,7 @@ fn launch_classic_mode() {
 
     // Lancement de la simulation classique
     println!("\n{}", "=== Lancement de la simulation ===".bright_green());
-    let mut simulation = SimulationEngine::new(game_map, robots);
-    simulation.run(50); // 50 tours de simulation
+    console::run_classic(&mut simulation, 50); // 50 tours de simulation
 
     println!("\n{}", "🎯 Simulation terminée ! Projet EREEA opérationnel.".bright_green().bold());
 }
@@ -154,93 +357,52 @@ fn launch_classic_mode() {
 fn run_demo() {
     println!("{}", "🧪 Mode démonstration lancé !".bright_green().bold());
 
-    // Créer une petite carte pour la démo
-    let mut demo_map = Vec::new();
-    for y in 0..10 {
-        let mut row = Vec::new();
-        for x in 0..15 {
-            let terrain = match (x, y) {
-                (0, _) | (14, _) | (_, 0) | (_, 9) => TerrainType::Obstacle, // Bordures
-                (3, 3) | (7, 6) | (11, 2) => TerrainType::Montagne,
-                (5, 7) | (9, 4) => TerrainType::Cratere,
-                _ => TerrainType::Plaine,
-            };
-
-            let mut cell = Cell::new(terrain);
-
-            // Ajouter quelques ressources pour la démo
-            match (x, y) {
-                (4, 4) => cell.add_resource(ResourceType::Energie, 30),
-                (8, 3) => cell.add_resource(ResourceType::Mineraux, 50),
-                (5, 7) => cell.add_resource(ResourceType::LieuxInteret, 1),
-                (9, 4) => cell.add_resource(ResourceType::LieuxInteret, 1),
-                _ => {}
-            }
-
-            row.push(cell);
+    // Petite carte avec ressources pré-placées et un robot de chaque type
+    let mut simulation = match Scenario::bundled_demo().build_engine() {
+        Ok(simulation) => simulation,
+        Err(e) => {
+            println!("{}", format!("❌ Scénario de démonstration invalide: {}", e).bright_red());
+            return;
         }
-        demo_map.push(row);
-    }
-
-    // Créer des robots pour la démo
-    let demo_robots = vec![
-        Robot::new(1, Position::new(2, 2), RobotBehavior::Explorateur),
-        Robot::new(2, Position::new(3, 2), RobotBehavior::Collecteur),
-        Robot::new(3, Position::new(4, 2), RobotBehavior::Scientifique),
-    ];
+    };
 
     println!("\n{}", "🗺️  Carte de démonstration générée !".bright_cyan());
     println!("{}", "Cette carte contient des ressources pré-placées pour démontrer le système.".bright_white());
 
     // Afficher la carte initiale
-    use crate::display::DisplayEngine;
-    DisplayEngine::display_map(&demo_map, &demo_robots);
-    DisplayEngine::display_robot_stats(&demo_robots);
+    DisplayEngine::display_map(&simulation.map, &simulation.robots, &simulation.depots, &simulation.teams);
+    DisplayEngine::display_robot_stats(&simulation.robots);
 
     println!("\n{}", "Appuyez sur ENTER pour lancer la simulation interactive...".bright_yellow());
     let mut input = String::new();
     io::stdin().read_line(&mut input).unwrap();
 
     // Lancer la simulation
-    let mut simulation = SimulationEngine::new(demo_map, demo_robots);
-    simulation.run_interactive();
+    console::run_interactive(&mut simulation);
 }
 
-fn create_simulation_environment() -> (Vec<Vec<Cell>>, Vec<Robot>) {
-    println!("{}", "=== Génération de l'environnement ===".bright_yellow());
-
-    // Carte VRAIMENT plus large pour utiliser tout l'écran
-    let map_generator = MapGenerator::new(42, 80, 20); // 80x20 pour utiliser toute la largeur
-    let game_map = map_generator.generate();
-    println!("✅ Carte générée: {}x{}", game_map[0].len(), game_map.len());
-
-    // Plus de robots bien répartis sur la grande largeur
-    let mut robots = Vec::new();
-
-    // Explorateurs - très bien répartis
-    robots.push(Robot::new(1, Position::new(5, 2), RobotBehavior::Explorateur));
-    robots.push(Robot::new(2, Position::new(20, 3), RobotBehavior::Explorateur));
-    robots.push(Robot::new(3, Position::new(40, 5), RobotBehavior::Explorateur));
-    robots.push(Robot::new(4, Position::new(60, 2), RobotBehavior::Explorateur));
-    robots.push(Robot::new(5, Position::new(75, 7), RobotBehavior::Explorateur));
-
-    // Collecteurs - bien espacés
-    robots.push(Robot::new(6, Position::new(10, 8), RobotBehavior::Collecteur));
-    robots.push(Robot::new(7, Position::new(30, 4), RobotBehavior::Collecteur));
-    robots.push(Robot::new(8, Position::new(50, 9), RobotBehavior::Collecteur));
-    robots.push(Robot::new(9, Position::new(70, 6), RobotBehavior::Collecteur));
+fn create_simulation_environment() -> SimulationEngine {
+    build_scenario(&Scenario::bundled_default()).expect("scénario par défaut invalide")
+}
 
-    // Scientifiques - dans différentes zones
-    robots.push(Robot::new(10, Position::new(15, 12), RobotBehavior::Scientifique));
-    robots.push(Robot::new(11, Position::new(45, 8), RobotBehavior::Scientifique));
-    robots.push(Robot::new(12, Position::new(65, 15), RobotBehavior::Scientifique));
+fn build_scenario(scenario: &Scenario) -> Option<SimulationEngine> {
+    println!("{}", "=== Génération de l'environnement ===".bright_yellow());
+    println!("📜 Scénario: {}", scenario.name);
 
-    println!("✅ {} robots créés avec différents comportements", robots.len());
+    let simulation = match scenario.build_engine() {
+        Ok(simulation) => simulation,
+        Err(e) => {
+            println!("{}", format!("❌ {}", e).bright_red());
+            return None;
+        }
+    };
+    println!("✅ Carte générée: {}x{}", simulation.width, simulation.height);
+    println!("✅ {} robots créés avec différents comportements", simulation.robots.len());
 
-    for robot in &robots {
+    for robot in &simulation.robots {
         println!("🤖 Robot {} ({:?}) déployé à ({:2},{:2})",
                  robot.id, robot.behavior, robot.position.x, robot.position.y);
     }
 
-    (game_map, robots)
-}
\ No newline at end of file
+    Some(simulation)
+}
@@ -0,0 +1,129 @@
+// src/map/elevation.rs - Relief : pentes, coût des déplacements et ligne de vue
+use crate::{Cell, Position, TerrainType};
+use crate::robot::MOVE_ENERGY_COST;
+use crate::Grid;
+
+/// Élévation maximale d'une cellule
+pub const MAX_ELEVATION: u8 = 100;
+/// Dénivelé à gravir pour chaque point d'énergie supplémentaire
+pub const CLIMB_PER_ENERGY: u8 = 10;
+/// Dénivelé à partir duquel une descente ne coûte plus d'énergie
+pub const FREE_DESCENT: u8 = 10;
+/// Pente (écart avec un voisin) au-delà de laquelle la case est une falaise
+pub const CLIFF_SLOPE: u8 = 20;
+/// Hauteur des capteurs et antennes au-dessus du sol
+pub const SENSOR_HEIGHT: f32 = 5.0;
+
+/// Terrain déduit du relief : falaises, sommets, cuvettes, plaines
+pub fn terrain_from_relief(elevation: u8, slope: u8) -> TerrainType {
+    match (elevation, slope) {
+        (_, s) if s >= CLIFF_SLOPE => TerrainType::Obstacle,
+        (e, _) if e >= 60 => TerrainType::Montagne,
+        (e, _) if e < 30 => TerrainType::Cratere,
+        _ => TerrainType::Plaine,
+    }
+}
+
+/// Pente d'une case : plus grand écart d'élévation avec ses voisins
+pub fn slope_at(elevations: &Grid<u8>, pos: Position) -> u8 {
+    let elevation = elevations[pos];
+    elevations
+        .neighbors8(pos)
+        .map(|neighbor| elevations[neighbor].abs_diff(elevation))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Énergie d'un pas de `from` vers `to` : plus chère en montée, gratuite en forte descente
+pub fn move_energy_cost(from: &Cell, to: &Cell) -> u32 {
+    if to.elevation > from.elevation {
+        let climb = to.elevation - from.elevation;
+        MOVE_ENERGY_COST + (climb / CLIMB_PER_ENERGY) as u32
+    } else if from.elevation - to.elevation >= FREE_DESCENT {
+        MOVE_ENERGY_COST.saturating_sub(1)
+    } else {
+        MOVE_ENERGY_COST
+    }
+}
+
+/// Vrai si aucun relief intermédiaire ne dépasse la ligne entre les deux capteurs
+pub fn line_of_sight(map: &Grid<Cell>, from: Position, to: Position) -> bool {
+    let (Some(start), Some(end)) = (map.get(from), map.get(to)) else {
+        return false;
+    };
+
+    let (dx, dy) = map.delta(from, to);
+    let steps = dx.unsigned_abs().max(dy.unsigned_abs());
+    let start_height = start.elevation as f32 + SENSOR_HEIGHT;
+    let end_height = end.elevation as f32 + SENSOR_HEIGHT;
+
+    (1..steps).all(|i| {
+        let t = i as f32 / steps as f32;
+        let offset = ((dx as f32 * t).round() as isize, (dy as f32 * t).round() as isize);
+        let sight_height = start_height + (end_height - start_height) * t;
+        map.offset(from, offset)
+            .is_none_or(|pos| map[pos].elevation as f32 <= sight_height)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Topology;
+
+    fn at(elevation: u8) -> Cell {
+        let mut cell = Cell::new(TerrainType::Plaine);
+        cell.elevation = elevation;
+        cell
+    }
+
+    // Ligne de plaine à 40, avec une bosse de `bump` au milieu
+    fn ridge(bump: u8) -> Grid<Cell> {
+        let mut map = Grid::new(9, 3, at(40));
+        map[Position::new(4, 1)].elevation = bump;
+        map
+    }
+
+    #[test]
+    fn climbing_costs_more_and_steep_descents_are_free() {
+        assert_eq!(move_energy_cost(&at(40), &at(40)), MOVE_ENERGY_COST);
+        assert_eq!(move_energy_cost(&at(40), &at(49)), MOVE_ENERGY_COST);
+        assert_eq!(move_energy_cost(&at(40), &at(50)), MOVE_ENERGY_COST + 1);
+        assert_eq!(move_energy_cost(&at(20), &at(55)), MOVE_ENERGY_COST + 3);
+        assert_eq!(move_energy_cost(&at(50), &at(41)), MOVE_ENERGY_COST);
+        assert_eq!(move_energy_cost(&at(50), &at(40)), MOVE_ENERGY_COST - 1);
+    }
+
+    #[test]
+    fn relief_blocks_the_line_of_sight() {
+        let (west, east) = (Position::new(0, 1), Position::new(8, 1));
+        assert!(line_of_sight(&ridge(40), west, east));
+        // Les capteurs voient au-dessus d'une bosse plus basse qu'eux
+        assert!(line_of_sight(&ridge(45), west, east));
+        assert!(!line_of_sight(&ridge(46), west, east));
+        // Une bosse hors du trajet ne gêne pas, deux voisins se voient toujours
+        assert!(line_of_sight(&ridge(90), Position::new(0, 0), Position::new(8, 0)));
+        assert!(line_of_sight(&ridge(90), Position::new(3, 1), Position::new(4, 1)));
+        assert!(!line_of_sight(&ridge(40), west, Position::new(9, 1)));
+    }
+
+    #[test]
+    fn line_of_sight_follows_the_short_way_around() {
+        // Sur un cylindre, (0,1) et (8,1) sont voisins : la bosse du milieu n'est plus sur le trajet
+        let map = ridge(90).with_topology(Topology::Cylindre);
+        assert!(line_of_sight(&map, Position::new(0, 1), Position::new(8, 1)));
+        assert!(!line_of_sight(&map, Position::new(2, 1), Position::new(6, 1)));
+    }
+
+    #[test]
+    fn slope_and_terrain_from_relief() {
+        let map = ridge(70);
+        let elevations = Grid::from_fn(9, 3, |pos| map[pos].elevation);
+        assert_eq!(slope_at(&elevations, Position::new(3, 0)), 30);
+        assert_eq!(slope_at(&elevations, Position::new(0, 0)), 0);
+        assert_eq!(terrain_from_relief(70, 30), TerrainType::Obstacle);
+        assert_eq!(terrain_from_relief(70, 5), TerrainType::Montagne);
+        assert_eq!(terrain_from_relief(20, 5), TerrainType::Cratere);
+        assert_eq!(terrain_from_relief(45, 5), TerrainType::Plaine);
+    }
+}
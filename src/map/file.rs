@@ -0,0 +1,618 @@
+// src/map/file.rs - Import/export de cartes (ASCII et JSON)
+use crate::{Cell, ResourceType, TerrainType};
+use crate::{Grid, Topology};
+use crate::map::elevation::MAX_ELEVATION;
+use crate::map::terrain::{terrain_from_symbol, terrain_symbol};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Format ASCII :
+//   ; commentaire
+//   [topology]
+//   tore
+//   [terrain]
+//   #####
+//   #.^O#
+//   #####
+//   [elevation]
+//   80 80 80 80 80
+//   80 40 70 20 80
+//   80 80 80 80 80
+//   [resources]
+//   2,1 Mineraux=40 Energie=10
+//
+// Les symboles de terrain sont ceux de `terrain_symbol` ; '#' étant un obstacle,
+// les commentaires commencent par ';'. La section [elevation] (0 à 100, une ligne
+// par ligne de terrain) est facultative : sans elle, chaque terrain prend son
+// élévation typique. La section [topology] ("bornee", "cylindre" ou "tore") aussi :
+// la carte est alors bornée.
+
+#[derive(Debug)]
+pub enum MapFileError {
+    Io(io::Error),
+    /// Erreur de syntaxe ou de contenu, positions à partir de 1
+    Parse { line: usize, column: usize, message: String },
+}
+
+impl fmt::Display for MapFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapFileError::Io(e) => write!(f, "erreur d'accès au fichier: {}", e),
+            MapFileError::Parse { line, column, message } => {
+                write!(f, "ligne {}, colonne {}: {}", line, column, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapFileError {}
+
+impl From<io::Error> for MapFileError {
+    fn from(e: io::Error) -> Self {
+        MapFileError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for MapFileError {
+    fn from(e: serde_json::Error) -> Self {
+        // Le message de serde_json se termine déjà par la position : on la retire
+        let suffix = format!(" at line {} column {}", e.line(), e.column());
+        let message = e.to_string();
+        MapFileError::Parse {
+            line: e.line(),
+            column: e.column(),
+            message: message.strip_suffix(&suffix).unwrap_or(&message).to_string(),
+        }
+    }
+}
+
+fn parse_error(line: usize, column: usize, message: impl Into<String>) -> MapFileError {
+    MapFileError::Parse { line, column, message: message.into() }
+}
+
+/// Charge une carte ; le format est choisi d'après l'extension (.json ou ASCII)
+pub fn load_map(path: &Path) -> Result<Grid<Cell>, MapFileError> {
+    let content = fs::read_to_string(path)?;
+    if is_json_path(path) {
+        parse_json_map(&content)
+    } else {
+        parse_ascii_map(&content)
+    }
+}
+
+/// Enregistre une carte ; le format est choisi d'après l'extension (.json ou ASCII)
+pub fn save_map(map: &Grid<Cell>, path: &Path) -> Result<(), MapFileError> {
+    let content = if is_json_path(path) {
+        map_to_json(map)
+    } else {
+        map_to_ascii(map)
+    };
+    fs::write(path, content)?;
+    Ok(())
+}
+
+fn is_json_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+pub fn resource_name(resource: ResourceType) -> &'static str {
+    match resource {
+        ResourceType::Energie => "Energie",
+        ResourceType::Mineraux => "Mineraux",
+        ResourceType::LieuxInteret => "LieuxInteret",
+    }
+}
+
+pub fn resource_from_name(name: &str) -> Option<ResourceType> {
+    ResourceType::ALL.into_iter().find(|r| resource_name(*r) == name)
+}
+
+// Mêmes noms que dans les scénarios
+fn topology_name(topology: Topology) -> &'static str {
+    match topology {
+        Topology::Bornee => "bornee",
+        Topology::Cylindre => "cylindre",
+        Topology::Tore => "tore",
+    }
+}
+
+fn topology_from_name(name: &str) -> Option<Topology> {
+    [Topology::Bornee, Topology::Cylindre, Topology::Tore].into_iter().find(|t| topology_name(*t) == name)
+}
+
+// ----- ASCII -----
+
+#[derive(PartialEq)]
+enum Section {
+    None,
+    Topology,
+    Terrain,
+    Elevation,
+    Resources,
+}
+
+pub fn parse_ascii_map(content: &str) -> Result<Grid<Cell>, MapFileError> {
+    let mut section = Section::None;
+    let mut map: Vec<Vec<Cell>> = Vec::new();
+    let mut terrain_line = 0;
+    let mut elevation_rows = 0;
+    let mut elevation_line = 0;
+    let mut topology_line = 0;
+    let mut topology = None;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = raw_line.trim_end();
+
+        if line.trim().is_empty() || line.trim_start().starts_with(';') {
+            continue;
+        }
+
+        match line.trim() {
+            "[topology]" => {
+                if topology_line > 0 {
+                    return Err(parse_error(line_number, 1, "section [topology] déjà définie"));
+                }
+                section = Section::Topology;
+                topology_line = line_number;
+                continue;
+            }
+            "[terrain]" => {
+                if !map.is_empty() {
+                    return Err(parse_error(line_number, 1, "section [terrain] déjà définie"));
+                }
+                section = Section::Terrain;
+                terrain_line = line_number;
+                continue;
+            }
+            "[elevation]" => {
+                if map.is_empty() {
+                    return Err(parse_error(line_number, 1, "section [elevation] avant [terrain]"));
+                }
+                if elevation_line > 0 {
+                    return Err(parse_error(line_number, 1, "section [elevation] déjà définie"));
+                }
+                section = Section::Elevation;
+                elevation_line = line_number;
+                continue;
+            }
+            "[resources]" => {
+                section = Section::Resources;
+                continue;
+            }
+            _ => {}
+        }
+
+        match section {
+            Section::None => {
+                return Err(parse_error(
+                    line_number,
+                    1,
+                    "section attendue: [topology], [terrain], [elevation] ou [resources]",
+                ));
+            }
+            Section::Topology => {
+                let column = line.len() - line.trim_start().len() + 1;
+                if topology.is_some() {
+                    return Err(parse_error(line_number, column, "une seule topologie attendue"));
+                }
+                topology = Some(topology_from_name(line.trim()).ok_or_else(|| {
+                    parse_error(
+                        line_number,
+                        column,
+                        format!("topologie inconnue '{}', attendu bornee, cylindre ou tore", line.trim()),
+                    )
+                })?);
+            }
+            Section::Terrain => {
+                let mut row = Vec::new();
+                for (column, symbol) in line.chars().enumerate() {
+                    let terrain = terrain_from_symbol(symbol).ok_or_else(|| {
+                        parse_error(line_number, column + 1, format!("symbole de terrain inconnu '{}'", symbol))
+                    })?;
+                    row.push(Cell::new(terrain));
+                }
+
+                if let Some(first) = map.first()
+                    && row.len() != first.len()
+                {
+                    return Err(parse_error(
+                        line_number,
+                        row.len().min(first.len()) + 1,
+                        format!("ligne de largeur {} au lieu de {}", row.len(), first.len()),
+                    ));
+                }
+                map.push(row);
+            }
+            Section::Elevation => {
+                let row = map.get_mut(elevation_rows).ok_or_else(|| {
+                    parse_error(line_number, 1, format!("plus de {} lignes d'élévation", elevation_rows))
+                })?;
+                parse_elevation_line(line, line_number, row)?;
+                elevation_rows += 1;
+            }
+            Section::Resources => parse_resource_line(line, line_number, &mut map)?,
+        }
+    }
+
+    if elevation_line > 0 && elevation_rows != map.len() {
+        return Err(parse_error(
+            elevation_line,
+            1,
+            format!("{} lignes d'élévation au lieu de {}", elevation_rows, map.len()),
+        ));
+    }
+
+    if map.is_empty() {
+        let line = if terrain_line > 0 { terrain_line } else { content.lines().count().max(1) };
+        return Err(parse_error(line, 1, "aucune ligne de terrain"));
+    }
+
+    if topology_line > 0 && topology.is_none() {
+        return Err(parse_error(topology_line, 1, "topologie attendue après [topology]"));
+    }
+
+    // Les largeurs ont été vérifiées ligne par ligne
+    let map = Grid::from_rows(map).expect("lignes de terrain de même largeur");
+    Ok(map.with_topology(topology.unwrap_or_default()))
+}
+
+// Une valeur 0-100 par case de la ligne de terrain correspondante
+fn parse_elevation_line(line: &str, line_number: usize, row: &mut [Cell]) -> Result<(), MapFileError> {
+    let tokens = tokenize(line);
+    if tokens.len() != row.len() {
+        let column = tokens.get(row.len()).map_or(line.chars().count() + 1, |(column, _)| *column);
+        return Err(parse_error(
+            line_number,
+            column,
+            format!("{} élévations au lieu de {}", tokens.len(), row.len()),
+        ));
+    }
+
+    for (cell, (column, token)) in row.iter_mut().zip(tokens) {
+        cell.elevation = token
+            .parse::<u8>()
+            .ok()
+            .filter(|&elevation| elevation <= MAX_ELEVATION)
+            .ok_or_else(|| parse_error(line_number, column, format!("élévation invalide '{}', attendu 0 à {}", token, MAX_ELEVATION)))?;
+    }
+
+    Ok(())
+}
+
+// "x,y Type=quantité Type=quantité ..."
+fn parse_resource_line(line: &str, line_number: usize, map: &mut [Vec<Cell>]) -> Result<(), MapFileError> {
+    let mut tokens = tokenize(line).into_iter();
+    let (column, coordinates) = tokens
+        .next()
+        .ok_or_else(|| parse_error(line_number, 1, "coordonnées attendues"))?;
+
+    let (x, y) = coordinates
+        .split_once(',')
+        .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)))
+        .ok_or_else(|| parse_error(line_number, column, format!("coordonnées invalides '{}', attendu x,y", coordinates)))?;
+
+    let height = map.len();
+    let width = map.first().map_or(0, |row| row.len());
+    if x >= width || y >= height {
+        return Err(parse_error(
+            line_number,
+            column,
+            format!("position ({},{}) hors de la carte {}x{}", x, y, width, height),
+        ));
+    }
+
+    let mut has_resource = false;
+    for (column, token) in tokens {
+        let (name, amount) = token
+            .split_once('=')
+            .ok_or_else(|| parse_error(line_number, column, format!("attendu Type=quantité, trouvé '{}'", token)))?;
+        let resource = resource_from_name(name)
+            .ok_or_else(|| parse_error(line_number, column, format!("ressource inconnue '{}'", name)))?;
+        let amount = amount.parse::<u32>().map_err(|_| {
+            parse_error(line_number, column + name.chars().count() + 1, format!("quantité invalide '{}'", amount))
+        })?;
+
+        map[y][x].add_resource(resource, amount);
+        has_resource = true;
+    }
+
+    if !has_resource {
+        return Err(parse_error(line_number, line.chars().count() + 1, "au moins une ressource attendue"));
+    }
+
+    Ok(())
+}
+
+// Découpe une ligne en mots avec leur colonne (à partir de 1)
+fn tokenize(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start: Option<(usize, usize)> = None; // (colonne, octet)
+
+    for (column, (byte, c)) in line.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, byte)),
+            (true, Some((token_column, token_byte))) => {
+                tokens.push((token_column, &line[token_byte..byte]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((token_column, token_byte)) = start {
+        tokens.push((token_column, &line[token_byte..]));
+    }
+
+    tokens
+}
+
+pub fn map_to_ascii(map: &Grid<Cell>) -> String {
+    let mut output = format!("; Carte EREEA\n[topology]\n{}\n[terrain]\n", topology_name(map.topology()));
+
+    for row in map.rows() {
+        output.extend(row.iter().map(|cell| terrain_symbol(&cell.terrain)));
+        output.push('\n');
+    }
+
+    output.push_str("[elevation]\n");
+    for row in map.rows() {
+        let elevations: Vec<String> = row.iter().map(|cell| cell.elevation.to_string()).collect();
+        output.push_str(&elevations.join(" "));
+        output.push('\n');
+    }
+
+    output.push_str("[resources]\n");
+    for (pos, cell) in map.enumerate() {
+        let resources: Vec<String> = cell
+            .resources
+            .iter()
+            .map(|(r, amount)| format!("{}={}", resource_name(r), amount))
+            .collect();
+
+        if !resources.is_empty() {
+            output.push_str(&format!("{},{} {}\n", pos.x, pos.y, resources.join(" ")));
+        }
+    }
+
+    output
+}
+
+// ----- JSON -----
+
+#[derive(Serialize, Deserialize)]
+struct MapFile {
+    width: usize,
+    height: usize,
+    /// Carte bornée si absente
+    #[serde(default)]
+    topology: Topology,
+    cells: Vec<Vec<CellData>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CellData {
+    terrain: TerrainType,
+    /// Élévation typique du terrain si absente
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "elevation_in_range")]
+    elevation: Option<u8>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    resources: BTreeMap<ResourceType, u32>,
+}
+
+// Vérifiée pendant la lecture pour que serde_json situe l'erreur sur la valeur fautive
+fn elevation_in_range<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u8>, D::Error> {
+    let elevation = Option::<u8>::deserialize(deserializer)?;
+    match elevation {
+        Some(e) if e > MAX_ELEVATION => {
+            Err(serde::de::Error::custom(format!("élévation {} au-delà de {}", e, MAX_ELEVATION)))
+        }
+        _ => Ok(elevation),
+    }
+}
+
+pub fn parse_json_map(content: &str) -> Result<Grid<Cell>, MapFileError> {
+    let file: MapFile = serde_json::from_str(content)?;
+
+    // Serde ne connaît plus les positions une fois la lecture finie : on retrouve la ligne de cellules dans le texte
+    let shape_error = |row: usize, message: String| {
+        let (line, column) = locate_row(content, row);
+        parse_error(line, column, message)
+    };
+    if file.width == 0 || file.height == 0 {
+        return Err(shape_error(0, "carte vide".to_string()));
+    }
+    if file.cells.len() != file.height {
+        return Err(shape_error(
+            file.height.min(file.cells.len()),
+            format!("{} lignes de cellules au lieu de height = {}", file.cells.len(), file.height),
+        ));
+    }
+
+    let mut map = Vec::with_capacity(file.height);
+    for (y, row) in file.cells.into_iter().enumerate() {
+        if row.len() != file.width {
+            return Err(shape_error(
+                y,
+                format!("ligne de cellules {} de largeur {} au lieu de width = {}", y, row.len(), file.width),
+            ));
+        }
+
+        map.push(
+            row.into_iter()
+                .map(|data| {
+                    let mut cell = Cell::new(data.terrain);
+                    cell.elevation = data.elevation.unwrap_or(cell.elevation);
+                    for (resource, amount) in data.resources {
+                        cell.add_resource(resource, amount);
+                    }
+                    cell
+                })
+                .collect(),
+        );
+    }
+
+    let map = Grid::from_rows(map).expect("lignes de cellules de même largeur");
+    Ok(map.with_topology(file.topology))
+}
+
+// Position du crochet ouvrant de la ligne de cellules `row` dans un JSON déjà validé,
+// ou du crochet fermant de `cells` s'il a moins de lignes
+fn locate_row(content: &str, row: usize) -> (usize, usize) {
+    let (mut line, mut column) = (1, 0);
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut key = String::new();
+    let mut last_key = String::new();
+    let mut cells_depth = None;
+    let mut rows = 0;
+
+    for c in content.chars() {
+        if c == '\n' {
+            line += 1;
+            column = 0;
+            continue;
+        }
+        column += 1;
+
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+                last_key = std::mem::take(&mut key);
+            } else {
+                key.push(c);
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '[' | '{' => {
+                depth += 1;
+                if c == '[' && depth == 2 && cells_depth.is_none() && last_key == "cells" {
+                    cells_depth = Some(depth);
+                } else if c == '[' && cells_depth.is_some_and(|cells| depth == cells + 1) {
+                    if rows == row {
+                        return (line, column);
+                    }
+                    rows += 1;
+                }
+            }
+            ']' | '}' => {
+                if cells_depth == Some(depth) {
+                    return (line, column);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+
+    (line, column.max(1))
+}
+
+pub fn map_to_json(map: &Grid<Cell>) -> String {
+    let file = MapFile {
+        width: map.width(),
+        height: map.height(),
+        topology: map.topology(),
+        cells: map
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| CellData {
+                        terrain: cell.terrain,
+                        elevation: Some(cell.elevation),
+                        resources: cell.resources.into(),
+                    })
+                    .collect()
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&file).expect("sérialisation de carte infaillible")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+    use crate::map::MapGenerator;
+
+    fn sample_map() -> Grid<Cell> {
+        MapGenerator::new(7, 12, 9).generate().with_topology(Topology::Cylindre)
+    }
+
+    // `Cell` n'est pas comparable : on compare la carte sérialisée, topologie comprise
+    fn fingerprint(map: &Grid<Cell>) -> String {
+        serde_json::to_string(map).unwrap()
+    }
+
+    fn parse_position(result: Result<Grid<Cell>, MapFileError>) -> (usize, usize, String) {
+        match result {
+            Err(MapFileError::Parse { line, column, message }) => (line, column, message),
+            other => panic!("erreur de syntaxe attendue, obtenu {:?}", other.map(|map| map.len())),
+        }
+    }
+
+    #[test]
+    fn ascii_round_trip_keeps_everything() {
+        let map = sample_map();
+        assert_eq!(fingerprint(&parse_ascii_map(&map_to_ascii(&map)).unwrap()), fingerprint(&map));
+    }
+
+    #[test]
+    fn json_round_trip_keeps_everything() {
+        let map = sample_map().with_topology(Topology::Tore);
+        assert_eq!(fingerprint(&parse_json_map(&map_to_json(&map)).unwrap()), fingerprint(&map));
+    }
+
+    #[test]
+    fn unknown_symbol_is_located() {
+        let content = "; carte\n[terrain]\n#####\n#.X.#\n#####\n";
+        let (line, column, message) = parse_position(parse_ascii_map(content));
+        assert_eq!((line, column), (4, 3));
+        assert!(message.contains("'X'"));
+    }
+
+    #[test]
+    fn ragged_row_is_refused() {
+        let (line, column, _) = parse_position(parse_ascii_map("[terrain]\n.....\n...\n.....\n"));
+        assert_eq!((line, column), (3, 4));
+
+        let json = r#"{"width": 2, "height": 2, "cells": [
+            [{"terrain": "Plaine"}, {"terrain": "Plaine"}],
+            [{"terrain": "Plaine"}]
+        ]}"#;
+        let (line, column, message) = parse_position(parse_json_map(json));
+        assert_eq!((line, column), (3, 13));
+        assert!(message.contains("ligne de cellules 1"));
+
+        // Ligne manquante : le crochet fermant de `cells`
+        let json = "{\"width\": 1, \"height\": 2,\n \"cells\": [[{\"terrain\": \"Plaine\"}]]}";
+        let (line, column, message) = parse_position(parse_json_map(json));
+        assert_eq!((line, column), (2, 35));
+        assert!(message.contains("height = 2"));
+    }
+
+    #[test]
+    fn json_errors_point_at_the_faulty_value() {
+        let json = "{\"width\": 1, \"height\": 1, \"cells\": [\n  [{\"terrain\": \"Plaine\", \"elevation\": 150}]\n]}";
+        let (line, _, message) = parse_position(parse_json_map(json));
+        assert_eq!(line, 2);
+        assert!(message.contains("150"));
+
+        let map = parse_json_map(r#"{"width": 1, "height": 1, "cells": [[{"terrain": "Montagne"}]]}"#).unwrap();
+        assert_eq!(map.topology(), Topology::Bornee);
+        assert_eq!(map[Position::new(0, 0)].terrain, TerrainType::Montagne);
+    }
+}
@@ -1,5 +1,13 @@
 // src/map/generator.rs - Générateur de cartes simple
 use crate::{Cell, TerrainType, ResourceType};
+use crate::Grid;
+use crate::map::elevation::{slope_at, terrain_from_relief, MAX_ELEVATION};
+use crate::utils::NoiseGenerator;
+
+// Relief : grandes structures + détails (échelle, poids)
+const RELIEF_SCALE: f64 = 0.08;
+const DETAIL_SCALE: f64 = 0.3;
+const DETAIL_WEIGHT: f32 = 0.35;
 
 pub struct MapGenerator {
     seed: u64,
@@ -12,37 +20,33 @@ impl MapGenerator {
         MapGenerator { seed, width, height }
     }
 
-    pub fn generate(&self) -> Vec<Vec<Cell>> {
-        let mut map = Vec::with_capacity(self.height);
+    pub fn generate(&self) -> Grid<Cell> {
+        // Le terrain découle de l'élévation et de la pente
+        let elevations = self.generate_elevations();
 
-        for y in 0..self.height {
-            let mut row = Vec::with_capacity(self.width);
-            for x in 0..self.width {
-                let terrain = self.generate_terrain(x, y);
-                let mut cell = Cell::new(terrain);
+        Grid::from_fn(self.width, self.height, |pos| {
+            let elevation = elevations[pos];
+            let terrain = terrain_from_relief(elevation, slope_at(&elevations, pos));
+            let mut cell = Cell::new(terrain);
+            cell.elevation = elevation;
 
-                // Ajouter des ressources basé sur la position
-                self.add_resources_by_position(x, y, &mut cell);
+            // Ajouter des ressources basé sur la position
+            self.add_resources_by_position(pos.x, pos.y, &mut cell);
 
-                row.push(cell);
-            }
-            map.push(row);
-        }
-
-        map
+            cell
+        })
     }
 
-    fn generate_terrain(&self, x: usize, y: usize) -> TerrainType {
-        // Génération simple basée sur la position
-        let hash = self.simple_hash(x, y);
-        let value = (hash % 100) as f32 / 100.0;
+    fn generate_elevations(&self) -> Grid<u8> {
+        let relief = NoiseGenerator::new(self.seed as u32, RELIEF_SCALE);
+        let detail = NoiseGenerator::new((self.seed as u32).wrapping_add(1), DETAIL_SCALE);
 
-        match value {
-            v if v < 0.1 => TerrainType::Obstacle,
-            v if v < 0.3 => TerrainType::Montagne,
-            v if v < 0.4 => TerrainType::Cratere,
-            _ => TerrainType::Plaine,
-        }
+        Grid::from_fn(self.width, self.height, |pos| {
+            let (x, y) = (pos.x as f64, pos.y as f64);
+            let elevation = relief.get_elevation(x, y) * (1.0 - DETAIL_WEIGHT)
+                + detail.get_elevation(x, y) * DETAIL_WEIGHT;
+            elevation.round().clamp(0.0, MAX_ELEVATION as f32) as u8
+        })
     }
 
     fn add_resources_by_position(&self, x: usize, y: usize, cell: &mut Cell) {
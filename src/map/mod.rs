@@ -1,7 +1,15 @@
 // src/map/mod.rs - Module de gestion des cartes
+pub mod elevation;
+pub mod file;
 pub mod generator;
+pub mod pathfinding;
+pub mod spatial;
 pub mod terrain;
 
 // Réexporter les types publics
+pub use elevation::*;
+pub use file::*;
 pub use generator::*;
+pub use pathfinding::*;
+pub use spatial::*;
 pub use terrain::*;
\ No newline at end of file
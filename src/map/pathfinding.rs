@@ -0,0 +1,235 @@
+// src/map/pathfinding.rs - Recherche de chemin sur la carte (respecte la topologie et le relief)
+use crate::{Cell, Position};
+use crate::Grid;
+use crate::map::elevation::move_energy_cost;
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+/// Rayon (en cases) de la fenêtre explorée par `find_path_to_any` autour du départ : les cibles
+/// hors de la fenêtre sont écartées sans recherche, et le robot avance vers elles en ligne droite
+pub const MAX_SEARCH_RADIUS: usize = 100;
+
+/// Chemin le moins coûteux en énergie, en 8 directions, entre deux cases praticables.
+/// Le chemin exclut le départ et se termine sur l'arrivée ; `None` si inaccessible.
+pub fn find_path(map: &Grid<Cell>, from: Position, to: Position) -> Option<Vec<Position>> {
+    search(map, from, &[to], usize::MAX)
+}
+
+/// Chemin vers la plus proche (en énergie) des cibles accessibles sans sortir de `MAX_SEARCH_RADIUS` ;
+/// il se termine sur cette cible
+pub fn find_path_to_any(map: &Grid<Cell>, from: Position, targets: &[Position]) -> Option<Vec<Position>> {
+    search(map, from, targets, MAX_SEARCH_RADIUS)
+}
+
+// Tampons de Dijkstra gardés d'un appel à l'autre, un jeu par thread de décision ;
+// `visited` porte le numéro de la recherche qui a écrit la case, ce qui évite de tout remettre à zéro
+#[derive(Default)]
+struct Scratch {
+    search: u32,
+    visited: Vec<u32>,
+    best_cost: Vec<u32>,
+    came_from: Vec<Position>,
+    queue: BinaryHeap<Reverse<(u32, usize, usize)>>,
+}
+
+impl Scratch {
+    fn reset(&mut self, cells: usize) {
+        if self.visited.len() != cells || self.search == u32::MAX {
+            self.visited = vec![0; cells];
+            self.best_cost = vec![u32::MAX; cells];
+            self.came_from = vec![Position::new(0, 0); cells];
+            self.search = 0;
+        }
+        self.search += 1;
+        self.queue.clear();
+    }
+
+    fn cost(&self, index: usize) -> u32 {
+        if self.visited[index] == self.search { self.best_cost[index] } else { u32::MAX }
+    }
+
+    fn record(&mut self, index: usize, cost: u32, previous: Position) {
+        self.visited[index] = self.search;
+        self.best_cost[index] = cost;
+        self.came_from[index] = previous;
+    }
+}
+
+thread_local! {
+    static SCRATCH: RefCell<Scratch> = RefCell::new(Scratch::default());
+}
+
+fn search(map: &Grid<Cell>, from: Position, targets: &[Position], radius: usize) -> Option<Vec<Position>> {
+    let in_window = |pos: Position| map.chebyshev_distance(from, pos) <= radius;
+    let targets: HashSet<Position> = targets
+        .iter()
+        .copied()
+        .filter(|target| map.get(*target).is_some_and(|cell| cell.is_passable()) && in_window(*target))
+        .collect();
+    if !map.contains(from) || targets.is_empty() {
+        return None;
+    }
+    if targets.contains(&from) {
+        return Some(Vec::new());
+    }
+
+    SCRATCH.with(|scratch| {
+        let scratch = &mut *scratch.borrow_mut();
+        let index = |pos: Position| pos.y * map.width() + pos.x;
+        scratch.reset(map.width() * map.height());
+
+        // Dijkstra : le coût d'un pas dépend du dénivelé
+        scratch.record(index(from), 0, from);
+        // (coût, y, x) : l'ordre des coordonnées départage les égalités de façon déterministe
+        scratch.queue.push(Reverse((0, from.y, from.x)));
+
+        while let Some(Reverse((cost, y, x))) = scratch.queue.pop() {
+            let current = Position::new(x, y);
+            if targets.contains(&current) {
+                return Some(rebuild_path(scratch, index, from, current));
+            }
+            if cost > scratch.cost(index(current)) {
+                continue;
+            }
+
+            for next in map.neighbors8(current) {
+                if !map[next].is_passable() || !in_window(next) {
+                    continue;
+                }
+                let next_cost = cost + move_energy_cost(&map[current], &map[next]);
+                if next_cost < scratch.cost(index(next)) {
+                    scratch.record(index(next), next_cost, current);
+                    scratch.queue.push(Reverse((next_cost, next.y, next.x)));
+                }
+            }
+        }
+
+        None
+    })
+}
+
+/// Première case du plus court chemin vers `to`
+pub fn next_step(map: &Grid<Cell>, from: Position, to: Position) -> Option<Position> {
+    find_path(map, from, to)?.first().copied()
+}
+
+/// Première case du chemin vers la cible accessible la plus proche
+pub fn next_step_to_any(map: &Grid<Cell>, from: Position, targets: &[Position]) -> Option<Position> {
+    find_path_to_any(map, from, targets)?.first().copied()
+}
+
+fn rebuild_path(scratch: &Scratch, index: impl Fn(Position) -> usize, from: Position, to: Position) -> Vec<Position> {
+    let mut path = vec![to];
+    let mut current = to;
+    loop {
+        let previous = scratch.came_from[index(current)];
+        if previous == from {
+            break;
+        }
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainType;
+    use crate::{Direction, Topology};
+
+    // Carte 10x5 de plaine avec un mur vertical en x=5 (y de 0 à 3)
+    fn walled_map(topology: Topology) -> Grid<Cell> {
+        Grid::from_fn(10, 5, |pos| {
+            let terrain = if pos.x == 5 && pos.y < 4 { TerrainType::Obstacle } else { TerrainType::Plaine };
+            Cell::new(terrain)
+        })
+        .with_topology(topology)
+    }
+
+    fn assert_valid(map: &Grid<Cell>, from: Position, path: &[Position]) {
+        let mut current = from;
+        for &step in path {
+            assert_eq!(map.chebyshev_distance(current, step), 1, "saut de {:?} à {:?}", current, step);
+            assert!(map[step].is_passable());
+            current = step;
+        }
+    }
+
+    #[test]
+    fn bounded_map_goes_around_the_wall() {
+        let map = walled_map(Topology::Bornee);
+        let (from, to) = (Position::new(0, 0), Position::new(9, 0));
+        let path = find_path(&map, from, to).unwrap();
+
+        assert_valid(&map, from, &path);
+        assert_eq!(path.last(), Some(&to));
+        assert!(path.iter().any(|p| p.y == 4), "le chemin doit contourner le mur par le bas");
+    }
+
+    #[test]
+    fn cylinder_wraps_across_the_east_west_edge() {
+        let map = walled_map(Topology::Cylindre);
+        let (from, to) = (Position::new(0, 0), Position::new(9, 0));
+        let path = find_path(&map, from, to).unwrap();
+
+        assert_eq!(path, vec![to]);
+        // Pas de bouclage nord-sud sur un cylindre
+        assert_eq!(map.neighbor(Position::new(3, 0), Direction::Nord), None);
+    }
+
+    #[test]
+    fn torus_wraps_on_both_axes() {
+        let map = walled_map(Topology::Tore);
+        let (from, to) = (Position::new(0, 0), Position::new(9, 4));
+        let path = find_path(&map, from, to).unwrap();
+
+        assert_valid(&map, from, &path);
+        assert_eq!(path, vec![to]);
+        assert_eq!(map.chebyshev_distance(from, to), 1);
+    }
+
+    #[test]
+    fn unreachable_target_has_no_path() {
+        for topology in [Topology::Bornee, Topology::Cylindre, Topology::Tore] {
+            let mut map = walled_map(topology);
+            let to = Position::new(7, 2);
+            for neighbor in map.neighbors8(to).collect::<Vec<_>>() {
+                map[neighbor].terrain = TerrainType::Obstacle;
+            }
+
+            assert_eq!(find_path(&map, Position::new(0, 0), to), None, "{:?}", topology);
+        }
+    }
+
+    #[test]
+    fn nearest_reachable_target_wins() {
+        let map = walled_map(Topology::Bornee);
+        let from = Position::new(4, 0);
+        // (6,0) est à deux cases à vol d'oiseau mais derrière le mur ; (0,0) est plus court à pied
+        let targets = [Position::new(6, 0), Position::new(0, 0)];
+        let path = find_path_to_any(&map, from, &targets).unwrap();
+
+        assert_valid(&map, from, &path);
+        assert_eq!(path.last(), Some(&Position::new(0, 0)));
+        assert_eq!(find_path_to_any(&map, from, &[]), None);
+    }
+
+    #[test]
+    fn decisions_only_search_around_the_robot() {
+        let strip = Grid::new(400, 1, Cell::new(TerrainType::Plaine));
+        let (from, far) = (Position::new(0, 0), Position::new(399, 0));
+        let near = Position::new(MAX_SEARCH_RADIUS, 0);
+
+        assert_eq!(find_path(&strip, from, far).map(|path| path.len()), Some(399));
+        assert_eq!(find_path_to_any(&strip, from, &[far]), None);
+        assert_eq!(find_path_to_any(&strip, from, &[far, near]).and_then(|path| path.last().copied()), Some(near));
+
+        // Les tampons réutilisés s'adaptent à une autre carte
+        let map = walled_map(Topology::Bornee);
+        let path = find_path_to_any(&map, Position::new(0, 0), &[Position::new(9, 0)]).unwrap();
+        assert_valid(&map, Position::new(0, 0), &path);
+    }
+}
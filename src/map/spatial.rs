@@ -0,0 +1,324 @@
+// src/map/spatial.rs - Index spatial en seaux réguliers : robots et cases porteuses de ressources
+use crate::{Cell, Position};
+use crate::{Grid, Topology};
+
+/// Côté d'un seau, en cases
+pub const BUCKET_SIZE: usize = 8;
+
+/// Éléments (identifiant, position) rangés par seau de BUCKET_SIZE x BUCKET_SIZE cases.
+/// Les distances suivent la topologie de la carte, comme `Grid::chebyshev_distance`.
+#[derive(Debug, Clone)]
+pub struct SpatialIndex {
+    width: usize,
+    height: usize,
+    topology: Topology,
+    columns: usize,
+    buckets: Vec<Vec<(usize, Position)>>,
+    len: usize,
+}
+
+impl SpatialIndex {
+    /// Index vide aux dimensions et à la topologie d'une grille
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        let columns = grid.width().div_ceil(BUCKET_SIZE).max(1);
+        let rows = grid.height().div_ceil(BUCKET_SIZE).max(1);
+        SpatialIndex {
+            width: grid.width(),
+            height: grid.height(),
+            topology: grid.topology(),
+            columns,
+            buckets: vec![Vec::new(); columns * rows],
+            len: 0,
+        }
+    }
+
+    /// Robots indexés par leur rang dans la liste
+    pub fn of_positions(map: &Grid<Cell>, positions: impl IntoIterator<Item = Position>) -> Self {
+        let mut index = Self::for_grid(map);
+        for (id, pos) in positions.into_iter().enumerate() {
+            index.insert(id, pos);
+        }
+        index
+    }
+
+    /// Cases porteuses de ressources, identifiées par leur rang ligne par ligne
+    pub fn of_resources(map: &Grid<Cell>) -> Self {
+        let mut index = Self::for_grid(map);
+        for (pos, _) in map.enumerate().filter(|(_, cell)| !cell.resources.is_empty()) {
+            index.insert(index.cell_id(pos), pos);
+        }
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Identifiant d'une case dans un index de cases
+    pub fn cell_id(&self, pos: Position) -> usize {
+        pos.y * self.width + pos.x
+    }
+
+    pub fn insert(&mut self, id: usize, pos: Position) {
+        let bucket = self.bucket_of(pos);
+        self.buckets[bucket].push((id, pos));
+        self.len += 1;
+    }
+
+    /// Retire un élément ; faux s'il n'était pas à cette position
+    pub fn remove(&mut self, id: usize, pos: Position) -> bool {
+        let bucket = self.bucket_of(pos);
+        let bucket = &mut self.buckets[bucket];
+        let Some(slot) = bucket.iter().position(|entry| *entry == (id, pos)) else {
+            return false;
+        };
+        bucket.swap_remove(slot);
+        self.len -= 1;
+        true
+    }
+
+    pub fn relocate(&mut self, id: usize, from: Position, to: Position) {
+        if from != to && self.remove(id, from) {
+            self.insert(id, to);
+        }
+    }
+
+    /// Tient à jour un index de cases : présente si `present`, absente sinon
+    pub fn set_cell(&mut self, pos: Position, present: bool) {
+        let id = self.cell_id(pos);
+        let indexed = self.buckets[self.bucket_of(pos)].contains(&(id, pos));
+        if present && !indexed {
+            self.insert(id, pos);
+        } else if !present && indexed {
+            self.remove(id, pos);
+        }
+    }
+
+    /// Identifiants des éléments posés sur une case, sans allocation
+    pub fn at(&self, pos: Position) -> impl Iterator<Item = usize> + '_ {
+        let bucket = if pos.x < self.width && pos.y < self.height { &self.buckets[self.bucket_of(pos)][..] } else { &[] };
+        bucket.iter().filter(move |(_, found)| *found == pos).map(|(id, _)| *id)
+    }
+
+    /// Éléments à au plus `radius` pas (8 directions) du centre, triés par identifiant
+    pub fn within_radius(&self, center: Position, radius: usize) -> Vec<(usize, Position)> {
+        let columns = axis_buckets(center.x, radius, self.width, self.topology.wraps_x());
+        let rows = axis_buckets(center.y, radius, self.height, self.topology.wraps_y());
+
+        let mut found: Vec<(usize, Position)> = rows
+            .iter()
+            .flat_map(|row| columns.iter().map(move |column| row * self.columns + column))
+            .flat_map(|bucket| self.buckets[bucket].iter().copied())
+            .filter(|(_, pos)| self.distance(center, *pos) <= radius)
+            .collect();
+        found.sort_unstable_by_key(|(id, _)| *id);
+        found
+    }
+
+    /// Éléments du rectangle [origin, origin + taille[ (tronqué aux bords), triés par ligne, colonne puis identifiant
+    pub fn in_rect(&self, origin: Position, width: usize, height: usize) -> Vec<(usize, Position)> {
+        let x_end = origin.x.saturating_add(width).min(self.width);
+        let y_end = origin.y.saturating_add(height).min(self.height);
+        if origin.x >= x_end || origin.y >= y_end {
+            return Vec::new();
+        }
+
+        let mut found: Vec<(usize, Position)> = (origin.y / BUCKET_SIZE..=(y_end - 1) / BUCKET_SIZE)
+            .flat_map(|row| (origin.x / BUCKET_SIZE..=(x_end - 1) / BUCKET_SIZE).map(move |column| (row, column)))
+            .flat_map(|(row, column)| self.buckets[row * self.columns + column].iter().copied())
+            .filter(|(_, pos)| (origin.x..x_end).contains(&pos.x) && (origin.y..y_end).contains(&pos.y))
+            .collect();
+        found.sort_unstable_by_key(|(id, pos)| (pos.y, pos.x, *id));
+        found
+    }
+
+    /// Les `k` éléments retenus par `filter` les plus proches du centre, à au plus `max_radius` pas.
+    /// Triés par distance puis identifiant ; le rayon de recherche double tant qu'il en manque.
+    pub fn nearest_k(
+        &self,
+        center: Position,
+        k: usize,
+        max_radius: usize,
+        filter: impl Fn(usize, Position) -> bool,
+    ) -> Vec<(usize, Position)> {
+        let map_span = self.width.max(self.height);
+        let mut radius = BUCKET_SIZE.min(max_radius);
+        loop {
+            let mut found: Vec<(usize, Position)> = self
+                .within_radius(center, radius)
+                .into_iter()
+                .filter(|(id, pos)| filter(*id, *pos))
+                .collect();
+
+            // Tous les éléments à moins de `radius` sont vus : les k premiers sont les bons
+            if found.len() >= k || radius >= max_radius || radius >= map_span {
+                found.sort_by_key(|(id, pos)| (self.distance(center, *pos), *id));
+                found.truncate(k);
+                return found;
+            }
+            radius = radius.saturating_mul(2).min(max_radius);
+        }
+    }
+
+    /// Distance en 8 directions, bords bouclés compris
+    pub fn distance(&self, from: Position, to: Position) -> usize {
+        let dx = from.x.abs_diff(to.x);
+        let dy = from.y.abs_diff(to.y);
+        let dx = if self.topology.wraps_x() { dx.min(self.width - dx) } else { dx };
+        let dy = if self.topology.wraps_y() { dy.min(self.height - dy) } else { dy };
+        dx.max(dy)
+    }
+
+    fn bucket_of(&self, pos: Position) -> usize {
+        (pos.y / BUCKET_SIZE) * self.columns + pos.x / BUCKET_SIZE
+    }
+}
+
+// Seaux d'un axe couverts par [center - radius, center + radius], découpé aux bords ou bouclé
+fn axis_buckets(center: usize, radius: usize, len: usize, wraps: bool) -> Vec<usize> {
+    if len == 0 {
+        return Vec::new();
+    }
+    let last = len - 1;
+    let spans: Vec<(usize, usize)> = if wraps && radius.saturating_mul(2) + 1 >= len {
+        vec![(0, last)]
+    } else if wraps && radius > center {
+        vec![(0, center + radius), (len + center - radius, last)]
+    } else if wraps && center + radius > last {
+        vec![(center - radius, last), (0, center + radius - len)]
+    } else {
+        vec![(center.saturating_sub(radius), center.saturating_add(radius).min(last))]
+    };
+
+    let mut buckets: Vec<usize> = spans
+        .into_iter()
+        .flat_map(|(start, end)| start / BUCKET_SIZE..=end / BUCKET_SIZE)
+        .collect();
+    buckets.sort_unstable();
+    buckets.dedup();
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainType;
+    use std::time::Instant;
+
+    // Positions pseudo-aléatoires reproductibles
+    fn scattered(width: usize, height: usize, count: usize) -> Vec<Position> {
+        let mut state: u64 = 0x2545F4914F6CDD1D;
+        (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                Position::new(state as usize % width, (state >> 32) as usize % height)
+            })
+            .collect()
+    }
+
+    fn plain(width: usize, height: usize, topology: Topology) -> Grid<Cell> {
+        Grid::new(width, height, Cell::new(TerrainType::Plaine)).with_topology(topology)
+    }
+
+    // Référence : parcours de toute la liste
+    fn linear_within(map: &Grid<Cell>, positions: &[Position], center: Position, radius: usize) -> Vec<(usize, Position)> {
+        positions
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, pos)| map.chebyshev_distance(center, *pos) <= radius)
+            .collect()
+    }
+
+    #[test]
+    fn radius_queries_match_a_linear_scan_on_every_topology() {
+        for topology in [Topology::Bornee, Topology::Cylindre, Topology::Tore] {
+            let map = plain(37, 21, topology);
+            let positions = scattered(37, 21, 200);
+            let index = SpatialIndex::of_positions(&map, positions.iter().copied());
+
+            for center in [Position::new(0, 0), Position::new(36, 20), Position::new(18, 10), Position::new(2, 19)] {
+                for radius in [0, 1, 5, 12, 40] {
+                    assert_eq!(
+                        index.within_radius(center, radius),
+                        linear_within(&map, &positions, center, radius),
+                        "{:?} {:?} r={}",
+                        topology,
+                        center,
+                        radius
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_k_and_rectangles_follow_moves() {
+        let map = plain(40, 40, Topology::Bornee);
+        let mut index = SpatialIndex::of_positions(&map, [Position::new(5, 5), Position::new(30, 30), Position::new(8, 5)]);
+        index.relocate(1, Position::new(30, 30), Position::new(6, 6));
+
+        let nearest = index.nearest_k(Position::new(5, 5), 2, usize::MAX, |id, _| id != 0);
+        assert_eq!(nearest, vec![(1, Position::new(6, 6)), (2, Position::new(8, 5))]);
+        assert_eq!(index.nearest_k(Position::new(5, 5), 1, 2, |id, _| id == 2), vec![]);
+        assert_eq!(index.in_rect(Position::new(0, 5), 40, 1), vec![(0, Position::new(5, 5)), (2, Position::new(8, 5))]);
+        assert_eq!(index.len(), 3);
+
+        index.insert(3, Position::new(6, 6));
+        assert_eq!(index.at(Position::new(6, 6)).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(index.at(Position::new(30, 30)).count(), 0);
+        assert_eq!(index.at(Position::new(40, 0)).count(), 0);
+    }
+
+    #[test]
+    #[ignore = "banc d'essai : cargo test --release -- --ignored --nocapture"]
+    fn bench_index_against_linear_scan() {
+        const QUERIES: usize = 2000;
+        let map = plain(500, 500, Topology::Bornee);
+        let positions = scattered(500, 500, 10_000);
+        let index = SpatialIndex::of_positions(&map, positions.iter().copied());
+        let centers = scattered(500, 500, QUERIES);
+
+        let start = Instant::now();
+        let linear: Vec<usize> = centers.iter().map(|c| linear_within(&map, &positions, *c, 10).len()).collect();
+        let linear_time = start.elapsed();
+
+        let start = Instant::now();
+        let indexed: Vec<usize> = centers.iter().map(|c| index.within_radius(*c, 10).len()).collect();
+        let index_time = start.elapsed();
+        assert_eq!(linear, indexed);
+        println!("rayon 10      : balayage {:?}, index {:?}", linear_time, index_time);
+
+        let start = Instant::now();
+        let linear: Vec<Option<usize>> = centers
+            .iter()
+            .map(|c| (0..positions.len()).min_by_key(|id| (map.chebyshev_distance(*c, positions[*id]), *id)))
+            .collect();
+        let linear_time = start.elapsed();
+
+        let start = Instant::now();
+        let indexed: Vec<Option<usize>> = centers
+            .iter()
+            .map(|c| index.nearest_k(*c, 1, usize::MAX, |_, _| true).first().map(|(id, _)| *id))
+            .collect();
+        let index_time = start.elapsed();
+        assert_eq!(linear, indexed);
+        println!("plus proche   : balayage {:?}, index {:?}", linear_time, index_time);
+
+        let start = Instant::now();
+        let linear: Vec<usize> = (0..500).map(|y| positions.iter().filter(|pos| pos.y == y && pos.x < 80).count()).collect();
+        let linear_time = start.elapsed();
+
+        let start = Instant::now();
+        let indexed: Vec<usize> = (0..500).map(|y| index.in_rect(Position::new(0, y), 80, 1).len()).collect();
+        let index_time = start.elapsed();
+        assert_eq!(linear, indexed);
+        println!("ligne d'écran : balayage {:?}, index {:?}", linear_time, index_time);
+    }
+}
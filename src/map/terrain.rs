@@ -1,20 +1,30 @@
 // src/map/terrain.rs - Utilitaires pour terrains
 
 // Fonctions utilitaires pour les terrains
-pub fn terrain_symbol(terrain: &final_project::TerrainType) -> char {
+pub fn terrain_symbol(terrain: &crate::TerrainType) -> char {
     match terrain {
-        final_project::TerrainType::Plaine => '.',
-        final_project::TerrainType::Montagne => '^',
-        final_project::TerrainType::Cratere => 'O',
-        final_project::TerrainType::Obstacle => '#',
+        crate::TerrainType::Plaine => '.',
+        crate::TerrainType::Montagne => '^',
+        crate::TerrainType::Cratere => 'O',
+        crate::TerrainType::Obstacle => '#',
     }
 }
 
-pub fn movement_cost(terrain: &final_project::TerrainType) -> u32 {
+pub fn movement_cost(terrain: &crate::TerrainType) -> u32 {
     match terrain {
-        final_project::TerrainType::Plaine => 1,
-        final_project::TerrainType::Cratere => 2,
-        final_project::TerrainType::Montagne => 3,
-        final_project::TerrainType::Obstacle => u32::MAX,
+        crate::TerrainType::Plaine => 1,
+        crate::TerrainType::Cratere => 2,
+        crate::TerrainType::Montagne => 3,
+        crate::TerrainType::Obstacle => u32::MAX,
     }
-}
\ No newline at end of file
+}
+
+pub fn terrain_from_symbol(symbol: char) -> Option<crate::TerrainType> {
+    match symbol {
+        '.' => Some(crate::TerrainType::Plaine),
+        '^' => Some(crate::TerrainType::Montagne),
+        'O' => Some(crate::TerrainType::Cratere),
+        '#' => Some(crate::TerrainType::Obstacle),
+        _ => None,
+    }
+}
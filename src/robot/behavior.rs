@@ -1,84 +1,152 @@
 // src/robot/behavior.rs - Comportements des robots améliorés
-use crate::Position;
+use crate::{Cell, Position, ResourceType};
+use crate::map::{next_step_to_any, SpatialIndex};
+use crate::robot::{RelayAssignment, Robot, REPAIR_MINERAL_COST};
+use crate::simulation::{
+    team_depot_positions, Depot, DepotKind, Team, MAX_OUTPOSTS, OUTPOST_MINERAL_COST, OUTPOST_MIN_SPACING,
+};
+use crate::{Direction, Grid, RobotModule};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RobotAction {
     Move(Position),
     Collect,
     Analyze,
     Communicate(Vec<usize>), // IDs des robots à contacter
     Wait,
+    Blocked(Direction), // Déplacement voulu mais au-delà d'un bord de la carte
+    Repair(usize),      // ID du robot voisin à réparer avec des mineraux
+    Drop(ResourceType), // Dépose tout le chargement d'une ressource sur la case
+    Transfer { to: usize, resource: ResourceType, amount: u32 }, // Cède du chargement à un voisin
+    ShareEnergy { to: usize, amount: u32 },                       // Cède de l'énergie à un voisin
+    BuildOutpost, // Construit un avant-poste sur la case avec les mineraux transportés
+}
+
+// Ordre de balayage des explorateurs : cardinaux puis diagonaux
+const EXPLORATION_DIRECTIONS: [Direction; 8] = [
+    Direction::Est, Direction::Sud, Direction::Ouest, Direction::Nord,
+    Direction::SudEst, Direction::SudOuest, Direction::NordOuest, Direction::NordEst,
+];
+
+// Directions de recherche des collecteurs
+const COLLECTION_DIRECTIONS: [Direction; 4] = [
+    Direction::Est, Direction::Sud, Direction::Ouest, Direction::Nord,
+];
+
+// En dessous de ce niveau d'énergie, un collecteur chargé s'allège pour rentrer
+const LOW_ENERGY_DROP: u32 = 8;
+
+// Partage d'énergie : un robot bien chargé dépanne un voisin presque à sec
+const SHARE_ENERGY_DONOR: u32 = 60;
+const SHARE_ENERGY_NEEDY: u32 = 15;
+const SHARE_ENERGY_AMOUNT: u32 = 20;
+
+// Un relais attend sur place en dessous de ce niveau d'énergie
+const RELAY_LOW_ENERGY: u32 = 5;
+
+// Portée à laquelle un collecteur repère une ressource connue de son équipe
+const COLLECTOR_SENSE_RADIUS: usize = 6;
+// Un explorateur sur une découverte la signale à son équipe un tour sur ce nombre (selon son énergie)
+const DISCOVERY_REPORT_PERIOD: u32 = 4;
+
+// Zone balayée par les scientifiques
+const SCIENCE_SWEEP_WIDTH: usize = 20;
+const SCIENCE_SWEEP_HEIGHT: usize = 15;
+
+/// Ce que le robot sait au moment de choisir son action
+pub struct DecisionContext<'a> {
+    pub robot: &'a Robot,
+    /// Équipe du robot : ses tactiques et sa connaissance de la carte
+    pub team: &'a Team,
+    pub robots: &'a [Robot],
+    pub depots: &'a [Depot],
+    pub map: &'a Grid<Cell>,
+    /// Index spatiaux tenus par le moteur : robots (par rang) et cases porteuses de ressources
+    pub robot_positions: &'a SpatialIndex,
+    pub resource_cells: &'a SpatialIndex,
 }
 
 pub struct BehaviorEngine;
 
 impl BehaviorEngine {
-    pub fn decide_action(
-        robot_behavior: crate::RobotBehavior,
-        current_position: Position,
-        energy: u32,
-        inventory_full: bool,
-    ) -> RobotAction {
-        match robot_behavior {
+    pub fn decide_action(context: &DecisionContext) -> RobotAction {
+        let robot = context.robot;
+        let current_position = robot.position;
+        let energy = robot.energy;
+        let map = context.map;
+
+        // Plus de déplacement possible : attendre une réparation sur place
+        if robot.has_module(RobotModule::Deplacement) && robot.condition(RobotModule::Deplacement).is_broken() {
+            return RobotAction::Wait;
+        }
+
+        match robot.behavior {
             crate::RobotBehavior::Explorateur => {
                 if energy < 10 {
                     RobotAction::Wait // Récupérer de l'énergie
+                } else if let Some(listeners) = Self::discovery_listeners(context) {
+                    // Ressource sous les roues : prévenir les coéquipiers à portée radio
+                    RobotAction::Communicate(listeners)
                 } else {
                     // Mouvement intelligent : exploration en spirale
-                    let directions = [
-                        (1, 0), (0, 1), (-1, 0), (0, -1), // Cardinal
-                        (1, 1), (-1, 1), (-1, -1), (1, -1) // Diagonal
-                    ];
-
-                    let choice = (current_position.x + current_position.y + energy as usize) % directions.len();
-                    let (dx, dy) = directions[choice];
-
-                    let new_x = (current_position.x as i32 + dx).max(0) as usize;
-                    let new_y = (current_position.y as i32 + dy).max(0) as usize;
-
-                    RobotAction::Move(Position::new(new_x, new_y))
+                    let choice = (current_position.x + current_position.y + energy as usize) % EXPLORATION_DIRECTIONS.len();
+                    Self::move_toward(current_position, EXPLORATION_DIRECTIONS[choice], map)
                 }
             },
             crate::RobotBehavior::Collecteur => {
-                if inventory_full {
-                    // Retourner vers le point de départ pour décharger
-                    if current_position.x > 1 || current_position.y > 1 {
-                        let new_x = if current_position.x > 1 { current_position.x - 1 } else { current_position.x };
-                        let new_y = if current_position.y > 1 { current_position.y - 1 } else { current_position.y };
-                        RobotAction::Move(Position::new(new_x, new_y))
-                    } else {
-                        RobotAction::Wait
-                    }
+                let can_haul = robot.can_collect(ResourceType::Energie) || robot.can_collect(ResourceType::Mineraux);
+
+                if let Some(target) = Self::repair_target(context) {
+                    // Réparer un voisin en panne avec les mineraux transportés
+                    RobotAction::Repair(target)
+                } else if let Some(target) = Self::energy_share_target(context) {
+                    RobotAction::ShareEnergy { to: target, amount: SHARE_ENERGY_AMOUNT }
+                } else if let Some(relay) = &robot.relay {
+                    // Maillon d'une chaîne de relais : récolter ou faire suivre le chargement
+                    Self::relay_action(context, relay)
+                } else if let Some(resource) = Self::cargo_to_drop(context) {
+                    RobotAction::Drop(resource)
+                } else if !can_haul {
+                    // Modules de collecte hors d'usage : direction l'atelier d'une station
+                    Self::return_to_depot(context, Some(DepotKind::Station))
+                } else if robot.is_inventory_full() && Self::can_build_outpost(context) {
+                    // Trop loin de tout dépôt : le chargement sert à en bâtir un sur place
+                    RobotAction::BuildOutpost
+                } else if robot.is_inventory_full() {
+                    // Retourner vers le dépôt le plus proche pour décharger
+                    Self::return_to_depot(context, None)
                 } else if energy > 5 {
-                    // Alterner entre collecte et mouvement
-                    if energy % 3 == 0 {
+                    // Collecter ce que les modules encore valides permettent de ramasser
+                    // (sans CollecteMineraux, le collecteur ne transporte plus que de l'énergie)
+                    if energy.is_multiple_of(3) && Self::collectible_here(context) {
                         RobotAction::Collect
+                    } else if let Some(target) = Self::sensed_resource(context) {
+                        // Ressource connue à proximité : s'y rendre
+                        Self::move_to(context, target)
                     } else {
                         // Mouvement de recherche de ressources
-                        let directions = [(1, 0), (0, 1), (-1, 0), (0, -1)];
-                        let choice = (energy as usize / 3) % directions.len();
-                        let (dx, dy) = directions[choice];
-
-                        let new_x = (current_position.x as i32 + dx).max(0) as usize;
-                        let new_y = (current_position.y as i32 + dy).max(0) as usize;
-
-                        RobotAction::Move(Position::new(new_x, new_y))
+                        let choice = (energy as usize / 3) % COLLECTION_DIRECTIONS.len();
+                        Self::move_toward(current_position, COLLECTION_DIRECTIONS[choice], map)
                     }
                 } else {
                     RobotAction::Wait
                 }
             },
             crate::RobotBehavior::Scientifique => {
-                if energy > 8 {
+                if robot.has_module(RobotModule::AnalyseChimique)
+                    && robot.condition(RobotModule::AnalyseChimique).is_broken()
+                {
+                    // Analyseur hors d'usage : retour à une station pour réparation
+                    Self::return_to_depot(context, Some(DepotKind::Station))
+                } else if energy > 8 {
                     // Alterner entre analyse et mouvement vers zones d'intérêt
-                    if energy % 2 == 0 {
+                    if energy.is_multiple_of(2) {
                         RobotAction::Analyze
                     } else {
-                        // Mouvement méthodique pour l'analyse
-                        let new_x = if current_position.x < 20 { current_position.x + 1 } else { 1 };
-                        let new_y = if new_x == 1 && current_position.y < 15 { current_position.y + 1 } else { current_position.y };
-
-                        RobotAction::Move(Position::new(new_x, new_y))
+                        // Balayage méthodique en serpentin de la zone d'analyse
+                        let direction = Self::sweep_direction(current_position, map);
+                        Self::move_toward(current_position, direction, map)
                     }
                 } else {
                     RobotAction::Wait
@@ -86,4 +154,191 @@ impl BehaviorEngine {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Chemin vers le dépôt de l'équipe accessible le plus proche, éventuellement d'un seul type
+    fn return_to_depot(context: &DecisionContext, kind: Option<DepotKind>) -> RobotAction {
+        Self::move_to_any(context, &team_depot_positions(context.depots, context.robot.team, kind))
+    }
+
+    /// Robot de la même équipe, seul à pouvoir recevoir aide et chargement
+    fn teammate(context: &DecisionContext, other: &Robot) -> bool {
+        other.id != context.robot.id && other.team == context.robot.team && other.is_operational()
+    }
+
+    fn move_to(context: &DecisionContext, target: Position) -> RobotAction {
+        Self::move_to_any(context, &[target])
+    }
+
+    /// Un pas vers la cible la plus proche en contournant les obstacles, sinon en ligne droite
+    fn move_to_any(context: &DecisionContext, targets: &[Position]) -> RobotAction {
+        let (from, map) = (context.robot.position, context.map);
+        if let Some(step) = next_step_to_any(map, from, targets) {
+            return RobotAction::Move(step);
+        }
+
+        let nearest = targets.iter().min_by_key(|target| map.chebyshev_distance(from, **target));
+        match nearest.and_then(|target| map.direction_toward(from, *target)) {
+            Some(direction) => Self::move_toward(from, direction, map),
+            None => RobotAction::Wait,
+        }
+    }
+
+    /// Assez de mineraux à bord, de la place pour un avant-poste et aucun dépôt à proximité
+    fn can_build_outpost(context: &DecisionContext) -> bool {
+        let robot = context.robot;
+        let outposts = context
+            .depots
+            .iter()
+            .filter(|depot| depot.team == robot.team && depot.kind == DepotKind::Avantposte)
+            .count();
+
+        context.team.tactics.build_outposts
+            && robot.carried(ResourceType::Mineraux) >= OUTPOST_MINERAL_COST
+            && outposts < MAX_OUTPOSTS
+            && context
+                .depots
+                .iter()
+                .all(|depot| context.map.chebyshev_distance(robot.position, depot.position) >= OUTPOST_MIN_SPACING)
+    }
+
+    /// Case voisine la plus proche, connue de l'équipe, où le robot peut collecter quelque chose
+    fn sensed_resource(context: &DecisionContext) -> Option<Position> {
+        let (robot, map) = (context.robot, context.map);
+        context
+            .resource_cells
+            .nearest_k(robot.position, 1, COLLECTOR_SENSE_RADIUS, |_, pos| {
+                pos != robot.position
+                    && context.team.knows(pos)
+                    && map[pos].is_passable()
+                    && map[pos].resources.iter().any(|(resource, _)| robot.can_collect(resource))
+            })
+            .first()
+            .map(|(_, pos)| *pos)
+    }
+
+    /// Coéquipiers à portée radio à prévenir d'une découverte, s'il y en a
+    fn discovery_listeners(context: &DecisionContext) -> Option<Vec<usize>> {
+        let robot = context.robot;
+        if !robot.module_working(RobotModule::Communication)
+            || !robot.energy.is_multiple_of(DISCOVERY_REPORT_PERIOD)
+            || context.map[robot.position].resources.is_empty()
+        {
+            return None;
+        }
+
+        let listeners: Vec<usize> = Self::robots_within(context, robot.communication_range)
+            .filter(|other| Self::teammate(context, other) && other.module_working(RobotModule::Communication))
+            .map(|other| other.id)
+            .collect();
+        (!listeners.is_empty()).then_some(listeners)
+    }
+
+    /// Robots à au plus `radius` pas, par ordre de rang, d'après l'index spatial
+    fn robots_within<'a>(context: &'a DecisionContext, radius: usize) -> impl Iterator<Item = &'a Robot> {
+        context
+            .robot_positions
+            .within_radius(context.robot.position, radius)
+            .into_iter()
+            .map(|(index, _)| &context.robots[index])
+    }
+
+    fn collectible_here(context: &DecisionContext) -> bool {
+        context.map[context.robot.position]
+            .resources
+            .iter()
+            .any(|(resource, _)| context.robot.can_collect(resource))
+    }
+
+    /// Tête : récolte sur le gisement ; maillons : attendent au poste puis portent au suivant
+    fn relay_action(context: &DecisionContext, relay: &RelayAssignment) -> RobotAction {
+        let robot = context.robot;
+        if robot.energy <= RELAY_LOW_ENERGY {
+            return RobotAction::Wait;
+        }
+
+        let at_post = robot.position == relay.post;
+        let harvesting = relay.collects && at_post && !robot.is_inventory_full() && Self::collectible_here(context);
+        if harvesting {
+            return RobotAction::Collect;
+        }
+
+        if robot.inventory.is_empty() {
+            return if at_post { RobotAction::Wait } else { Self::move_to(context, relay.post) };
+        }
+
+        // Faire suivre : au robot suivant s'il est à côté, sinon rejoindre son poste
+        let Some((receiver_id, receiver_post)) = relay.receiver else {
+            return Self::return_to_depot(context, None);
+        };
+        let receiver_adjacent = Self::robots_within(context, 1).any(|other| other.id == receiver_id);
+        if receiver_adjacent && let Some(resource) = robot.heaviest_cargo() {
+            return RobotAction::Transfer { to: receiver_id, resource, amount: robot.carried(resource) };
+        }
+
+        if context.map.chebyshev_distance(robot.position, receiver_post) <= 1 {
+            RobotAction::Wait // Le suivant est parti livrer : l'attendre près de son poste
+        } else {
+            Self::move_to(context, receiver_post)
+        }
+    }
+
+    /// Voisin presque à sec, si l'on a de l'énergie à revendre
+    fn energy_share_target(context: &DecisionContext) -> Option<usize> {
+        let robot = context.robot;
+        if !context.team.tactics.share_energy || robot.energy < SHARE_ENERGY_DONOR {
+            return None;
+        }
+
+        Self::robots_within(context, 1)
+            .find(|other| Self::teammate(context, other) && other.energy < SHARE_ENERGY_NEEDY)
+            .map(|other| other.id)
+    }
+
+    /// Chargement trop lourd pour l'énergie restante : lâcher le plus pesant (hors dépôt)
+    fn cargo_to_drop(context: &DecisionContext) -> Option<ResourceType> {
+        let robot = context.robot;
+        let at_depot = context.depots.iter().any(|depot| depot.team == robot.team && depot.position == robot.position);
+        if at_depot || robot.energy >= LOW_ENERGY_DROP || robot.move_penalty() == 0 {
+            return None;
+        }
+        robot.heaviest_cargo()
+    }
+
+    /// Voisin immédiat avec un module abîmé, si l'on transporte de quoi le réparer
+    fn repair_target(context: &DecisionContext) -> Option<usize> {
+        let robot = context.robot;
+        if !context.team.tactics.field_repairs || robot.carried(ResourceType::Mineraux) < REPAIR_MINERAL_COST {
+            return None;
+        }
+
+        Self::robots_within(context, 1)
+            .find(|other| Self::teammate(context, other) && !other.broken_modules().is_empty())
+            .map(|other| other.id)
+    }
+
+    /// Déplacement d'une case, ou `Blocked` si la case visée est au-delà d'un bord
+    fn move_toward(current_position: Position, direction: Direction, map: &Grid<Cell>) -> RobotAction {
+        match map.neighbor(current_position, direction) {
+            Some(target) => RobotAction::Move(target),
+            None => RobotAction::Blocked(direction),
+        }
+    }
+
+    // Lignes paires vers l'est, impaires vers l'ouest, puis une ligne plus bas
+    fn sweep_direction(current_position: Position, map: &Grid<Cell>) -> Direction {
+        let last_x = SCIENCE_SWEEP_WIDTH.min(map.width().saturating_sub(1));
+        let last_y = SCIENCE_SWEEP_HEIGHT.min(map.height().saturating_sub(1));
+
+        let heading = if current_position.y.is_multiple_of(2) { Direction::Est } else { Direction::Ouest };
+        let row_done = match heading {
+            Direction::Est => current_position.x >= last_x,
+            _ => current_position.x <= 1,
+        };
+
+        match (row_done, current_position.y < last_y) {
+            (false, _) => heading,
+            (true, true) => Direction::Sud,
+            (true, false) => Direction::Nord,
+        }
+    }
+}
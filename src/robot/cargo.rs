@@ -0,0 +1,58 @@
+// src/robot/cargo.rs - Règles de chargement : capacité et masse de chaque ressource
+use crate::ResourceType;
+use serde::{Deserialize, Serialize};
+
+/// Masse transportée qui coûte 1 énergie de plus à chaque pas
+pub const MASS_PER_EXTRA_ENERGY: u32 = 25;
+
+/// Quantité maximale d'une ressource et masse d'une unité
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CargoSpec {
+    pub capacity: u32,
+    pub mass: u32,
+}
+
+/// Règles de chargement par ressource ; un échantillon de site est lourd et encombrant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CargoRules {
+    #[serde(default = "default_energie")]
+    pub energie: CargoSpec,
+    #[serde(default = "default_mineraux")]
+    pub mineraux: CargoSpec,
+    #[serde(default = "default_lieux_interet")]
+    pub lieux_interet: CargoSpec,
+}
+
+impl Default for CargoRules {
+    fn default() -> Self {
+        CargoRules {
+            energie: default_energie(),
+            mineraux: default_mineraux(),
+            lieux_interet: default_lieux_interet(),
+        }
+    }
+}
+
+fn default_energie() -> CargoSpec {
+    CargoSpec { capacity: 50, mass: 1 }
+}
+
+fn default_mineraux() -> CargoSpec {
+    CargoSpec { capacity: 30, mass: 2 }
+}
+
+fn default_lieux_interet() -> CargoSpec {
+    CargoSpec { capacity: 3, mass: 5 }
+}
+
+impl CargoRules {
+    pub fn spec(&self, resource_type: ResourceType) -> CargoSpec {
+        match resource_type {
+            ResourceType::Energie => self.energie,
+            ResourceType::Mineraux => self.mineraux,
+            ResourceType::LieuxInteret => self.lieux_interet,
+        }
+    }
+}
@@ -1,9 +1,14 @@
 // src/robot/mod.rs - Module de gestion des robots
+#[allow(clippy::module_inception)]
 pub mod robot;
 pub mod behavior;
+pub mod cargo;
 pub mod modules;
+pub mod wear;
 
 // Réexporter les types publics
 pub use robot::*;
 pub use behavior::*;
-pub use modules::*;
\ No newline at end of file
+pub use cargo::*;
+pub use modules::*;
+pub use wear::*;
\ No newline at end of file
@@ -1,18 +1,85 @@
 // src/robot/robot.rs - Structure principale des robots
 use crate::{Position, ResourceType};
-use std::collections::HashMap;
-use final_project::{RobotBehavior, RobotModule};
+use std::collections::BTreeMap;
+use crate::{RobotBehavior, RobotModule};
+use crate::robot::cargo::{CargoRules, MASS_PER_EXTRA_ENERGY};
+use crate::robot::wear::{ModuleCondition, MAX_MODULE_HEALTH};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+/// Énergie consommée par un déplacement sur terrain plat
+pub const MOVE_ENERGY_COST: u32 = 1;
+
+/// Module nécessaire pour collecter un type de ressource
+pub fn collection_module(resource_type: ResourceType) -> RobotModule {
+    match resource_type {
+        ResourceType::Energie => RobotModule::CollecteEnergie,
+        ResourceType::Mineraux => RobotModule::CollecteMineraux,
+        ResourceType::LieuxInteret => RobotModule::CollecteDonnees,
+    }
+}
+
+/// Modules qui rendent un rôle possible ; un seul en état de marche suffit
+pub fn role_modules(behavior: RobotBehavior) -> &'static [RobotModule] {
+    match behavior {
+        RobotBehavior::Explorateur => &[RobotModule::ImageHauteResolution, RobotModule::Deplacement],
+        RobotBehavior::Collecteur => &[RobotModule::CollecteEnergie, RobotModule::CollecteMineraux],
+        RobotBehavior::Scientifique => &[RobotModule::AnalyseChimique],
+    }
+}
+
+/// Dernière réaffectation de rôle décidée par le coordinateur
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleChange {
+    pub turn: usize,
+    pub from: RobotBehavior,
+    pub to: RobotBehavior,
+    pub reason: String,
+}
+
+/// Poste dans une chaîne de relais : où attendre et à qui passer le chargement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayAssignment {
+    pub chain: usize,
+    pub post: Position,
+    /// Robot suivant vers le dépôt et son poste ; `None` : livrer au dépôt le plus proche
+    pub receiver: Option<(usize, Position)>,
+    /// Tête de chaîne : récolte le gisement au lieu d'attendre un chargement
+    pub collects: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Robot {
     pub id: usize,
+    /// Essaim auquel appartient le robot
+    #[serde(default)]
+    pub team: usize,
     pub position: Position,
     pub behavior: RobotBehavior,
     pub modules: Vec<RobotModule>,
     pub energy: u32,
-    pub inventory: HashMap<ResourceType, u32>,
+    /// Chargement, trié par ressource pour un affichage stable
+    pub inventory: BTreeMap<ResourceType, u32>,
+    /// Masse totale transportable
     pub max_inventory: u32,
+    #[serde(default)]
+    pub cargo: CargoRules,
     pub communication_range: usize,
+    /// État des modules usés ; un module absent de la table est neuf
+    #[serde(default)]
+    pub module_condition: BTreeMap<RobotModule, ModuleCondition>,
+    /// Pannes subies : modules hors d'usage ou hors ligne
+    #[serde(default)]
+    pub failures: u32,
+    /// Modules remis en service
+    #[serde(default)]
+    pub repairs: u32,
+    /// Dernière réaffectation et nombre total de changements de rôle
+    #[serde(default)]
+    pub role_change: Option<RoleChange>,
+    #[serde(default)]
+    pub role_changes: u32,
+    #[serde(default)]
+    pub relay: Option<RelayAssignment>,
 }
 
 impl Robot {
@@ -21,13 +88,21 @@ impl Robot {
 
         Robot {
             id,
+            team: 0,
             position,
             behavior,
             modules,
             energy: 100,
-            inventory: HashMap::new(),
+            inventory: BTreeMap::new(),
             max_inventory: 50,
+            cargo: CargoRules::default(),
             communication_range: 5,
+            module_condition: BTreeMap::new(),
+            failures: 0,
+            repairs: 0,
+            role_change: None,
+            role_changes: 0,
+            relay: None,
         }
     }
 
@@ -55,12 +130,104 @@ impl Robot {
         self.modules.contains(&module)
     }
 
-    pub fn can_collect(&self, resource_type: ResourceType) -> bool {
-        match resource_type {
-            ResourceType::Energie => self.has_module(RobotModule::CollecteEnergie),
-            ResourceType::Mineraux => self.has_module(RobotModule::CollecteMineraux),
-            ResourceType::LieuxInteret => self.has_module(RobotModule::CollecteDonnees),
+    pub fn condition(&self, module: RobotModule) -> ModuleCondition {
+        self.module_condition.get(&module).copied().unwrap_or_default()
+    }
+
+    /// Module installé, en état de marche et pas en panne passagère
+    pub fn module_working(&self, module: RobotModule) -> bool {
+        self.has_module(module) && self.condition(module).is_working()
+    }
+
+    /// Modules installés mais hors d'usage
+    pub fn broken_modules(&self) -> Vec<RobotModule> {
+        self.modules.iter().copied().filter(|m| self.condition(*m).is_broken()).collect()
+    }
+
+    pub fn needs_repair(&self) -> bool {
+        self.modules.iter().any(|m| self.condition(*m).is_damaged())
+    }
+
+    /// Abîme un module ; vrai s'il vient de tomber hors d'usage
+    pub fn wear(&mut self, module: RobotModule, amount: u8) -> bool {
+        if !self.has_module(module) || amount == 0 {
+            return false;
+        }
+
+        let condition = self.module_condition.entry(module).or_default();
+        let was_broken = condition.is_broken();
+        condition.health = condition.health.saturating_sub(amount);
+        let broke = !was_broken && condition.is_broken();
+        if broke {
+            self.failures += 1;
         }
+        broke
+    }
+
+    /// Panne passagère : le module reste hors ligne quelques tours
+    pub fn malfunction(&mut self, module: RobotModule, turns: u8) {
+        if self.has_module(module) {
+            self.module_condition.entry(module).or_default().offline_turns = turns;
+            self.failures += 1;
+        }
+    }
+
+    /// Fait avancer les pannes passagères d'un tour
+    pub fn tick_malfunctions(&mut self) {
+        for condition in self.module_condition.values_mut() {
+            condition.offline_turns = condition.offline_turns.saturating_sub(1);
+        }
+    }
+
+    /// Répare le module le plus abîmé ; rend ce module s'il y en avait un et s'il est remis en service
+    pub fn repair_most_damaged(&mut self, amount: u8) -> Option<(RobotModule, bool)> {
+        let module = self
+            .modules
+            .iter()
+            .copied()
+            .filter(|m| self.condition(*m).is_damaged())
+            .min_by_key(|m| self.condition(*m).health)?;
+        let restored = self.repair(module, amount);
+        Some((module, restored))
+    }
+
+    /// Répare un module et annule sa panne passagère ; vrai s'il est remis en service
+    pub fn repair(&mut self, module: RobotModule, amount: u8) -> bool {
+        let Some(condition) = self.module_condition.get_mut(&module) else {
+            return false;
+        };
+
+        let was_working = condition.is_working();
+        condition.health = condition.health.saturating_add(amount).min(MAX_MODULE_HEALTH);
+        condition.offline_turns = 0;
+        let restored = !was_working && condition.is_working();
+        if !condition.is_damaged() {
+            self.module_condition.remove(&module);
+        }
+        if restored {
+            self.repairs += 1;
+        }
+        restored
+    }
+
+    pub fn can_take_role(&self, behavior: RobotBehavior) -> bool {
+        role_modules(behavior).iter().any(|m| self.module_working(*m))
+    }
+
+    /// Change de rôle en gardant trace de la raison
+    pub fn switch_role(&mut self, to: RobotBehavior, turn: usize, reason: String) {
+        self.role_change = Some(RoleChange { turn, from: self.behavior, to, reason });
+        self.behavior = to;
+        self.role_changes += 1;
+    }
+
+    /// Tour de la dernière réaffectation, s'il y en a eu une
+    pub fn last_role_change(&self) -> Option<usize> {
+        self.role_change.as_ref().map(|change| change.turn)
+    }
+
+    pub fn can_collect(&self, resource_type: ResourceType) -> bool {
+        self.module_working(collection_module(resource_type))
     }
 
     pub fn collect_resource(&mut self, resource_type: ResourceType, amount: u32) -> u32 {
@@ -68,29 +235,107 @@ impl Robot {
             return 0;
         }
 
-        let current_total: u32 = self.inventory.values().sum();
-        let available_space = self.max_inventory.saturating_sub(current_total);
-        let collected = amount.min(available_space);
+        self.load(resource_type, amount)
+    }
+
+    pub fn carried(&self, resource_type: ResourceType) -> u32 {
+        self.inventory.get(&resource_type).copied().unwrap_or(0)
+    }
+
+    /// Masse du chargement complet
+    pub fn cargo_mass(&self) -> u32 {
+        self.inventory
+            .iter()
+            .map(|(resource_type, amount)| amount * self.cargo.spec(*resource_type).mass)
+            .sum()
+    }
+
+    /// Quantité encore chargeable : limitée par la capacité de la ressource et la masse totale
+    pub fn room_for(&self, resource_type: ResourceType) -> u32 {
+        let spec = self.cargo.spec(resource_type);
+        let by_capacity = spec.capacity.saturating_sub(self.carried(resource_type));
+        let by_mass = self.max_inventory.saturating_sub(self.cargo_mass()) / spec.mass.max(1);
+        by_capacity.min(by_mass)
+    }
 
-        if collected > 0 {
-            *self.inventory.entry(resource_type).or_insert(0) += collected;
+    /// Charge ce qui rentre ; rend la quantité chargée
+    pub fn load(&mut self, resource_type: ResourceType, amount: u32) -> u32 {
+        let loaded = amount.min(self.room_for(resource_type));
+        if loaded > 0 {
+            *self.inventory.entry(resource_type).or_insert(0) += loaded;
         }
+        loaded
+    }
+
+    /// Retire jusqu'à `amount` unités ; rend la quantité retirée
+    pub fn unload(&mut self, resource_type: ResourceType, amount: u32) -> u32 {
+        let carried = self.carried(resource_type);
+        let taken = amount.min(carried);
+        if taken == carried {
+            self.inventory.remove(&resource_type);
+        } else {
+            self.inventory.insert(resource_type, carried - taken);
+        }
+        taken
+    }
+
+    /// Énergie supplémentaire par pas due à la masse transportée
+    pub fn move_penalty(&self) -> u32 {
+        self.cargo_mass() / MASS_PER_EXTRA_ENERGY
+    }
 
-        collected
+    /// Ressource la plus lourde à bord (masse totale), à lâcher en premier
+    pub fn heaviest_cargo(&self) -> Option<ResourceType> {
+        self.inventory
+            .iter()
+            .max_by_key(|(resource_type, amount)| *amount * self.cargo.spec(**resource_type).mass)
+            .map(|(resource_type, _)| *resource_type)
     }
 
-    pub fn move_to(&mut self, new_position: Position) -> bool {
-        if self.energy > 0 {
+    /// Se déplace si l'énergie couvre le coût du pas (selon le relief)
+    pub fn move_to(&mut self, new_position: Position, cost: u32) -> bool {
+        if self.energy > 0 && self.energy >= cost {
             self.position = new_position;
-            self.energy = self.energy.saturating_sub(1);
+            self.energy -= cost;
             true
         } else {
             false
         }
     }
 
+    /// Un robot à court d'énergie est hors service
+    pub fn is_operational(&self) -> bool {
+        self.energy > 0
+    }
+
+    /// Plus de place pour aucune des ressources que les modules permettent de collecter
     pub fn is_inventory_full(&self) -> bool {
-        let current_total: u32 = self.inventory.values().sum();
-        current_total >= self.max_inventory
+        ResourceType::ALL
+            .iter()
+            .filter(|resource_type| self.can_collect(**resource_type))
+            .all(|resource_type| self.room_for(*resource_type) == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inventory_is_full_once_collectable_resources_are_at_capacity() {
+        let mut robot = Robot::new(1, Position::new(0, 0), RobotBehavior::Collecteur);
+        robot.max_inventory = 200;
+        assert!(!robot.can_collect(ResourceType::LieuxInteret));
+
+        robot.collect_resource(ResourceType::Energie, 1000);
+        assert!(!robot.is_inventory_full());
+        robot.collect_resource(ResourceType::Mineraux, 1000);
+
+        // Il resterait de la masse pour des données, mais sans module pour les collecter
+        assert!(robot.room_for(ResourceType::LieuxInteret) > 0);
+        assert!(robot.is_inventory_full());
+
+        robot.unload(ResourceType::Mineraux, 1);
+        assert!(!robot.is_inventory_full());
     }
 }
\ No newline at end of file
@@ -0,0 +1,149 @@
+// src/robot/wear.rs - Usure des modules, pannes et réparations
+use crate::RobotModule;
+use serde::{Deserialize, Serialize};
+
+/// Santé d'un module neuf
+pub const MAX_MODULE_HEALTH: u8 = 100;
+/// Probabilité qu'une utilisation coûte 1 point de santé au module
+pub const WEAR_CHANCE: f64 = 0.25;
+/// Probabilité de panne d'un module à bout de souffle (proportionnelle à l'usure)
+pub const MALFUNCTION_RATE: f64 = 0.05;
+/// Tours passés hors ligne après une panne passagère
+pub const MALFUNCTION_TURNS: u8 = 5;
+/// Usure infligée par un tour dans une tempête
+pub const STORM_WEAR: u8 = 2;
+/// Usure infligée par une météorite
+pub const METEOR_WEAR: u8 = 30;
+/// Santé rendue à chaque module par tour passé à la station
+pub const STATION_REPAIR: u8 = 20;
+/// Santé rendue par une réparation sur le terrain
+pub const FIELD_REPAIR: u8 = 40;
+/// Mineraux consommés par une réparation sur le terrain
+pub const REPAIR_MINERAL_COST: u32 = 5;
+
+/// État d'un module : santé (0 = hors d'usage) et panne passagère
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleCondition {
+    pub health: u8,
+    pub offline_turns: u8,
+}
+
+impl Default for ModuleCondition {
+    fn default() -> Self {
+        ModuleCondition { health: MAX_MODULE_HEALTH, offline_turns: 0 }
+    }
+}
+
+impl ModuleCondition {
+    pub fn is_broken(&self) -> bool {
+        self.health == 0
+    }
+
+    pub fn is_working(&self) -> bool {
+        !self.is_broken() && self.offline_turns == 0
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.health < MAX_MODULE_HEALTH || self.offline_turns > 0
+    }
+
+    /// Chance de panne à chaque utilisation : nulle à neuf, maximale à l'usure complète
+    pub fn malfunction_chance(&self) -> f64 {
+        (MAX_MODULE_HEALTH - self.health) as f64 / MAX_MODULE_HEALTH as f64 * MALFUNCTION_RATE
+    }
+}
+
+/// Tirage pseudo-aléatoire dans [0, 1[ : même graine, même tour, même robot => même résultat
+pub fn roll(seed: u64, turn: usize, robot_id: usize, module: RobotModule) -> f64 {
+    // SplitMix64 sur une combinaison des entrées
+    let mut z = seed
+        ^ (turn as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (robot_id as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ (module as u64).wrapping_mul(0x1656_67B1_9E37_79F9);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Position, ResourceType, RobotBehavior};
+    use crate::robot::Robot;
+
+    fn collector() -> Robot {
+        Robot::new(1, Position::new(0, 0), RobotBehavior::Collecteur)
+    }
+
+    #[test]
+    fn wear_breaks_a_module_once() {
+        let mut robot = collector();
+        assert!(!robot.needs_repair());
+        assert!(!robot.wear(RobotModule::CollecteMineraux, 60));
+        assert!(robot.needs_repair());
+        assert!(robot.module_working(RobotModule::CollecteMineraux));
+        assert!(robot.condition(RobotModule::CollecteMineraux).malfunction_chance() > 0.0);
+
+        assert!(robot.wear(RobotModule::CollecteMineraux, 60));
+        assert!(!robot.wear(RobotModule::CollecteMineraux, METEOR_WEAR));
+        assert!(!robot.can_collect(ResourceType::Mineraux));
+        assert_eq!(robot.broken_modules(), vec![RobotModule::CollecteMineraux]);
+        assert_eq!(robot.failures, 1);
+
+        // Un module absent ne s'use pas
+        assert!(!robot.wear(RobotModule::AnalyseChimique, 100));
+        assert!(!robot.condition(RobotModule::AnalyseChimique).is_damaged());
+    }
+
+    #[test]
+    fn malfunction_keeps_a_module_offline_for_a_few_turns() {
+        let mut robot = collector();
+        robot.malfunction(RobotModule::Deplacement, 2);
+        assert!(!robot.module_working(RobotModule::Deplacement));
+        assert!(robot.broken_modules().is_empty());
+
+        robot.tick_malfunctions();
+        assert!(!robot.module_working(RobotModule::Deplacement));
+        robot.tick_malfunctions();
+        assert!(robot.module_working(RobotModule::Deplacement));
+        assert_eq!(robot.failures, 1);
+    }
+
+    #[test]
+    fn repair_restores_the_most_damaged_module_first() {
+        let mut robot = collector();
+        robot.wear(RobotModule::CollecteEnergie, 30);
+        robot.wear(RobotModule::CollecteMineraux, MAX_MODULE_HEALTH);
+
+        assert_eq!(robot.repair_most_damaged(FIELD_REPAIR), Some((RobotModule::CollecteMineraux, true)));
+        assert_eq!(robot.condition(RobotModule::CollecteMineraux).health, FIELD_REPAIR);
+        assert_eq!(robot.repairs, 1);
+
+        // Une panne passagère est annulée par la réparation, sans compter une remise en service de plus
+        robot.malfunction(RobotModule::CollecteEnergie, MALFUNCTION_TURNS);
+        assert!(robot.repair(RobotModule::CollecteEnergie, STATION_REPAIR));
+        assert!(robot.module_working(RobotModule::CollecteEnergie));
+        assert!(!robot.repair(RobotModule::CollecteEnergie, MAX_MODULE_HEALTH));
+        assert!(!robot.condition(RobotModule::CollecteEnergie).is_damaged());
+        assert_eq!(robot.repairs, 2);
+
+        robot.repair(RobotModule::CollecteMineraux, MAX_MODULE_HEALTH);
+        assert!(!robot.needs_repair());
+        assert_eq!(robot.repair_most_damaged(FIELD_REPAIR), None);
+    }
+
+    #[test]
+    fn rolls_are_reproducible_and_spread() {
+        let first = roll(42, 10, 3, RobotModule::Deplacement);
+        assert_eq!(first, roll(42, 10, 3, RobotModule::Deplacement));
+        assert_ne!(first, roll(42, 11, 3, RobotModule::Deplacement));
+        assert_ne!(first, roll(42, 10, 4, RobotModule::Deplacement));
+        assert_ne!(first, roll(43, 10, 3, RobotModule::Deplacement));
+
+        let rolls: Vec<f64> = (0..1000).map(|turn| roll(7, turn, 1, RobotModule::Communication)).collect();
+        assert!(rolls.iter().all(|r| (0.0..1.0).contains(r)));
+        let below_quarter = rolls.iter().filter(|r| **r < WEAR_CHANCE).count();
+        assert!((200..300).contains(&below_quarter), "{}", below_quarter);
+    }
+}
@@ -0,0 +1,209 @@
+// src/simulation/benchmark.rs - Charges de mesure partagées par `cargo bench` et le mode bench du binaire
+use crate::{Cell, Position, RobotBehavior, TerrainType};
+use crate::Grid;
+use crate::map::{find_path, map_to_ascii, map_to_json, parse_ascii_map, parse_json_map, MapGenerator};
+use crate::robot::Robot;
+use crate::simulation::engine::SimulationEngine;
+use colored::Colorize;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+/// Graine commune à toutes les mesures, pour comparer des runs entre eux
+pub const BENCH_SEED: u64 = 7;
+/// Côtés de carte mesurés pour le débit du moteur
+pub const MAP_SIZES: [usize; 3] = [50, 100, 200];
+/// Effectifs mesurés pour le débit du moteur
+pub const ROBOT_COUNTS: [usize; 3] = [10, 50, 200];
+/// Tours joués par mesure de débit, depuis un moteur neuf (l'essaim s'épuise au fil d'une partie)
+pub const ENGINE_TURNS: usize = 50;
+/// Grand essaim mesuré à part, en séquentiel et en parallèle : côté de carte, effectif et tours joués
+pub const CROWD_SIZE: usize = 500;
+pub const CROWD_ROBOTS: usize = 1000;
+pub const CROWD_TURNS: usize = 20;
+/// Requêtes de pathfinding par lot
+pub const PATH_QUERIES: usize = 50;
+
+// Durée minimale d'une mesure du mode bench
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(500);
+
+const ROLES: [RobotBehavior; 3] = [RobotBehavior::Explorateur, RobotBehavior::Collecteur, RobotBehavior::Scientifique];
+
+/// Moteur de mesure : carte générée de côté `size`, robots répartis sur les cases libres, rôles alternés
+pub fn bench_engine(size: usize, robot_count: usize) -> SimulationEngine {
+    let mut map = MapGenerator::new(BENCH_SEED, size, size).generate();
+    map[Position::new(1, 1)].terrain = TerrainType::Plaine;
+    let free: Vec<Position> = map.enumerate().filter(|(_, cell)| cell.is_passable()).map(|(pos, _)| pos).collect();
+    let stride = (free.len() / robot_count.max(1)).max(1);
+    let robots = (0..robot_count)
+        .map(|id| Robot::new(id, free[(id * stride) % free.len()], ROLES[id % ROLES.len()]))
+        .collect();
+    SimulationEngine::new(map, robots)
+}
+
+/// Joue `turns` tours complets
+pub fn run_turns(engine: &mut SimulationEngine, turns: usize) {
+    for _ in 0..turns {
+        engine.execute_turn();
+        engine.end_turn();
+    }
+}
+
+/// Manières d'obtenir une carte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapStrategy {
+    /// Relief par bruit puis ressources (`MapGenerator`)
+    Procedurale,
+    /// Lecture d'une carte au format texte
+    FichierAscii,
+    /// Lecture d'une carte au format JSON
+    FichierJson,
+}
+
+impl MapStrategy {
+    pub const ALL: [MapStrategy; 3] = [MapStrategy::Procedurale, MapStrategy::FichierAscii, MapStrategy::FichierJson];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MapStrategy::Procedurale => "procédurale",
+            MapStrategy::FichierAscii => "fichier ascii",
+            MapStrategy::FichierJson => "fichier json",
+        }
+    }
+}
+
+/// Production d'une carte carrée ; le texte des formats fichier est préparé hors mesure
+pub struct MapWorkload {
+    pub strategy: MapStrategy,
+    pub size: usize,
+    source: String,
+}
+
+impl MapWorkload {
+    pub fn new(strategy: MapStrategy, size: usize) -> Self {
+        let source = match strategy {
+            MapStrategy::Procedurale => String::new(),
+            MapStrategy::FichierAscii => map_to_ascii(&MapGenerator::new(BENCH_SEED, size, size).generate()),
+            MapStrategy::FichierJson => map_to_json(&MapGenerator::new(BENCH_SEED, size, size).generate()),
+        };
+        MapWorkload { strategy, size, source }
+    }
+
+    pub fn run(&self) -> Grid<Cell> {
+        match self.strategy {
+            MapStrategy::Procedurale => MapGenerator::new(BENCH_SEED, self.size, self.size).generate(),
+            MapStrategy::FichierAscii => parse_ascii_map(&self.source).expect("carte ascii illisible"),
+            MapStrategy::FichierJson => parse_json_map(&self.source).expect("carte json illisible"),
+        }
+    }
+}
+
+/// Lot de requêtes de chemin entre cases libres tirées au hasard
+pub struct PathWorkload {
+    pub map: Grid<Cell>,
+    pub queries: Vec<(Position, Position)>,
+}
+
+impl PathWorkload {
+    pub fn new(size: usize, count: usize) -> Self {
+        let map = MapGenerator::new(BENCH_SEED, size, size).generate();
+        let free: Vec<Position> = map.enumerate().filter(|(_, cell)| cell.is_passable()).map(|(pos, _)| pos).collect();
+        let mut rng = StdRng::seed_from_u64(BENCH_SEED);
+        let queries = (0..count)
+            .map(|_| (free[rng.gen_range(0..free.len())], free[rng.gen_range(0..free.len())]))
+            .collect();
+        PathWorkload { map, queries }
+    }
+
+    /// Nombre de chemins trouvés
+    pub fn run(&self) -> usize {
+        self.queries.iter().filter(|(from, to)| find_path(&self.map, *from, *to).is_some()).count()
+    }
+}
+
+/// Une ligne du tableau du mode bench
+#[derive(Debug, Clone)]
+pub struct BenchRow {
+    pub group: &'static str,
+    pub case: String,
+    pub per_op: Duration,
+    pub throughput: Option<(f64, &'static str)>,
+}
+
+// Répète `op` sur une entrée neuve jusqu'à MIN_SAMPLE_TIME mesurés ; renvoie la durée moyenne d'un appel.
+// La préparation (`setup`) n'est pas chronométrée.
+fn sample<T>(mut setup: impl FnMut() -> T, mut op: impl FnMut(T)) -> Duration {
+    op(setup()); // échauffement
+    let mut measured = Duration::ZERO;
+    let mut iterations = 0u32;
+    while iterations == 0 || measured < MIN_SAMPLE_TIME {
+        let input = setup();
+        let start = Instant::now();
+        op(input);
+        measured += start.elapsed();
+        iterations += 1;
+    }
+    measured / iterations
+}
+
+/// Mesures rapides du mode bench : débit, génération de cartes et pathfinding
+pub fn run_benchmarks() -> Vec<BenchRow> {
+    let mut rows = Vec::new();
+
+    for size in MAP_SIZES {
+        for robots in ROBOT_COUNTS {
+            let per_turn = sample(|| bench_engine(size, robots), |mut engine| run_turns(&mut engine, ENGINE_TURNS))
+                / ENGINE_TURNS as u32;
+            rows.push(BenchRow {
+                group: "moteur",
+                case: format!("{}x{}, {} robots", size, size, robots),
+                per_op: per_turn,
+                throughput: Some((1.0 / per_turn.as_secs_f64(), "tours/s")),
+            });
+        }
+    }
+
+    for strategy in MapStrategy::ALL {
+        for size in MAP_SIZES {
+            let workload = MapWorkload::new(strategy, size);
+            let per_map = sample(|| (), |_| {
+                workload.run();
+            });
+            rows.push(BenchRow {
+                group: "carte",
+                case: format!("{} {}x{}", strategy.label(), size, size),
+                per_op: per_map,
+                throughput: Some(((size * size) as f64 / per_map.as_secs_f64() / 1e6, "Mcases/s")),
+            });
+        }
+    }
+
+    for size in MAP_SIZES {
+        let workload = PathWorkload::new(size, PATH_QUERIES);
+        let per_batch = sample(|| (), |_| {
+            workload.run();
+        });
+        rows.push(BenchRow {
+            group: "chemin",
+            case: format!("{}x{}, par requête", size, size),
+            per_op: per_batch / PATH_QUERIES as u32,
+            throughput: None,
+        });
+    }
+
+    rows
+}
+
+/// Tableau des mesures, aligné pour comparer deux runs d'un coup d'œil
+pub fn print_bench_table(rows: &[BenchRow]) {
+    println!("{}", "=== BANC D'ESSAI ===".bright_yellow().bold());
+    println!("{:<8} │ {:<28} │ {:>14} │ {:>16}", "Groupe", "Cas", "Par opération", "Débit");
+    println!("{}", "─".repeat(76));
+    for row in rows {
+        let throughput = row
+            .throughput
+            .map(|(value, unit)| format!("{:.1} {}", value, unit))
+            .unwrap_or_else(|| "-".to_string());
+        println!("{:<8} │ {:<28} │ {:>14} │ {:>16}", row.group, row.case, format!("{:.2?}", row.per_op), throughput);
+    }
+}
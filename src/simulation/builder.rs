@@ -0,0 +1,317 @@
+// src/simulation/builder.rs - Assemblage d'un moteur de simulation depuis du code
+use crate::{Cell, Position, ResourceType, TerrainType};
+use crate::{Grid, Topology};
+use crate::map::MapGenerator;
+use crate::robot::Robot;
+use crate::simulation::coordinator::CoordinatorConfig;
+use crate::simulation::depot::{Depot, DepotKind};
+use crate::simulation::engine::SimulationEngine;
+use crate::simulation::environment::{Environment, EnvironmentConfig};
+use crate::simulation::mission::Objectives;
+use crate::simulation::team::{Tactics, Team, TeamColor};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Station posée d'office quand aucun dépôt n'est déclaré
+pub const DEFAULT_STATION: Position = Position { x: 1, y: 1 };
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// Ni `map` ni `generated_map` n'ont été appelés
+    MissingMap,
+    /// Robots, dépôts ou gisements incompatibles avec la carte ou les équipes
+    Invalid(String),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::MissingMap => write!(f, "aucune carte fournie"),
+            BuildError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+#[derive(Debug, Clone)]
+struct TeamSetup {
+    name: String,
+    color: TeamColor,
+    tactics: Tactics,
+}
+
+/// Construit un `SimulationEngine` : la carte est obligatoire, tout le reste a une valeur par défaut
+/// (une équipe, une station en (1,1), environnement et coordinateur standard, décisions parallèles).
+///
+/// Sur une carte générée, les cases des robots et des dépôts sont dégagées ; sur une carte fournie,
+/// elles doivent déjà être praticables.
+#[derive(Debug, Clone)]
+pub struct SimulationBuilder {
+    map: Option<Grid<Cell>>,
+    generated: bool,
+    topology: Option<Topology>,
+    seed: u64,
+    robots: Vec<Robot>,
+    depots: Vec<(usize, DepotKind, Position)>,
+    deposits: Vec<(Position, ResourceType, u32)>,
+    teams: Vec<TeamSetup>,
+    objectives: Objectives,
+    environment: EnvironmentConfig,
+    coordinator: CoordinatorConfig,
+    parallel: bool,
+}
+
+impl Default for SimulationBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulationBuilder {
+    pub fn new() -> Self {
+        SimulationBuilder {
+            map: None,
+            generated: false,
+            topology: None,
+            seed: 0,
+            robots: Vec::new(),
+            depots: Vec::new(),
+            deposits: Vec::new(),
+            teams: Vec::new(),
+            objectives: Objectives::default(),
+            environment: EnvironmentConfig::default(),
+            coordinator: CoordinatorConfig::default(),
+            parallel: true,
+        }
+    }
+
+    /// Carte fournie telle quelle (chargée d'un fichier, construite à la main...)
+    pub fn map(mut self, map: Grid<Cell>) -> Self {
+        self.map = Some(map);
+        self.generated = false;
+        self
+    }
+
+    /// Carte procédurale ; la graine sert aussi d'amorce à l'environnement
+    pub fn generated_map(mut self, seed: u64, width: usize, height: usize) -> Self {
+        self.map = Some(MapGenerator::new(seed, width, height).generate());
+        self.generated = true;
+        self.seed = seed;
+        self
+    }
+
+    /// Bords de la carte ; ceux de la carte fournie sont gardés sinon
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = Some(topology);
+        self
+    }
+
+    /// Amorce de l'environnement (tempêtes, météores) quand sa configuration n'en donne pas
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn robot(mut self, robot: Robot) -> Self {
+        self.robots.push(robot);
+        self
+    }
+
+    pub fn robots(mut self, robots: impl IntoIterator<Item = Robot>) -> Self {
+        self.robots.extend(robots);
+        self
+    }
+
+    /// Dépôt d'une équipe ; le premier dépôt déclaré remplace la station par défaut
+    pub fn depot(mut self, team: usize, kind: DepotKind, position: Position) -> Self {
+        self.depots.push((team, kind, position));
+        self
+    }
+
+    /// Ressources ajoutées à une case après la génération de la carte
+    pub fn deposit(mut self, position: Position, resource: ResourceType, amount: u32) -> Self {
+        self.deposits.push((position, resource, amount));
+        self
+    }
+
+    /// Équipe suivante (numérotée dans l'ordre des appels) ; sans appel, un seul essaim
+    pub fn team(mut self, name: &str, color: TeamColor, tactics: Tactics) -> Self {
+        self.teams.push(TeamSetup { name: name.to_string(), color, tactics });
+        self
+    }
+
+    pub fn objectives(mut self, objectives: Objectives) -> Self {
+        self.objectives = objectives;
+        self
+    }
+
+    pub fn environment(mut self, environment: EnvironmentConfig) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    pub fn coordinator(mut self, coordinator: CoordinatorConfig) -> Self {
+        self.coordinator = coordinator;
+        self
+    }
+
+    /// Décisions des robots réparties sur plusieurs threads (même résultat qu'en séquentiel)
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Vérifie dépôts, gisements et robots, puis assemble le moteur
+    pub fn build(self) -> Result<SimulationEngine, BuildError> {
+        let mut map = self.map.ok_or(BuildError::MissingMap)?;
+        if let Some(topology) = self.topology {
+            map.set_topology(topology);
+        }
+        let (width, height) = (map.width(), map.height());
+        let inside = |pos: Position| pos.x < width && pos.y < height;
+        let team_count = self.teams.len().max(1);
+
+        let declared = if self.depots.is_empty() { vec![(0, DepotKind::Station, DEFAULT_STATION)] } else { self.depots };
+        let mut depots: Vec<Depot> = Vec::with_capacity(declared.len());
+        for (team, kind, pos) in declared {
+            if team >= team_count {
+                return Err(BuildError::Invalid(format!(
+                    "{} ({},{}) rattachée à l'équipe {} inexistante",
+                    kind.label(), pos.x, pos.y, team
+                )));
+            }
+            if !inside(pos) {
+                return Err(BuildError::Invalid(format!(
+                    "{} ({},{}) hors de la carte {}x{}",
+                    kind.label(), pos.x, pos.y, width, height
+                )));
+            }
+            if depots.iter().any(|depot| depot.position == pos) {
+                return Err(BuildError::Invalid(format!("deux bases en ({},{})", pos.x, pos.y)));
+            }
+            // Comme pour les robots : terrain dégagé sur une carte générée, vérifié sinon
+            if self.generated && !map[pos].is_passable() {
+                map[pos].terrain = TerrainType::Plaine;
+            }
+            if !map[pos].is_passable() {
+                return Err(BuildError::Invalid(format!(
+                    "{} placée sur une case infranchissable ({},{})",
+                    kind.label(), pos.x, pos.y
+                )));
+            }
+            depots.push(Depot::new(depots.len(), team, kind, pos));
+        }
+
+        for (pos, resource, amount) in self.deposits {
+            if !inside(pos) {
+                return Err(BuildError::Invalid(format!(
+                    "gisement ({},{}) hors de la carte {}x{}",
+                    pos.x, pos.y, width, height
+                )));
+            }
+            map[pos].add_resource(resource, amount);
+        }
+
+        let mut ids = HashSet::new();
+        for robot in &self.robots {
+            let pos = robot.position;
+            if !ids.insert(robot.id) {
+                return Err(BuildError::Invalid(format!("identifiant de robot {} dupliqué", robot.id)));
+            }
+            if robot.team >= team_count {
+                return Err(BuildError::Invalid(format!(
+                    "robot {} rattaché à l'équipe {} inexistante",
+                    robot.id, robot.team
+                )));
+            }
+            // Sur une carte générée, la zone d'atterrissage est dégagée
+            if self.generated && inside(pos) && !map[pos].is_passable() {
+                map[pos].terrain = TerrainType::Plaine;
+            }
+            if !map.get(pos).is_some_and(|cell| cell.is_passable()) {
+                return Err(BuildError::Invalid(format!(
+                    "robot {} placé sur une case invalide ({},{})",
+                    robot.id, pos.x, pos.y
+                )));
+            }
+        }
+
+        let mut engine = SimulationEngine::new(map, self.robots);
+        engine.depots = depots;
+        if !self.teams.is_empty() {
+            engine.teams = self
+                .teams
+                .into_iter()
+                .enumerate()
+                .map(|(id, setup)| {
+                    let mut team = Team::new(id, &setup.name, setup.color, &engine.map);
+                    team.tactics = setup.tactics;
+                    team
+                })
+                .collect();
+        }
+        engine.objectives = self.objectives;
+        engine.environment = Environment::new(self.environment, self.seed);
+        engine.coordinator = self.coordinator;
+        engine.parallel = self.parallel;
+        Ok(engine)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RobotBehavior;
+
+    fn plain_map() -> Grid<Cell> {
+        let mut map = Grid::new(10, 6, Cell::new(TerrainType::Plaine));
+        map[Position::new(5, 3)].terrain = TerrainType::Obstacle;
+        map
+    }
+
+    #[test]
+    fn provided_map_must_accept_robots_and_depots() {
+        let on_obstacle = SimulationEngine::builder()
+            .map(plain_map())
+            .robot(Robot::new(1, Position::new(5, 3), RobotBehavior::Explorateur))
+            .build();
+        assert!(matches!(on_obstacle, Err(BuildError::Invalid(_))));
+
+        let duplicate = SimulationEngine::builder()
+            .map(plain_map())
+            .robot(Robot::new(1, Position::new(2, 2), RobotBehavior::Explorateur))
+            .robot(Robot::new(1, Position::new(3, 2), RobotBehavior::Collecteur))
+            .build();
+        assert!(matches!(duplicate, Err(BuildError::Invalid(_))));
+
+        let foreign_depot = SimulationEngine::builder().map(plain_map()).depot(1, DepotKind::Station, Position::new(2, 2)).build();
+        assert!(matches!(foreign_depot, Err(BuildError::Invalid(_))));
+
+        assert_eq!(SimulationEngine::builder().build().err(), Some(BuildError::MissingMap));
+    }
+
+    #[test]
+    fn teams_depots_and_deposits_reach_the_engine() {
+        let engine = SimulationEngine::builder()
+            .map(plain_map())
+            .topology(Topology::Tore)
+            .team("Alpha", TeamColor::Vert, Tactics::default())
+            .team("Beta", TeamColor::Rouge, Tactics { relays: false, ..Tactics::default() })
+            .depot(0, DepotKind::Station, Position::new(0, 0))
+            .depot(1, DepotKind::Station, Position::new(9, 5))
+            .deposit(Position::new(4, 4), ResourceType::Mineraux, 30)
+            .robot(Robot::new(7, Position::new(1, 1), RobotBehavior::Collecteur))
+            .parallel(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(engine.teams.len(), 2);
+        assert!(!engine.teams[1].tactics.relays);
+        assert_eq!(engine.depots[1].team, 1);
+        assert_eq!(engine.map.topology(), Topology::Tore);
+        assert_eq!(engine.cell(Position::new(4, 4)).unwrap().resources.amount(ResourceType::Mineraux), 30);
+        assert_eq!(engine.robot(7).unwrap().position, Position::new(1, 1));
+        assert!(!engine.parallel);
+    }
+}
@@ -0,0 +1,214 @@
+// src/simulation/control.rs - Pilotage du moteur depuis du code : commandes, bilans et observateurs
+use crate::{Position, ResourceType};
+use crate::robot::{Robot, RobotAction};
+use crate::simulation::events::SimulationEvent;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Intervention extérieure sur le monde, appliquée immédiatement par `SimulationEngine::command`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Command {
+    /// Déplace un robot (par identifiant) sur une case praticable, sans coût d'énergie
+    MoveRobot { id: usize, to: Position },
+    /// Ajoute un robot ; identifiant libre, équipe existante et case praticable exigés
+    SpawnRobot(Box<Robot>),
+    /// Fixe la quantité d'une ressource sur une case (0 pour l'effacer)
+    SetResource { position: Position, resource: ResourceType, amount: u32 },
+    /// Téléopération : le robot n'agit plus que sur ordre (et attend sans ordre)
+    TakeControl(usize),
+    /// Rend le robot à son comportement autonome
+    ReleaseControl(usize),
+    /// Action d'un robot téléopéré pour le prochain tour ; remplace l'ordre précédent
+    Order { id: usize, action: RobotAction },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    UnknownRobot(usize),
+    DuplicateRobot(usize),
+    UnknownTeam(usize),
+    NotPiloted(usize),
+    /// Case hors de la carte ou infranchissable
+    Blocked(Position),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::UnknownRobot(id) => write!(f, "aucun robot {}", id),
+            CommandError::DuplicateRobot(id) => write!(f, "robot {} déjà présent", id),
+            CommandError::UnknownTeam(team) => write!(f, "équipe {} inexistante", team),
+            CommandError::NotPiloted(id) => write!(f, "robot {} pas en pilotage manuel", id),
+            CommandError::Blocked(pos) => write!(f, "case ({},{}) inaccessible", pos.x, pos.y),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Rappel enregistré auprès du moteur, appelé pour chaque nouvel événement
+pub type EventObserver = Box<dyn FnMut(&SimulationEvent) + Send>;
+
+/// Identifiant rendu par `subscribe`, à passer à `unsubscribe`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(pub(crate) usize);
+
+/// Bilan chiffré de la simulation à un instant donné
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationStats {
+    pub turn: usize,
+    pub explored_cells: usize,
+    pub exploration_percentage: f32,
+    pub alive_robots: usize,
+    pub energy_collected: u32,
+    pub minerals_collected: u32,
+    pub minerals_delivered: u32,
+    pub science_data: u32,
+    pub science_sites: usize,
+    pub messages_delivered: usize,
+    pub module_failures: u32,
+    pub module_repairs: u32,
+    pub role_changes: u32,
+    pub relay_chains: usize,
+    pub storms: usize,
+    pub meteor_impacts: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, RobotBehavior, TerrainType};
+    use crate::Grid;
+    use crate::simulation::engine::SimulationEngine;
+    use crate::simulation::events::EventKind;
+    use crate::simulation::replay::{ReplayError, ReplayPlayer};
+    use std::sync::{Arc, Mutex};
+
+    fn small_engine() -> SimulationEngine {
+        let mut map = Grid::new(12, 6, Cell::new(TerrainType::Plaine));
+        map[Position::new(6, 2)].terrain = TerrainType::Obstacle;
+        SimulationEngine::builder()
+            .map(map)
+            .robot(Robot::new(1, Position::new(3, 3), RobotBehavior::Explorateur))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn commands_change_the_world_and_keep_indexes_in_sync() {
+        let mut engine = small_engine();
+
+        engine.command(Command::MoveRobot { id: 1, to: Position::new(9, 4) }).unwrap();
+        assert_eq!(engine.robot(1).unwrap().position, Position::new(9, 4));
+        assert_eq!(engine.robot_positions.within_radius(Position::new(9, 4), 0), vec![(0, Position::new(9, 4))]);
+
+        let scout = Robot::new(2, Position::new(2, 1), RobotBehavior::Collecteur);
+        engine.command(Command::SpawnRobot(Box::new(scout))).unwrap();
+        assert_eq!(engine.robots.len(), 2);
+        assert_eq!(engine.robot_positions.len(), 2);
+
+        let deposit = Position::new(4, 4);
+        engine.command(Command::SetResource { position: deposit, resource: ResourceType::Mineraux, amount: 40 }).unwrap();
+        assert_eq!(engine.cell(deposit).unwrap().resources.amount(ResourceType::Mineraux), 40);
+        assert_eq!(engine.resource_cells.within_radius(deposit, 0).len(), 1);
+        engine.command(Command::SetResource { position: deposit, resource: ResourceType::Mineraux, amount: 0 }).unwrap();
+        assert!(engine.resource_cells.within_radius(deposit, 0).is_empty());
+    }
+
+    #[test]
+    fn invalid_commands_are_rejected_without_effect() {
+        let mut engine = small_engine();
+        let blocked = Position::new(6, 2);
+
+        assert_eq!(engine.command(Command::MoveRobot { id: 9, to: blocked }), Err(CommandError::UnknownRobot(9)));
+        assert_eq!(engine.command(Command::MoveRobot { id: 1, to: blocked }), Err(CommandError::Blocked(blocked)));
+        let twin = Robot::new(1, Position::new(1, 1), RobotBehavior::Collecteur);
+        assert_eq!(engine.command(Command::SpawnRobot(Box::new(twin))), Err(CommandError::DuplicateRobot(1)));
+        let mut foreign = Robot::new(5, Position::new(1, 1), RobotBehavior::Collecteur);
+        foreign.team = 3;
+        assert_eq!(engine.command(Command::SpawnRobot(Box::new(foreign))), Err(CommandError::UnknownTeam(3)));
+
+        assert_eq!(engine.robot(1).unwrap().position, Position::new(3, 3));
+        assert_eq!(engine.robots.len(), 1);
+        assert_eq!(engine.events.total(), 0);
+    }
+
+    #[test]
+    fn observers_receive_each_event_once_until_unsubscribed() {
+        let mut engine = small_engine();
+        let seen: Arc<Mutex<Vec<SimulationEvent>>> = Arc::default();
+        let sink = Arc::clone(&seen);
+        let observer = engine.subscribe(move |event| sink.lock().unwrap().push(event.clone()));
+
+        engine.command(Command::MoveRobot { id: 1, to: Position::new(4, 3) }).unwrap();
+        for _ in 0..30 {
+            engine.step();
+        }
+        {
+            let seen = seen.lock().unwrap();
+            assert_eq!(seen.len(), engine.events.total());
+            assert_eq!(seen[0].kind, EventKind::Commande);
+        }
+
+        assert!(engine.unsubscribe(observer));
+        assert!(!engine.unsubscribe(observer));
+        engine.command(Command::MoveRobot { id: 1, to: Position::new(5, 3) }).unwrap();
+        assert_eq!(seen.lock().unwrap().len(), engine.events.total() - 1);
+    }
+
+    #[test]
+    fn piloted_robot_follows_orders_then_waits() {
+        let mut engine = small_engine();
+        assert_eq!(engine.command(Command::Order { id: 1, action: RobotAction::Wait }), Err(CommandError::NotPiloted(1)));
+        assert_eq!(engine.command(Command::TakeControl(9)), Err(CommandError::UnknownRobot(9)));
+
+        engine.command(Command::TakeControl(1)).unwrap();
+        assert!(engine.is_piloted(1));
+        assert_eq!(engine.planned_action(1), Some(RobotAction::Wait));
+
+        let east = Position::new(4, 3);
+        engine.command(Command::Order { id: 1, action: RobotAction::Move(east) }).unwrap();
+        assert_eq!(engine.planned_action(1), Some(RobotAction::Move(east)));
+        engine.step();
+        assert_eq!(engine.robot(1).unwrap().position, east);
+
+        // L'ordre ne vaut qu'un tour : sans nouvel ordre, le robot reste sur place
+        engine.step();
+        assert_eq!(engine.robot(1).unwrap().position, east);
+
+        engine.command(Command::ReleaseControl(1)).unwrap();
+        assert!(!engine.is_piloted(1));
+        assert_eq!(engine.command(Command::ReleaseControl(1)), Err(CommandError::NotPiloted(1)));
+    }
+
+    #[test]
+    fn recorded_commands_replay_into_the_same_world() {
+        let mut engine = small_engine();
+        engine.start_recording();
+        for turn in 0..40 {
+            match turn {
+                5 => engine.command(Command::SpawnRobot(Box::new(Robot::new(2, Position::new(1, 4), RobotBehavior::Collecteur)))).unwrap(),
+                12 => engine.command(Command::MoveRobot { id: 1, to: Position::new(10, 1) }).unwrap(),
+                20 => engine.command(Command::SetResource { position: Position::new(2, 4), resource: ResourceType::Mineraux, amount: 30 }).unwrap(),
+                _ => {}
+            }
+            engine.step();
+        }
+
+        let replay = engine.take_recording().unwrap();
+        let mut player = ReplayPlayer::new(replay).unwrap();
+        player.seek(player.total_turns());
+        let replayed = player.engine();
+
+        assert_eq!(replayed.robots.len(), 2);
+        assert_eq!(serde_json::to_string(&replayed.snapshot()).unwrap(), serde_json::to_string(&engine.snapshot()).unwrap());
+    }
+
+    #[test]
+    fn actions_must_match_the_fleet() {
+        let mut engine = small_engine();
+        let result = engine.apply_actions(&[RobotAction::Wait, RobotAction::Wait]);
+        assert!(matches!(result, Err(ReplayError::ActionCount { expected: 1, found: 2, .. })));
+        assert_eq!(engine.robot(1).unwrap().energy, Robot::new(1, Position::new(3, 3), RobotBehavior::Explorateur).energy);
+    }
+}
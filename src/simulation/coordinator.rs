@@ -0,0 +1,271 @@
+// src/simulation/coordinator.rs - Coordinateur d'essaim : réaffectation des rôles selon les besoins
+use crate::{ResourceType, RobotBehavior};
+use crate::robot::Robot;
+use crate::simulation::engine::SimulationEngine;
+use serde::{Deserialize, Serialize};
+
+/// Ressources repérées à partir desquelles la collecte est pleinement prioritaire
+pub const DEPOSIT_SATURATION: f64 = 100.0;
+/// Sites en attente à partir desquels l'analyse est pleinement prioritaire
+pub const SITE_SATURATION: f64 = 5.0;
+/// Tours pendant lesquels une réaffectation reste signalée à l'écran
+pub const ROLE_CHANGE_NOTICE_TURNS: usize = 50;
+
+const ROLES: [RobotBehavior; 3] = [
+    RobotBehavior::Explorateur,
+    RobotBehavior::Collecteur,
+    RobotBehavior::Scientifique,
+];
+
+/// Réglages du coordinateur ; l'hystérésis vient de la marge et du délai entre deux changements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoordinatorConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Tours entre deux évaluations des besoins
+    #[serde(default = "default_interval")]
+    pub interval: usize,
+    /// Tours minimum avant qu'un robot change à nouveau de rôle
+    #[serde(default = "default_cooldown")]
+    pub cooldown: usize,
+    /// Écart, en robots, entre effectif voulu et effectif réel avant de réagir
+    #[serde(default = "default_margin")]
+    pub margin: f64,
+}
+
+impl Default for CoordinatorConfig {
+    fn default() -> Self {
+        CoordinatorConfig {
+            enabled: default_enabled(),
+            interval: default_interval(),
+            cooldown: default_cooldown(),
+            margin: default_margin(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_interval() -> usize {
+    25
+}
+
+fn default_cooldown() -> usize {
+    100
+}
+
+fn default_margin() -> f64 {
+    1.5
+}
+
+/// Besoins d'une équipe, estimés à partir de ce qu'elle a exploré et livré
+#[derive(Debug, Clone)]
+pub struct SwarmNeeds {
+    /// Part de la carte encore inexplorée (0 à 1)
+    pub unexplored: f64,
+    /// Énergie et mineraux repérés sur les cases explorées
+    pub known_deposits: u32,
+    /// Sites scientifiques repérés et pas encore analysés
+    pub pending_sites: usize,
+    /// Objectif de livraison de mineraux déjà atteint
+    pub stockpile_met: bool,
+}
+
+impl SwarmNeeds {
+    pub fn assess(engine: &SimulationEngine, team: usize) -> Self {
+        let knowledge = &engine.teams[team];
+        let mut known_deposits = 0;
+        let mut pending_sites = 0;
+        for (_, cell) in engine.map.enumerate().filter(|(pos, _)| knowledge.knows(*pos)) {
+            known_deposits += cell.resources.amount(ResourceType::Energie) + cell.resources.amount(ResourceType::Mineraux);
+            if cell.resources.amount(ResourceType::LieuxInteret) > 0 {
+                pending_sites += 1;
+            }
+        }
+
+        let stockpile_met = engine
+            .objectives
+            .minerals_delivered
+            .is_some_and(|target| engine.delivered_by(team, ResourceType::Mineraux) >= target);
+
+        let total_cells = engine.map.len().max(1) as f64;
+        SwarmNeeds {
+            unexplored: 1.0 - knowledge.known_cell_count() as f64 / total_cells,
+            known_deposits,
+            pending_sites,
+            stockpile_met,
+        }
+    }
+
+    /// Poids relatif d'un rôle (0 = inutile pour l'instant)
+    pub fn weight(&self, role: RobotBehavior) -> f64 {
+        match role {
+            RobotBehavior::Explorateur => self.unexplored,
+            RobotBehavior::Collecteur => {
+                // Une station déjà bien fournie réduit le besoin de collecte
+                let pressure = (self.known_deposits as f64 / DEPOSIT_SATURATION).min(1.0);
+                if self.stockpile_met { pressure / 2.0 } else { pressure }
+            }
+            RobotBehavior::Scientifique => (self.pending_sites as f64 / SITE_SATURATION).min(1.0),
+        }
+    }
+
+    pub fn reason(&self, role: RobotBehavior) -> String {
+        match role {
+            RobotBehavior::Explorateur => format!("{:.0}% de la carte inexplorée", self.unexplored * 100.0),
+            RobotBehavior::Collecteur => format!("{} unités de ressources repérées", self.known_deposits),
+            RobotBehavior::Scientifique => format!("{} sites à analyser", self.pending_sites),
+        }
+    }
+}
+
+/// Changement de rôle à appliquer
+#[derive(Debug, Clone)]
+pub struct Reassignment {
+    pub robot_index: usize,
+    pub role: RobotBehavior,
+    pub reason: String,
+}
+
+/// Au plus une réaffectation par équipe et par évaluation, pour laisser l'essaim se stabiliser
+pub fn plan_reassignment(config: &CoordinatorConfig, engine: &SimulationEngine, team: usize) -> Option<Reassignment> {
+    if !config.enabled || config.interval == 0 || !engine.turn.is_multiple_of(config.interval) {
+        return None;
+    }
+
+    let turn = engine.turn;
+    // Les maillons d'une chaîne de relais gardent leur rôle tant que la chaîne tient
+    let available = |robot: &Robot| {
+        robot.team == team
+            && robot.is_operational()
+            && robot.relay.is_none()
+            && robot.last_role_change().is_none_or(|changed| turn >= changed + config.cooldown)
+    };
+    let needs = SwarmNeeds::assess(engine, team);
+
+    // D'abord les robots que leurs modules ne laissent plus tenir leur rôle
+    for (index, robot) in engine.robots.iter().enumerate() {
+        if !available(robot) || robot.can_take_role(robot.behavior) {
+            continue;
+        }
+        let fallback = ROLES
+            .into_iter()
+            .filter(|role| robot.can_take_role(*role) && needs.weight(*role) > 0.0)
+            .max_by(|a, b| needs.weight(*a).total_cmp(&needs.weight(*b)));
+        if let Some(role) = fallback {
+            let reason = format!("modules de {:?} hors d'usage", robot.behavior);
+            return Some(Reassignment { robot_index: index, role, reason });
+        }
+    }
+
+    // Puis l'équilibre entre effectif voulu (proportionnel aux besoins) et effectif réel
+    let total_weight: f64 = ROLES.iter().map(|role| needs.weight(*role)).sum();
+    if total_weight <= 0.0 {
+        return None;
+    }
+
+    let members = || engine.robots.iter().filter(|robot| robot.team == team && robot.is_operational());
+    let alive = members().count() as f64;
+    let shortfall = |role: RobotBehavior| {
+        let staffed = members().filter(|r| r.behavior == role).count() as f64;
+        alive * needs.weight(role) / total_weight - staffed
+    };
+
+    let mut best: Option<(f64, Reassignment)> = None;
+    for (index, robot) in engine.robots.iter().enumerate().filter(|(_, robot)| available(robot)) {
+        let surplus = -shortfall(robot.behavior);
+        if surplus < config.margin {
+            continue;
+        }
+
+        for role in ROLES {
+            let gain = shortfall(role);
+            if role == robot.behavior || gain < config.margin || !robot.can_take_role(role) {
+                continue;
+            }
+            if best.as_ref().is_none_or(|(score, _)| gain + surplus > *score) {
+                let reason = needs.reason(role);
+                best = Some((gain + surplus, Reassignment { robot_index: index, role, reason }));
+            }
+        }
+    }
+
+    best.map(|(_, reassignment)| reassignment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Grid, Position, RobotModule, TerrainType};
+
+    // Quatre scientifiques capables de tout faire, sur une carte sans site ni gisement :
+    // seule l'exploration est utile
+    fn idle_scientists() -> SimulationEngine {
+        let map = Grid::new(20, 10, Cell::new(TerrainType::Plaine));
+        let robots = (1..=4)
+            .map(|id| {
+                let mut robot = Robot::new(id, Position::new(id * 2, 5), RobotBehavior::Scientifique);
+                robot.modules = vec![RobotModule::Deplacement, RobotModule::AnalyseChimique, RobotModule::CollecteMineraux];
+                robot
+            })
+            .collect();
+        SimulationEngine::new(map, robots)
+    }
+
+    fn config(margin: f64) -> CoordinatorConfig {
+        CoordinatorConfig { enabled: true, interval: 25, cooldown: 100, margin }
+    }
+
+    #[test]
+    fn overstaffed_role_moves_only_beyond_the_margin() {
+        let mut engine = idle_scientists();
+        let needs = SwarmNeeds::assess(&engine, 0);
+        assert_eq!(needs.weight(RobotBehavior::Scientifique), 0.0);
+        assert!(needs.weight(RobotBehavior::Explorateur) > 0.9);
+
+        // 4 scientifiques de trop, 4 explorateurs manquants
+        let reassignment = plan_reassignment(&config(1.5), &engine, 0).unwrap();
+        assert_eq!(reassignment.role, RobotBehavior::Explorateur);
+        assert!(plan_reassignment(&config(4.5), &engine, 0).is_none());
+        assert!(plan_reassignment(&CoordinatorConfig { enabled: false, ..config(1.5) }, &engine, 0).is_none());
+
+        // Entre deux évaluations, rien ne bouge
+        engine.turn = 10;
+        assert!(plan_reassignment(&config(1.5), &engine, 0).is_none());
+    }
+
+    #[test]
+    fn recently_reassigned_robots_wait_for_the_cooldown() {
+        let mut engine = idle_scientists();
+        for robot in &mut engine.robots {
+            robot.switch_role(RobotBehavior::Scientifique, 10, "essai".to_string());
+        }
+
+        engine.turn = 100;
+        assert!(plan_reassignment(&config(1.5), &engine, 0).is_none());
+        engine.turn = 125;
+        assert!(plan_reassignment(&config(1.5), &engine, 0).is_some());
+    }
+
+    #[test]
+    fn broken_modules_force_a_role_the_robot_can_still_hold() {
+        let mut engine = idle_scientists();
+        let collector = Robot::new(5, Position::new(12, 2), RobotBehavior::Collecteur);
+        engine.robots.push(collector);
+        engine.robots[4].wear(RobotModule::CollecteEnergie, 100);
+        engine.robots[4].wear(RobotModule::CollecteMineraux, 100);
+
+        // Passe avant l'équilibre général, même avec une marge qui bloquerait tout le reste
+        let reassignment = plan_reassignment(&config(50.0), &engine, 0).unwrap();
+        assert_eq!(reassignment.robot_index, 4);
+        assert_eq!(reassignment.role, RobotBehavior::Explorateur);
+        assert!(reassignment.reason.contains("hors d'usage"));
+
+        // Plus aucun module de déplacement : aucun rôle utile à proposer
+        engine.robots[4].wear(RobotModule::Deplacement, 100);
+        assert!(plan_reassignment(&config(50.0), &engine, 0).is_none());
+    }
+}
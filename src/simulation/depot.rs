@@ -0,0 +1,94 @@
+// src/simulation/depot.rs - Dépôts : stations et avant-postes construits par l'essaim
+use crate::{Position, ResourceType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Mineraux consommés par la construction d'un avant-poste
+pub const OUTPOST_MINERAL_COST: u32 = 20;
+/// Distance minimale entre un nouvel avant-poste et le dépôt le plus proche
+pub const OUTPOST_MIN_SPACING: usize = 15;
+/// Nombre maximal d'avant-postes construits par équipe
+pub const MAX_OUTPOSTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DepotKind {
+    /// Base complète : stockage, recharge rapide et atelier de réparation
+    #[default]
+    Station,
+    /// Construit sur le terrain : stockage et recharge lente seulement
+    Avantposte,
+}
+
+impl DepotKind {
+    /// Énergie rendue à chaque tour passé sur le dépôt
+    pub fn recharge(&self) -> u32 {
+        match self {
+            DepotKind::Station => 5,
+            DepotKind::Avantposte => 2,
+        }
+    }
+
+    pub fn repairs_modules(&self) -> bool {
+        *self == DepotKind::Station
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            DepotKind::Station => "H",
+            DepotKind::Avantposte => "h",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DepotKind::Station => "Station",
+            DepotKind::Avantposte => "Avant-poste",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Depot {
+    pub id: usize,
+    /// Équipe propriétaire : seuls ses robots y déchargent et s'y rechargent
+    #[serde(default)]
+    pub team: usize,
+    pub kind: DepotKind,
+    pub position: Position,
+    /// Ressources livrées à ce dépôt
+    #[serde(default)]
+    pub stockpile: BTreeMap<ResourceType, u32>,
+}
+
+impl Depot {
+    pub fn new(id: usize, team: usize, kind: DepotKind, position: Position) -> Self {
+        Depot { id, team, kind, position, stockpile: BTreeMap::new() }
+    }
+
+    pub fn store(&mut self, resource_type: ResourceType, amount: u32) {
+        *self.stockpile.entry(resource_type).or_insert(0) += amount;
+    }
+
+    pub fn stored(&self, resource_type: ResourceType) -> u32 {
+        self.stockpile.get(&resource_type).copied().unwrap_or(0)
+    }
+}
+
+/// Positions des dépôts, éventuellement restreintes à un type
+pub fn depot_positions(depots: &[Depot], kind: Option<DepotKind>) -> Vec<Position> {
+    depots
+        .iter()
+        .filter(|depot| kind.is_none_or(|kind| depot.kind == kind))
+        .map(|depot| depot.position)
+        .collect()
+}
+
+/// Positions des dépôts d'une équipe, éventuellement restreintes à un type
+pub fn team_depot_positions(depots: &[Depot], team: usize, kind: Option<DepotKind>) -> Vec<Position> {
+    depots
+        .iter()
+        .filter(|depot| depot.team == team && kind.is_none_or(|kind| depot.kind == kind))
+        .map(|depot| depot.position)
+        .collect()
+}
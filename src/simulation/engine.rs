@@ -1,24 +1,90 @@
-// src/simulation/engine.rs - Moteur de simulation avec interface visuelle
-use crate::{Cell, Position};
-use crate::robot::{Robot, RobotAction, BehaviorEngine};
-use colored::Colorize;
-use std::io::{self, Write};
-use std::thread;
-use std::time::Duration;
-use crate::display::DisplayEngine;
+// src/simulation/engine.rs - Moteur de simulation : tours, règles, commandes et observateurs
+use crate::{Cell, Position, ResourceType};
+use crate::Grid;
+use crate::RobotModule;
+use crate::robot::{
+    collection_module, roll, BehaviorEngine, DecisionContext, Robot, RobotAction, FIELD_REPAIR, MALFUNCTION_TURNS,
+    REPAIR_MINERAL_COST, STATION_REPAIR, WEAR_CHANCE,
+};
+use crate::map::{line_of_sight, move_energy_cost, SpatialIndex};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use crate::simulation::builder::SimulationBuilder;
+use crate::simulation::control::{Command, CommandError, EventObserver, ObserverId, SimulationStats};
+use crate::simulation::coordinator::{self, CoordinatorConfig};
+use crate::simulation::depot::{depot_positions, Depot, DepotKind, MAX_OUTPOSTS, OUTPOST_MINERAL_COST, OUTPOST_MIN_SPACING};
+use crate::simulation::environment::Environment;
+use crate::simulation::events::{EventKind, EventLog, SimulationEvent};
+use crate::simulation::heatmap::Traces;
+use crate::simulation::mission::{self, MissionStatus, Objectives};
+use crate::simulation::relay::RelayNetwork;
+use crate::simulation::replay::{Replay, ReplayError, SimulationSnapshot};
+use crate::simulation::team::{Team, TeamColor};
+
+/// Énergie maximale d'un robot
+pub const MAX_ENERGY: u32 = 100;
+/// Énergie consommée par une analyse
+pub const ANALYZE_ENERGY_COST: u32 = 3;
+/// Énergie consommée par une communication
+pub const COMMUNICATE_ENERGY_COST: u32 = 2;
+/// Énergie récupérée en attendant
+pub const WAIT_ENERGY_RECOVERY: u32 = 1;
+/// Énergie minimale conservée par un robot qui en cède à un autre
+pub const SHARE_ENERGY_RESERVE: u32 = 10;
+/// Énergie consommée par une réparation sur le terrain
+pub const REPAIR_ENERGY_COST: u32 = 2;
+/// Limite de tours du mode automatique quand le scénario n'en fixe pas
+pub const DEFAULT_MAX_TURNS: usize = 1000;
+/// En dessous de ce nombre de robots, répartir les décisions sur plusieurs threads coûte plus qu'il ne rapporte
+pub const PARALLEL_DECIDE_THRESHOLD: usize = 64;
 
 pub struct SimulationEngine {
-    pub map: Vec<Vec<Cell>>,
+    pub map: Grid<Cell>,
     pub robots: Vec<Robot>,
     pub turn: usize,
     pub width: usize,
     pub height: usize,
+    /// Stations et avant-postes ; chacun garde ce qui lui est livré
+    pub depots: Vec<Depot>,
+    pub science_sites_completed: usize,
+    pub messages_delivered: usize,
+    pub objectives: Objectives,
+    pub mission_status: MissionStatus,
+    pub environment: Environment,
+    pub coordinator: CoordinatorConfig,
+    pub relays: RelayNetwork,
+    /// Essaims en présence ; un seul sauf en mode compétition
+    pub teams: Vec<Team>,
+    pub events: EventLog,
+    /// Passages, énergie dépensée et dernier relevé de chaque case (cartes de chaleur)
+    pub traces: Traces,
+    /// Index spatial des robots (par rang dans `robots`) et des cases porteuses de ressources
+    pub robot_positions: SpatialIndex,
+    pub resource_cells: SpatialIndex,
+    /// Décisions des robots réparties sur plusieurs threads (résultat identique au séquentiel)
+    pub parallel: bool,
+    recording: Option<Replay>,
+    // Verrou seulement pour que le moteur reste partageable entre threads pendant la phase de décision
+    observers: Mutex<Vec<(ObserverId, EventObserver)>>,
+    next_observer: usize,
+    // Robots téléopérés (par identifiant) et leur ordre pour le prochain tour
+    piloted: BTreeMap<usize, Option<RobotAction>>,
 }
 
 impl SimulationEngine {
-    pub fn new(map: Vec<Vec<Cell>>, robots: Vec<Robot>) -> Self {
-        let height = map.len();
-        let width = if height > 0 { map[0].len() } else { 0 };
+    /// Point d'entrée recommandé : carte, robots, dépôts et réglages vérifiés à la construction
+    pub fn builder() -> SimulationBuilder {
+        SimulationBuilder::new()
+    }
+
+    /// Moteur brut sur une carte et des robots, sans vérification ; une équipe et une station en (1,1)
+    pub fn new(map: Grid<Cell>, robots: Vec<Robot>) -> Self {
+        let width = map.width();
+        let height = map.height();
+        let teams = vec![Team::new(0, "Essaim", TeamColor::for_team(0), &map)];
+        let robot_positions = SpatialIndex::of_positions(&map, robots.iter().map(|robot| robot.position));
+        let resource_cells = SpatialIndex::of_resources(&map);
 
         SimulationEngine {
             map,
@@ -26,286 +92,966 @@ impl SimulationEngine {
             turn: 0,
             width,
             height,
+            depots: vec![Depot::new(0, 0, DepotKind::Station, Position::new(1, 1))],
+            science_sites_completed: 0,
+            messages_delivered: 0,
+            objectives: Objectives::default(),
+            mission_status: MissionStatus::EnCours,
+            environment: Environment::default(),
+            coordinator: CoordinatorConfig::default(),
+            relays: RelayNetwork::default(),
+            teams,
+            events: EventLog::default(),
+            traces: Traces::new(width, height),
+            robot_positions,
+            resource_cells,
+            parallel: true,
+            recording: None,
+            observers: Mutex::new(Vec::new()),
+            next_observer: 0,
+            piloted: BTreeMap::new(),
         }
     }
 
-    /// Lance la simulation interactive
-    pub fn run_interactive(&mut self) {
-        println!("{}", "🎮 Mode interactif activé !".bright_green().bold());
-        println!("{}", "Utilisez ENTER pour avancer tour par tour, 'q' pour quitter".bright_yellow());
-
-        loop {
-            self.display_current_states();
-            self.display_controls();
-
-            print!("\n> ");
-            io::stdout().flush().unwrap();
-
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-            let command = input.trim().to_lowercase();
-
-            match command.as_str() {
-                "q" | "quit" => {
-                    println!("{}", "🏁 Simulation arrêtée par l'utilisateur".bright_red());
-                    break;
-                },
-                "r" | "rapport" => {
-                    self.print_detailed_report();
-                },
-                "s" | "auto" => {
-                    self.run_auto_mode();
-                },
-                "" => {
-                    // Exécuter un tour
-                    self.execute_turn();
-                    self.turn += 1;
-                },
-                _ => {
-                    println!("{}", "Commande non reconnue. Utilisez ENTER, 'q', 'r', ou 's'".bright_red());
-                }
-            }
+    /// Reconstruit un moteur à partir d'un instantané
+    pub fn from_snapshot(snapshot: SimulationSnapshot) -> Self {
+        let mut engine = SimulationEngine::new(snapshot.map, snapshot.robots);
+        engine.turn = snapshot.turn;
+        if !snapshot.depots.is_empty() {
+            engine.depots = snapshot.depots;
+        }
+        engine.science_sites_completed = snapshot.science_sites_completed;
+        engine.objectives = snapshot.objectives;
+        engine.mission_status = snapshot.mission_status;
+        engine.messages_delivered = snapshot.messages_delivered;
+        engine.environment = snapshot.environment;
+        engine.coordinator = snapshot.coordinator;
+        engine.relays = snapshot.relays;
+        if !snapshot.teams.is_empty() {
+            engine.teams = snapshot.teams;
         }
+        engine.events = snapshot.events;
+        if let Some(traces) = snapshot.traces
+            && traces.fits(&engine.map)
+        {
+            engine.traces = traces;
+        }
+        engine
+    }
 
-        self.print_final_report();
+    /// Reconstruit les index spatiaux après une modification directe de la carte ou des robots
+    pub fn reindex(&mut self) {
+        self.robot_positions = SpatialIndex::of_positions(&self.map, self.robots.iter().map(|robot| robot.position));
+        self.resource_cells = SpatialIndex::of_resources(&self.map);
     }
 
-    /// Affiche l'état actuel de la simulation
-    fn display_current_states(&self) {
-        use crate::display::DisplayEngine;
-        DisplayEngine::clear_screen();
-        DisplayEngine::display_header(self.turn);
-        DisplayEngine::display_map(&self.map, &self.robots);
-        DisplayEngine::display_robot_stats(&self.robots);
-        // Statistiques rapides
-        let explored_cells = self.map.iter()
-            .flat_map(|row| row.iter())
-            .filter(|cell| cell.explored)
-            .count();
-        let total_cells = self.width * self.height;
-        let exploration_percentage = (explored_cells as f32 / total_cells as f32) * 100.0;
+    /// Capture l'état courant (carte, robots, tour)
+    pub fn snapshot(&self) -> SimulationSnapshot {
+        SimulationSnapshot {
+            turn: self.turn,
+            map: self.map.clone(),
+            robots: self.robots.clone(),
+            depots: self.depots.clone(),
+            science_sites_completed: self.science_sites_completed,
+            objectives: self.objectives.clone(),
+            mission_status: self.mission_status.clone(),
+            messages_delivered: self.messages_delivered,
+            environment: self.environment.clone(),
+            coordinator: self.coordinator.clone(),
+            relays: self.relays.clone(),
+            teams: self.teams.clone(),
+            events: self.events.clone(),
+            traces: Some(self.traces.clone()),
+        }
+    }
 
-        println!("\n📊 Exploration: {:.1}% ({}/{})", exploration_percentage, explored_cells, total_cells);
+    /// Avance d'un tour puis réévalue la mission
+    pub fn step(&mut self) {
+        self.execute_turn();
+        self.close_turn();
     }
 
-    /// Affiche les contrôles
-    fn display_controls(&self) {
-        use crate::display::DisplayEngine;
-        DisplayEngine::display_controls();
+    /// Rejoue un tour enregistré : commandes passées avant le tour, actions des robots, puis fin de tour
+    pub fn replay_turn(&mut self, commands: &[Command], actions: &[RobotAction]) -> Result<(), ReplayError> {
+        for command in commands {
+            self.command(command.clone()).map_err(|error| ReplayError::Command { turn: self.turn, error })?;
+        }
+        self.apply_actions(actions)?;
+        self.close_turn();
+        Ok(())
     }
 
-    /// Mode automatique rapide
-    pub fn run_auto_mode(&mut self) {
-        println!("{}", "🚀 Mode automatique lancé ! (Ctrl+C pour arrêter)".bright_green().bold());
+    // Fin de tour commune au direct et à la relecture, puis réévaluation de la mission
+    fn close_turn(&mut self) {
+        self.end_turn();
 
-        loop {
-            self.display_current_states();
-            self.execute_turn();
-            self.turn += 1;
+        // La fin de mission n'est annoncée qu'une fois, au tour où elle survient
+        let was_finished = self.mission_status.is_finished();
+        self.mission_status = mission::evaluate(&self.objectives, self);
+        if self.mission_status.is_finished() && !was_finished {
+            let message = format!("Mission {}", self.mission_status.label());
+            self.events.push(self.turn, EventKind::Mission, message);
+        }
+        self.notify_observers();
+    }
 
-            // Pause entre les tours
-            thread::sleep(Duration::from_millis(100)); // 10 tours/seconde
+    /// Fin de tour : l'environnement évolue, les rôles et les relais s'ajustent, puis le compteur avance
+    pub fn end_turn(&mut self) {
+        let depots = depot_positions(&self.depots, None);
+        let impacts = self.environment.meteor_impacts;
+        self.environment.update(self.turn, &mut self.map, &mut self.robots, &depots, &mut self.events);
+        // Un impact peut faire apparaître un site scientifique
+        if self.environment.meteor_impacts != impacts {
+            self.resource_cells = SpatialIndex::of_resources(&self.map);
+        }
+        for robot in &mut self.robots {
+            robot.tick_malfunctions();
+        }
 
-            // Arrêter après 1000 tours pour éviter une boucle infinie
-            if self.turn >= 1000 {
-                println!("{}", "⚠️  Limite de 1000 tours atteinte - arrêt automatique".bright_yellow());
-                break;
+        // Chaque équipe a son propre coordinateur
+        for team in 0..self.teams.len() {
+            if !self.teams[team].tactics.reassign_roles {
+                continue;
+            }
+            if let Some(change) = coordinator::plan_reassignment(&self.coordinator, self, team) {
+                let robot = &mut self.robots[change.robot_index];
+                let message = format!("Robot {} : {:?} → {:?} ({})", robot.id, robot.behavior, change.role, change.reason);
+                robot.switch_role(change.role, self.turn, change.reason);
+                self.events.push(self.turn, EventKind::Robot, message);
             }
         }
+        RelayNetwork::update(self);
+        self.turn += 1;
+        self.notify_observers();
     }
 
-    /// Affiche l'état actuel de la simulation
-    fn display_current_state(&self) {
-        DisplayEngine::clear_screen();
-        DisplayEngine::display_header(self.turn);
-        DisplayEngine::display_map(&self.map, &self.robots);
-        DisplayEngine::display_robot_stats(&self.robots);
+    /// Enregistre un rappel appelé pour chaque événement, à la fin du tour (ou de la commande) qui l'a produit
+    pub fn subscribe(&mut self, observer: impl FnMut(&SimulationEvent) + Send + 'static) -> ObserverId {
+        let id = ObserverId(self.next_observer);
+        self.next_observer += 1;
+        self.observers.get_mut().unwrap().push((id, Box::new(observer)));
+        id
+    }
 
-        // Statistiques rapides
-        let explored_cells = self.map.iter()
-            .flat_map(|row| row.iter())
-            .filter(|cell| cell.explored)
-            .count();
-        let total_cells = self.width * self.height;
-        let exploration_percentage = (explored_cells as f32 / total_cells as f32) * 100.0;
+    /// Retire un observateur ; `false` s'il l'était déjà
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        let observers = self.observers.get_mut().unwrap();
+        let before = observers.len();
+        observers.retain(|(observer, _)| *observer != id);
+        observers.len() != before
+    }
 
-        println!("\n📊 Exploration: {:.1}% ({}/{})", exploration_percentage, explored_cells, total_cells);
+    fn notify_observers(&mut self) {
+        let fresh = self.events.take_fresh();
+        for (_, observer) in self.observers.get_mut().unwrap().iter_mut() {
+            for event in &fresh {
+                observer(event);
+            }
+        }
     }
 
-    /// Lance la simulation classique (sans interface)
-    pub fn run(&mut self, max_turns: usize) {
-        println!("{}", format!("🎮 Démarrage de la simulation ({} tours max)", max_turns).bright_green());
+    /// Applique une intervention extérieure tout de suite, entre deux tours ; enregistrée dans le replay en cours
+    pub fn command(&mut self, command: Command) -> Result<(), CommandError> {
+        let recorded = self.recording.is_some().then(|| command.clone());
+        let message = self.apply_command(command)?;
+        if let (Some(replay), Some(command)) = (&mut self.recording, recorded) {
+            replay.record_command(command);
+        }
 
-        for turn in 0..max_turns {
-            self.turn = turn;
-            self.execute_turn();
+        if let Some(message) = message {
+            self.events.push(self.turn, EventKind::Commande, message);
+            self.notify_observers();
+        }
+        Ok(())
+    }
 
-            if turn % 10 == 0 {
-                self.print_status();
+    // Effet d'une commande et message du journal (aucun pour un ordre ou une prise de contrôle répétée)
+    fn apply_command(&mut self, command: Command) -> Result<Option<String>, CommandError> {
+        let message = match command {
+            Command::MoveRobot { id, to } => {
+                let index = self.robots.iter().position(|robot| robot.id == id).ok_or(CommandError::UnknownRobot(id))?;
+                if !self.map.get(to).is_some_and(|cell| cell.is_passable()) {
+                    return Err(CommandError::Blocked(to));
+                }
+                let from = std::mem::replace(&mut self.robots[index].position, to);
+                self.robot_positions.relocate(index, from, to);
+                let team = self.robots[index].team;
+                self.map[to].explored = true;
+                self.teams[team].discover(to);
+                format!("Robot {} déplacé de ({},{}) en ({},{})", id, from.x, from.y, to.x, to.y)
+            }
+            Command::TakeControl(id) => {
+                self.robot(id).ok_or(CommandError::UnknownRobot(id))?;
+                if self.piloted.insert(id, None).is_some() {
+                    return Ok(None);
+                }
+                format!("Robot {} passe en pilotage manuel", id)
+            }
+            Command::ReleaseControl(id) => {
+                if self.piloted.remove(&id).is_none() {
+                    return Err(CommandError::NotPiloted(id));
+                }
+                format!("Robot {} rendu à l'autonomie", id)
             }
+            Command::Order { id, action } => {
+                let order = self.piloted.get_mut(&id).ok_or(CommandError::NotPiloted(id))?;
+                *order = Some(action);
+                // Ordre courant, sans trace dans le journal
+                return Ok(None);
+            }
+            Command::SpawnRobot(robot) => {
+                if self.robots.iter().any(|other| other.id == robot.id) {
+                    return Err(CommandError::DuplicateRobot(robot.id));
+                }
+                if robot.team >= self.teams.len() {
+                    return Err(CommandError::UnknownTeam(robot.team));
+                }
+                if !self.map.get(robot.position).is_some_and(|cell| cell.is_passable()) {
+                    return Err(CommandError::Blocked(robot.position));
+                }
+                let message = format!(
+                    "Robot {} ({:?}) ajouté en ({},{})",
+                    robot.id, robot.behavior, robot.position.x, robot.position.y
+                );
+                self.robot_positions.insert(self.robots.len(), robot.position);
+                self.robots.push(*robot);
+                message
+            }
+            Command::SetResource { position, resource, amount } => {
+                let cell = self.map.get_mut(position).ok_or(CommandError::Blocked(position))?;
+                cell.resources.remove(resource);
+                cell.resources.add(resource, amount);
+                let present = !cell.resources.is_empty();
+                self.resource_cells.set_cell(position, present);
+                format!("{:?} en ({},{}) fixé à {}", resource, position.x, position.y, amount)
+            }
+        };
+        Ok(Some(message))
+    }
+
+    /// Bilan chiffré courant (exploration, collecte, pannes, environnement)
+    pub fn stats(&self) -> SimulationStats {
+        SimulationStats {
+            turn: self.turn,
+            explored_cells: self.explored_cell_count(),
+            exploration_percentage: self.exploration_percentage(),
+            alive_robots: self.alive_robot_count(),
+            energy_collected: self.collected_total(ResourceType::Energie),
+            minerals_collected: self.collected_total(ResourceType::Mineraux),
+            minerals_delivered: self.delivered(ResourceType::Mineraux),
+            science_data: self.collected_total(ResourceType::LieuxInteret),
+            science_sites: self.science_sites_completed,
+            messages_delivered: self.messages_delivered,
+            module_failures: self.module_failures(),
+            module_repairs: self.module_repairs(),
+            role_changes: self.role_changes(),
+            relay_chains: self.relays.chains_formed,
+            storms: self.environment.storms_formed,
+            meteor_impacts: self.environment.meteor_impacts,
         }
+    }
 
-        println!("{}", "🏁 Simulation terminée !".bright_blue());
-        self.print_final_report();
+    /// Déroule la mission sans affichage jusqu'à sa fin
+    pub fn run_headless(&mut self) {
+        while !self.is_finished() {
+            self.step();
+        }
     }
 
-    fn execute_turn(&mut self) {
-        // Traiter chaque robot
-        for i in 0..self.robots.len() {
-            let robot = &self.robots[i];
+    /// Robot par identifiant (et non par rang dans `robots`)
+    pub fn robot(&self, id: usize) -> Option<&Robot> {
+        self.robots.iter().find(|robot| robot.id == id)
+    }
 
-            // Décider de l'action
-            let action = BehaviorEngine::decide_action(
-                robot.behavior,
-                robot.position,
-                robot.energy,
-                robot.is_inventory_full(),
-            );
+    /// Case de la carte, `None` hors des bords
+    pub fn cell(&self, pos: Position) -> Option<&Cell> {
+        self.map.get(pos)
+    }
+
+    /// Ce que le robot ferait au prochain tour dans l'état actuel (ordre en attente s'il est téléopéré)
+    pub fn planned_action(&self, id: usize) -> Option<RobotAction> {
+        let index = self.robots.iter().position(|robot| robot.id == id)?;
+        Some(self.decide(index))
+    }
 
-            // Exécuter l'action
-            self.execute_robot_action(i, action);
+    /// Robot sous contrôle manuel
+    pub fn is_piloted(&self, id: usize) -> bool {
+        self.piloted.contains_key(&id)
+    }
+
+    /// Coéquipiers que le robot peut joindre par radio ce tour-ci (identifiants)
+    pub fn reachable_teammates(&self, id: usize) -> Vec<usize> {
+        let Some(from) = self.robots.iter().position(|robot| robot.id == id) else {
+            return Vec::new();
+        };
+        (0..self.robots.len())
+            .filter(|to| self.can_communicate(from, *to))
+            .map(|to| self.robots[to].id)
+            .collect()
+    }
+
+    /// Mission réussie ou échouée : plus rien à jouer
+    pub fn is_finished(&self) -> bool {
+        self.mission_status.is_finished()
+    }
+
+    pub fn explored_cell_count(&self) -> usize {
+        self.map.iter()
+            .filter(|cell| cell.explored)
+            .count()
+    }
+
+    pub fn exploration_percentage(&self) -> f32 {
+        let total_cells = (self.width * self.height).max(1);
+        (self.explored_cell_count() as f32 / total_cells as f32) * 100.0
+    }
+
+    pub fn alive_robot_count(&self) -> usize {
+        self.robots.iter().filter(|robot| robot.is_operational()).count()
+    }
+
+    /// Quantité d'une ressource livrée, tous dépôts confondus
+    pub fn delivered(&self, resource_type: ResourceType) -> u32 {
+        self.depots.iter().map(|depot| depot.stored(resource_type)).sum()
+    }
+
+    /// Quantité d'une ressource livrée aux dépôts d'une équipe
+    pub fn delivered_by(&self, team: usize, resource_type: ResourceType) -> u32 {
+        self.depots.iter().filter(|depot| depot.team == team).map(|depot| depot.stored(resource_type)).sum()
+    }
+
+    /// Dépôt situé sur une case
+    pub fn depot_at(&self, pos: Position) -> Option<&Depot> {
+        self.depots.iter().find(|depot| depot.position == pos)
+    }
+
+    pub fn remaining_on_map(&self, resource_type: ResourceType) -> u32 {
+        self.map.iter()
+            .map(|cell| cell.resources.amount(resource_type))
+            .sum()
+    }
+
+    /// Commence l'enregistrement d'un replay à partir de l'état courant
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Replay::new(self.snapshot()));
+    }
+
+    /// Arrête l'enregistrement et rend le replay
+    pub fn take_recording(&mut self) -> Option<Replay> {
+        self.recording.take()
+    }
+
+    /// Un tour en deux phases : tous les robots décident sur l'état du début de tour, puis les actions sont appliquées
+    pub fn execute_turn(&mut self) {
+        let actions = self.decide_actions();
+        self.apply_actions(&actions).expect("une décision par robot");
+        // Un ordre manuel ne vaut que pour un tour
+        for order in self.piloted.values_mut() {
+            *order = None;
+        }
+
+        if let Some(replay) = &mut self.recording {
+            replay.record_turn(actions);
+        }
+    }
+
+    /// Phase de décision, sans effet sur le monde : parallélisable robot par robot
+    pub fn decide_actions(&self) -> Vec<RobotAction> {
+        if self.parallel && self.robots.len() >= PARALLEL_DECIDE_THRESHOLD {
+            (0..self.robots.len()).into_par_iter().map(|i| self.decide(i)).collect()
+        } else {
+            (0..self.robots.len()).map(|i| self.decide(i)).collect()
+        }
+    }
+
+    /// Action choisie par un robot d'après sa perception et la connaissance de son équipe ;
+    /// un robot téléopéré exécute son ordre ou attend
+    fn decide(&self, robot_index: usize) -> RobotAction {
+        let robot = &self.robots[robot_index];
+        if let Some(order) = self.piloted.get(&robot.id) {
+            return order.clone().unwrap_or(RobotAction::Wait);
+        }
+        let context = DecisionContext {
+            robot,
+            team: &self.teams[robot.team],
+            robots: &self.robots,
+            depots: &self.depots,
+            map: &self.map,
+            robot_positions: &self.robot_positions,
+            resource_cells: &self.resource_cells,
+        };
+        BehaviorEngine::decide_action(&context)
+    }
+
+    /// Phase de résolution, séquentielle : une action par robot, dans l'ordre des robots (sert aussi aux replays) ;
+    /// refusée sans effet si le nombre d'actions ne correspond pas à la flotte
+    pub fn apply_actions(&mut self, actions: &[RobotAction]) -> Result<(), ReplayError> {
+        if actions.len() != self.robots.len() {
+            return Err(ReplayError::ActionCount { turn: self.turn, expected: self.robots.len(), found: actions.len() });
+        }
+        let before: Vec<(Position, u32)> = self.robots.iter().map(|robot| (robot.position, robot.energy)).collect();
+        for (i, action) in actions.iter().enumerate() {
+            self.execute_robot_action(i, action.clone());
         }
+        // Batteries vidées par l'action du tour
+        for (robot, &(_, energy)) in self.robots.iter().zip(&before) {
+            if energy > 0 && !robot.is_operational() {
+                self.events.push(self.turn, EventKind::Perte, format!("Robot {} à court d'énergie, hors service", robot.id));
+            }
+        }
+        self.traces.record(self.turn, &before, &self.robots);
+        Ok(())
     }
 
     fn execute_robot_action(&mut self, robot_index: usize, action: RobotAction) {
+        // Un robot hors service n'agit plus
+        if !self.robots[robot_index].is_operational() {
+            return;
+        }
+
         match action {
-            RobotAction::Move(new_position) => {
-                if self.is_valid_position(new_position) &&
-                    self.map[new_position.y][new_position.x].is_passable() {
+            RobotAction::Move(mut new_position) => {
+                // Sans propulsion en état de marche, le robot est immobilisé
+                let robot = &self.robots[robot_index];
+                let from = robot.position;
+                let stalled = robot.has_module(RobotModule::Deplacement) && !robot.module_working(RobotModule::Deplacement);
+
+                // Une propulsion usée peut dériver vers une autre case voisine
+                if !stalled && self.wear_module(robot_index, RobotModule::Deplacement) {
+                    new_position = self.drift(robot_index, new_position);
+                }
+
+                // Une case adjacente et praticable uniquement
+                if !stalled && self.map.contains(from) && self.map.chebyshev_distance(from, new_position) == 1
+                    && self.map.get(new_position).is_some_and(|cell| cell.is_passable())
+                {
                     // Libérer l'ancienne position
                     let old_pos = self.robots[robot_index].position;
-                    self.map[old_pos.y][old_pos.x].occupied_by = None;
-
-                    // Déplacer le robot
-                    self.robots[robot_index].move_to(new_position);
+                    self.map[old_pos].occupied_by = None;
 
-                    // Occuper la nouvelle position
-                    self.map[new_position.y][new_position.x].occupied_by = Some(robot_index);
+                    // Déplacer le robot, plus cher en montée et chargé
+                    let cost = move_energy_cost(&self.map[old_pos], &self.map[new_position])
+                        + self.robots[robot_index].move_penalty();
+                    if self.robots[robot_index].move_to(new_position, cost) {
+                        self.robot_positions.relocate(robot_index, old_pos, new_position);
+                        // Occuper la nouvelle position (le passage d'un robot la révèle)
+                        // La poussière d'une tempête empêche de cartographier la case
+                        let hidden = self.environment.in_storm(&self.map, new_position);
+                        let cell = &mut self.map[new_position];
+                        cell.occupied_by = Some(robot_index);
+                        cell.explored |= !hidden;
+                        if !hidden {
+                            self.teams[self.robots[robot_index].team].discover(new_position);
+                        }
+                    } else {
+                        self.map[old_pos].occupied_by = Some(robot_index);
+                    }
                 }
             },
             RobotAction::Collect => {
                 let robot = &mut self.robots[robot_index];
                 let pos = robot.position;
-                let cell = &mut self.map[pos.y][pos.x];
+                let cell = &mut self.map[pos];
 
                 // Collecter les ressources disponibles
-                for (resource_type, &amount) in cell.resources.clone().iter() {
-                    if amount > 0 {
-                        let collected = robot.collect_resource(*resource_type, amount);
-                        if collected > 0 {
-                            *cell.resources.get_mut(resource_type).unwrap() -= collected;
-                        }
+                let available = cell.resources;
+                let mut used = Vec::new();
+                for (resource_type, amount) in available.iter() {
+                    let collected = robot.collect_resource(resource_type, amount);
+                    cell.resources.take(resource_type, collected);
+                    if collected > 0 {
+                        used.push(collection_module(resource_type));
                     }
                 }
+
+                let emptied = cell.resources.is_empty();
+                self.resource_cells.set_cell(pos, !emptied);
+                for module in used {
+                    self.wear_module(robot_index, module);
+                }
             },
             RobotAction::Analyze => {
                 let robot = &mut self.robots[robot_index];
                 let pos = robot.position;
-                self.map[pos.y][pos.x].explored = true;
-                robot.energy = robot.energy.saturating_sub(3);
+                let cell = &mut self.map[pos];
+                cell.explored = true;
+                let team = &mut self.teams[robot.team];
+                team.discover(pos);
+
+                // Analyser un site scientifique le termine, au profit de la première équipe venue
+                let analyzing = robot.module_working(RobotModule::AnalyseChimique);
+                let completed = analyzing && cell.resources.remove(ResourceType::LieuxInteret) > 0;
+                if completed {
+                    self.science_sites_completed += 1;
+                    team.science_sites += 1;
+                    self.resource_cells.set_cell(pos, !cell.resources.is_empty());
+                }
+                robot.energy = robot.energy.saturating_sub(ANALYZE_ENERGY_COST);
+
+                if completed {
+                    let message = format!("Robot {} termine l'analyse du site ({},{})", robot.id, pos.x, pos.y);
+                    self.events.push(self.turn, EventKind::Science, message);
+                }
+                if analyzing {
+                    self.wear_module(robot_index, RobotModule::AnalyseChimique);
+                }
             },
-            RobotAction::Communicate(_targets) => {
-                // TODO: Implémenter la communication
-                self.robots[robot_index].energy = self.robots[robot_index].energy.saturating_sub(2);
+            RobotAction::Communicate(targets) => {
+                // Seuls les robots à portée et non masqués par le relief reçoivent le message
+                let delivered = targets
+                    .iter()
+                    .filter_map(|id| self.robots.iter().position(|robot| robot.id == *id))
+                    .filter(|&target| self.can_communicate(robot_index, target))
+                    .count();
+                self.messages_delivered += delivered;
+                self.robots[robot_index].energy = self.robots[robot_index].energy.saturating_sub(COMMUNICATE_ENERGY_COST);
+
+                if self.robots[robot_index].module_working(RobotModule::Communication) {
+                    self.wear_module(robot_index, RobotModule::Communication);
+                }
             },
-            RobotAction::Wait => {
-                // Ne rien faire, mais récupérer un peu d'énergie
+            RobotAction::Repair(target_id) => {
+                self.field_repair(robot_index, target_id);
+            },
+            RobotAction::Drop(resource_type) => {
+                // Le chargement reste sur la case, à la disposition des autres robots
                 let robot = &mut self.robots[robot_index];
-                robot.energy = (robot.energy + 1).min(100);
+                let dropped = robot.unload(resource_type, u32::MAX);
+                if dropped > 0 {
+                    self.map[robot.position].add_resource(resource_type, dropped);
+                    self.resource_cells.set_cell(robot.position, true);
+                }
+            },
+            RobotAction::Transfer { to, resource, amount } => {
+                self.transfer_cargo(robot_index, to, resource, amount);
+            },
+            RobotAction::ShareEnergy { to, amount } => {
+                self.share_energy(robot_index, to, amount);
+            },
+            RobotAction::BuildOutpost => {
+                self.build_outpost(robot_index);
+            },
+            RobotAction::Blocked(_direction) => {
+                // Bord de carte : le robot reste sur place sans se reposer
+            },
+            RobotAction::Wait => {
+                // Ne rien faire, mais recharger au soleil (ni la nuit ni sous la poussière)
+                let pos = self.robots[robot_index].position;
+                if self.environment.solar_available(&self.map, self.turn, pos) {
+                    let robot = &mut self.robots[robot_index];
+                    robot.energy = (robot.energy + WAIT_ENERGY_RECOVERY).min(MAX_ENERGY);
+                }
             }
         }
+
+        self.service_at_depot(robot_index);
     }
 
-    fn is_valid_position(&self, pos: Position) -> bool {
-        pos.x < self.width && pos.y < self.height
+    /// Liaison radio possible : même équipe, modules présents, portée de l'émetteur et ligne de vue
+    pub fn can_communicate(&self, from_index: usize, to_index: usize) -> bool {
+        let (from, to) = (&self.robots[from_index], &self.robots[to_index]);
+        // Les tempêtes divisent la portée par deux
+        let storm = self.environment.in_storm(&self.map, from.position) || self.environment.in_storm(&self.map, to.position);
+        let range = if storm { from.communication_range / 2 } else { from.communication_range };
+
+        from_index != to_index
+            && from.team == to.team
+            && to.is_operational()
+            && from.module_working(RobotModule::Communication)
+            && to.module_working(RobotModule::Communication)
+            && self.map.chebyshev_distance(from.position, to.position) <= range
+            && line_of_sight(&self.map, from.position, to.position)
     }
 
-    fn print_status(&self) {
-        println!("\n{}", format!("📊 Tour {} - État de la simulation", self.turn).bright_cyan());
+    /// Usure d'un module après usage ; vrai en cas de panne passagère
+    fn wear_module(&mut self, robot_index: usize, module: RobotModule) -> bool {
+        let robot = &mut self.robots[robot_index];
+        let r = roll(self.environment.seed, self.turn, robot.id, module);
+        let malfunction_chance = robot.condition(module).malfunction_chance();
+
+        if r < WEAR_CHANCE && robot.wear(module, 1) {
+            let message = format!("Robot {} : module {:?} hors d'usage", robot.id, module);
+            self.events.push(self.turn, EventKind::Robot, message);
+        }
 
-        for robot in &self.robots {
-            let total_resources: u32 = robot.inventory.values().sum();
-            println!(
-                "🤖 Robot {} ({:?}): Position({},{}) Énergie:{} Ressources:{}",
-                robot.id,
-                robot.behavior,
-                robot.position.x,
-                robot.position.y,
-                robot.energy,
-                total_resources
-            );
+        if r < 1.0 - malfunction_chance {
+            return false;
         }
+        // La propulsion dérive au lieu de s'arrêter
+        if module == RobotModule::Deplacement {
+            robot.failures += 1;
+        } else {
+            robot.malfunction(module, MALFUNCTION_TURNS);
+            let message = format!("Robot {} : panne du module {:?} ({} tours)", robot.id, module, MALFUNCTION_TURNS);
+            self.events.push(self.turn, EventKind::Robot, message);
+        }
+        true
     }
 
-    fn print_detailed_report(&self) {
-        DisplayEngine::clear_screen();
-        println!("{}", "📋 RAPPORT DÉTAILLÉ".bright_yellow().bold());
-        println!("{}", "====================".bright_yellow());
+    /// Case voisine tirée au hasard parmi les praticables, à la place de la destination voulue
+    fn drift(&mut self, robot_index: usize, intended: Position) -> Position {
+        let robot = &self.robots[robot_index];
+        let candidates: Vec<Position> = self
+            .map
+            .neighbors8(robot.position)
+            .filter(|pos| self.map[*pos].is_passable())
+            .collect();
+        if candidates.is_empty() {
+            return intended;
+        }
 
-        // Statistiques globales
-        let mut total_energy_collected = 0;
-        let mut total_minerals_collected = 0;
-        let mut total_scientific_data = 0;
+        // Tirage indépendant de celui qui a déclenché la dérive
+        let r = roll(!self.environment.seed, self.turn, robot.id, RobotModule::Deplacement);
+        let landing = candidates[(r * candidates.len() as f64) as usize];
+        let message = format!("Robot {} : propulsion déréglée, dérive en ({},{})", robot.id, landing.x, landing.y);
+        self.events.push(self.turn, EventKind::Robot, message);
+        landing
+    }
 
-        for robot in &self.robots {
-            total_energy_collected += robot.inventory.get(&crate::ResourceType::Energie).unwrap_or(&0);
-            total_minerals_collected += robot.inventory.get(&crate::ResourceType::Mineraux).unwrap_or(&0);
-            total_scientific_data += robot.inventory.get(&crate::ResourceType::LieuxInteret).unwrap_or(&0);
+    /// Réparation sur le terrain : des mineraux transportés contre le module le plus abîmé d'un voisin
+    fn field_repair(&mut self, robot_index: usize, target_id: usize) {
+        let Some(target_index) = self.robots.iter().position(|robot| robot.id == target_id) else {
+            return;
+        };
+        let (repairer, target) = (&self.robots[robot_index], &self.robots[target_index]);
+        if target_index == robot_index
+            || repairer.team != target.team
+            || repairer.carried(ResourceType::Mineraux) < REPAIR_MINERAL_COST
+            || self.map.chebyshev_distance(repairer.position, target.position) > 1
+        {
+            return;
         }
 
-        println!("⚡ Énergie collectée: {}", total_energy_collected);
-        println!("🪨 Mineraux collectés: {}", total_minerals_collected);
-        println!("🔬 Données scientifiques: {}", total_scientific_data);
-        println!("🔄 Tours exécutés: {}", self.turn);
+        let Some((module, restored)) = self.robots[target_index].repair_most_damaged(FIELD_REPAIR) else {
+            return;
+        };
 
-        let explored_cells = self.map.iter()
-            .flat_map(|row| row.iter())
-            .filter(|cell| cell.explored)
+        let repairer = &mut self.robots[robot_index];
+        repairer.unload(ResourceType::Mineraux, REPAIR_MINERAL_COST);
+        repairer.energy = repairer.energy.saturating_sub(REPAIR_ENERGY_COST);
+        let outcome = if restored { "remis en service" } else { "consolidé" };
+        let message = format!("Robot {} répare le module {:?} du robot {} ({})", repairer.id, module, target_id, outcome);
+        self.events.push(self.turn, EventKind::Robot, message);
+    }
+
+    /// Cède du chargement à un coéquipier voisin opérationnel, dans la limite de sa place
+    fn transfer_cargo(&mut self, robot_index: usize, target_id: usize, resource_type: ResourceType, amount: u32) {
+        let Some(target_index) = self.robots.iter().position(|robot| robot.id == target_id) else {
+            return;
+        };
+        let (giver, receiver) = (&self.robots[robot_index], &self.robots[target_index]);
+        if target_index == robot_index
+            || giver.team != receiver.team
+            || !receiver.is_operational()
+            || self.map.chebyshev_distance(giver.position, receiver.position) > 1
+        {
+            return;
+        }
+
+        let moved = amount.min(giver.carried(resource_type)).min(receiver.room_for(resource_type));
+        self.robots[robot_index].unload(resource_type, moved);
+        self.robots[target_index].load(resource_type, moved);
+    }
+
+    /// Cède de l'énergie à un coéquipier voisin opérationnel, sans descendre sous la réserve
+    fn share_energy(&mut self, robot_index: usize, target_id: usize, amount: u32) {
+        let Some(target_index) = self.robots.iter().position(|robot| robot.id == target_id) else {
+            return;
+        };
+        let (giver, receiver) = (&self.robots[robot_index], &self.robots[target_index]);
+        if target_index == robot_index
+            || giver.team != receiver.team
+            || !receiver.is_operational()
+            || self.map.chebyshev_distance(giver.position, receiver.position) > 1
+        {
+            return;
+        }
+
+        let shared = amount
+            .min(giver.energy.saturating_sub(SHARE_ENERGY_RESERVE))
+            .min(MAX_ENERGY.saturating_sub(receiver.energy));
+        self.robots[robot_index].energy -= shared;
+        self.robots[target_index].energy += shared;
+    }
+
+    /// Avant-poste bâti avec les mineraux du robot, loin de tout autre dépôt
+    fn build_outpost(&mut self, robot_index: usize) {
+        let robot = &self.robots[robot_index];
+        let (pos, team) = (robot.position, robot.team);
+        let outposts = self
+            .depots
+            .iter()
+            .filter(|depot| depot.team == team && depot.kind == DepotKind::Avantposte)
             .count();
+        if robot.carried(ResourceType::Mineraux) < OUTPOST_MINERAL_COST
+            || outposts >= MAX_OUTPOSTS
+            || !self.map[pos].is_passable()
+            || self.depots.iter().any(|depot| self.map.chebyshev_distance(pos, depot.position) < OUTPOST_MIN_SPACING)
+        {
+            return;
+        }
 
-        let total_cells = self.width * self.height;
-        let exploration_percentage = (explored_cells as f32 / total_cells as f32) * 100.0;
+        self.robots[robot_index].unload(ResourceType::Mineraux, OUTPOST_MINERAL_COST);
+        let id = self.depots.iter().map(|depot| depot.id).max().map_or(0, |id| id + 1);
+        self.depots.push(Depot::new(id, team, DepotKind::Avantposte, pos));
+        let message = format!("Robot {} construit l'avant-poste {} en ({},{})", self.robots[robot_index].id, id, pos.x, pos.y);
+        self.events.push(self.turn, EventKind::Robot, message);
+    }
 
-        println!("🗺️  Exploration: {:.1}% ({}/{})", exploration_percentage, explored_cells, total_cells);
+    /// Sur un dépôt de l'équipe : déchargement, recharge, et révision des modules dans une station
+    fn service_at_depot(&mut self, robot_index: usize) {
+        let robot = &mut self.robots[robot_index];
+        let Some(depot) = self
+            .depots
+            .iter_mut()
+            .find(|depot| depot.team == robot.team && depot.position == robot.position)
+        else {
+            return;
+        };
 
-        // Détail par robot
-        println!("\n{}", "📋 Détail par robot:".bright_cyan());
-        DisplayEngine::display_robot_stats(&self.robots);
+        for (resource_type, amount) in std::mem::take(&mut robot.inventory) {
+            depot.store(resource_type, amount);
+        }
+        robot.energy = (robot.energy + depot.kind.recharge()).min(MAX_ENERGY);
 
-        println!("\n{}", "Appuyez sur ENTER pour continuer...".bright_white());
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
+        if !depot.kind.repairs_modules() {
+            return;
+        }
+        let modules = robot.modules.clone();
+        for module in modules {
+            if robot.repair(module, STATION_REPAIR) {
+                let message = format!("Robot {} : module {:?} réparé à la station {}", robot.id, module, depot.id);
+                self.events.push(self.turn, EventKind::Robot, message);
+            }
+        }
     }
 
-    fn print_final_report(&self) {
-        println!("\n{}", "📋 RAPPORT FINAL".bright_yellow().bold());
-        println!("{}", "================".bright_yellow());
+    pub fn module_failures(&self) -> u32 {
+        self.robots.iter().map(|robot| robot.failures).sum()
+    }
+
+    pub fn module_repairs(&self) -> u32 {
+        self.robots.iter().map(|robot| robot.repairs).sum()
+    }
+
+    /// Dépôts et mineraux reçus par chacun, ex. "Station 0 (1,1): 40 │ Avant-poste 1 (30,4): 12"
+    pub fn depot_summary(&self) -> String {
+        self.depots
+            .iter()
+            .map(|depot| {
+                format!(
+                    "{} {} ({},{}): {}",
+                    depot.kind.label(),
+                    depot.id,
+                    depot.position.x,
+                    depot.position.y,
+                    depot.stored(ResourceType::Mineraux)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" │ ")
+    }
+
+    /// Changements de rôle décidés par le coordinateur depuis le début
+    pub fn role_changes(&self) -> u32 {
+        self.robots.iter().map(|robot| robot.role_changes).sum()
+    }
+
+    /// Ressource collectée : transportée par les robots + livrée à la station
+    pub fn collected_total(&self, resource_type: ResourceType) -> u32 {
+        let carried: u32 = self.robots.iter()
+            .map(|robot| robot.carried(resource_type))
+            .sum();
+        carried + self.delivered(resource_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainType;
+    use crate::simulation::benchmark::bench_engine;
+    use crate::{RobotBehavior, Topology};
 
-        let mut total_energy_collected = 0;
-        let mut total_minerals_collected = 0;
-        let mut total_scientific_data = 0;
+    // État comparable entre deux moteurs : carte, robots, dépôts et journal
+    fn fingerprint(engine: &SimulationEngine) -> String {
+        serde_json::to_string(&engine.snapshot()).unwrap()
+    }
 
-        for robot in &self.robots {
-            total_energy_collected += robot.inventory.get(&crate::ResourceType::Energie).unwrap_or(&0);
-            total_minerals_collected += robot.inventory.get(&crate::ResourceType::Mineraux).unwrap_or(&0);
-            total_scientific_data += robot.inventory.get(&crate::ResourceType::LieuxInteret).unwrap_or(&0);
+    // Un explorateur en (0,0) tente de passer le bord ouest, puis le bord nord
+    fn explore_edges(topology: Topology) -> SimulationEngine {
+        let map = Grid::new(5, 4, Cell::new(TerrainType::Plaine)).with_topology(topology);
+        let robot = Robot::new(0, Position::new(0, 0), RobotBehavior::Explorateur);
+        let mut engine = SimulationEngine::new(map, vec![robot]);
+
+        engine.apply_actions(&[RobotAction::Move(Position::new(4, 0))]).unwrap();
+        engine.apply_actions(&[RobotAction::Move(Position::new(4, 3))]).unwrap();
+        engine
+    }
+
+    #[test]
+    fn bounded_map_rejects_moves_across_edges() {
+        let engine = explore_edges(Topology::Bornee);
+
+        assert_eq!(engine.robots[0].position, Position::new(0, 0));
+        assert_eq!(engine.explored_cell_count(), 0);
+        assert_eq!(engine.exploration_percentage(), 0.0);
+    }
+
+    #[test]
+    fn cylinder_explores_across_the_east_west_edge_only() {
+        let engine = explore_edges(Topology::Cylindre);
+
+        assert_eq!(engine.robots[0].position, Position::new(4, 0));
+        assert_eq!(engine.explored_cell_count(), 1);
+        assert_eq!(engine.exploration_percentage(), 5.0);
+    }
+
+    #[test]
+    fn torus_explores_across_both_edges() {
+        let engine = explore_edges(Topology::Tore);
+
+        assert_eq!(engine.robots[0].position, Position::new(4, 3));
+        assert_eq!(engine.explored_cell_count(), 2);
+        assert_eq!(engine.exploration_percentage(), 10.0);
+    }
+
+    #[test]
+    fn finished_site_and_drained_battery_are_reported() {
+        let site = Position::new(2, 2);
+        let mut map = Grid::new(6, 5, Cell::new(TerrainType::Plaine));
+        map[site].add_resource(ResourceType::LieuxInteret, 1);
+        let mut scientist = Robot::new(4, site, RobotBehavior::Scientifique);
+        scientist.energy = ANALYZE_ENERGY_COST;
+        let mut engine = SimulationEngine::new(map, vec![scientist]);
+
+        engine.command(Command::TakeControl(4)).unwrap();
+        engine.command(Command::Order { id: 4, action: RobotAction::Analyze }).unwrap();
+        engine.execute_turn();
+
+        let kinds: Vec<EventKind> = engine.events.iter().map(|event| event.kind).collect();
+        assert!(kinds.contains(&EventKind::Science));
+        assert_eq!(kinds.last(), Some(&EventKind::Perte));
+    }
+
+    #[test]
+    fn parallel_decisions_match_the_sequential_engine() {
+        let mut sequential = bench_engine(60, 2 * PARALLEL_DECIDE_THRESHOLD);
+        sequential.parallel = false;
+        let mut parallel = bench_engine(60, 2 * PARALLEL_DECIDE_THRESHOLD);
+
+        for _ in 0..100 {
+            sequential.step();
+            parallel.step();
         }
 
-        println!("⚡ Énergie collectée: {}", total_energy_collected);
-        println!("🪨 Mineraux collectés: {}", total_minerals_collected);
-        println!("🔬 Données scientifiques: {}", total_scientific_data);
-        println!("🔄 Tours exécutés: {}", self.turn);
+        assert_eq!(fingerprint(&sequential), fingerprint(&parallel));
+    }
 
-        let explored_cells = self.map.iter()
-            .flat_map(|row| row.iter())
-            .filter(|cell| cell.explored)
-            .count();
+    // Collecteur plein (mineraux et énergie) prêt à rentrer
+    fn loaded_collector(id: usize, pos: Position) -> Robot {
+        let mut robot = Robot::new(id, pos, RobotBehavior::Collecteur);
+        robot.load(ResourceType::Mineraux, 1000);
+        robot.load(ResourceType::Energie, 1000);
+        robot
+    }
+
+    // Résolution seule, sans fin de tour (ni coordinateur ni environnement)
+    fn run_decisions(engine: &mut SimulationEngine, turns: usize) {
+        for _ in 0..turns {
+            let actions = engine.decide_actions();
+            engine.apply_actions(&actions).unwrap();
+        }
+    }
+
+    #[test]
+    fn full_collectors_unload_at_the_nearest_reachable_depot() {
+        // Un mur en x=10 sépare le collecteur de la station, pourtant à deux cases
+        let mut map = Grid::new(20, 10, Cell::new(TerrainType::Plaine));
+        for y in 0..10 {
+            map[Position::new(10, y)].terrain = TerrainType::Obstacle;
+        }
+        let outpost = Position::new(18, 8);
+        let mut engine = SimulationEngine::builder()
+            .map(map)
+            .depot(0, DepotKind::Station, Position::new(9, 1))
+            .depot(0, DepotKind::Avantposte, outpost)
+            .robot(loaded_collector(1, Position::new(11, 1)))
+            .build()
+            .unwrap();
+        let minerals = engine.robots[0].carried(ResourceType::Mineraux);
+
+        // Sept pas en diagonale, le long du mur plutôt qu'à travers
+        run_decisions(&mut engine, 7);
+        assert_eq!(engine.robots[0].position, outpost);
+        assert!(engine.robots[0].inventory.is_empty());
+        // Chaque dépôt garde son propre stock
+        assert_eq!(engine.depots[1].stored(ResourceType::Mineraux), minerals);
+        assert_eq!(engine.depots[0].stored(ResourceType::Mineraux), 0);
+        assert_eq!(engine.delivered_by(0, ResourceType::Mineraux), minerals);
+    }
 
-        let total_cells = self.width * self.height;
-        let exploration_percentage = (explored_cells as f32 / total_cells as f32) * 100.0;
+    #[test]
+    fn outposts_cost_minerals_and_respect_spacing_and_limit() {
+        let map = Grid::new(60, 60, Cell::new(TerrainType::Plaine));
+        let mut short = loaded_collector(3, Position::new(30, 50));
+        short.unload(ResourceType::Mineraux, short.carried(ResourceType::Mineraux) - (OUTPOST_MINERAL_COST - 1));
+        let robots = vec![
+            loaded_collector(1, Position::new(30, 30)),
+            loaded_collector(2, Position::new(30 + OUTPOST_MIN_SPACING - 1, 30)),
+            short,
+            loaded_collector(4, Position::new(50, 50)),
+        ];
+        let mut engine = SimulationEngine::builder()
+            .map(map)
+            .depot(0, DepotKind::Station, Position::new(1, 1))
+            .robots(robots)
+            .build()
+            .unwrap();
+        assert_eq!(engine.decide_actions()[0], RobotAction::BuildOutpost);
+        let carried: Vec<u32> = engine.robots.iter().map(|robot| robot.carried(ResourceType::Mineraux)).collect();
 
-        println!("🗺️  Exploration: {:.1}% ({}/{})", exploration_percentage, explored_cells, total_cells);
+        let build = vec![RobotAction::BuildOutpost, RobotAction::BuildOutpost, RobotAction::BuildOutpost, RobotAction::Wait];
+        engine.apply_actions(&build).unwrap();
+        assert_eq!(engine.depots.len(), 2);
+        assert_eq!((engine.depots[1].kind, engine.depots[1].position), (DepotKind::Avantposte, Position::new(30, 30)));
+        // Le reste du chargement est aussitôt déposé dans l'avant-poste
+        assert_eq!(engine.depots[1].stored(ResourceType::Mineraux), carried[0] - OUTPOST_MINERAL_COST);
+        assert_eq!(engine.robots[0].carried(ResourceType::Mineraux), 0);
+        // Trop près du nouvel avant-poste, ou pas assez de mineraux : rien ne se construit
+        assert_eq!(engine.robots[1].carried(ResourceType::Mineraux), carried[1]);
+        assert_eq!(engine.robots[2].carried(ResourceType::Mineraux), carried[2]);
 
-        println!("\n{}", "🎯 Merci d'avoir utilisé EREEA !".bright_green().bold());
+        // Plafond d'avant-postes atteint : le collecteur rentre décharger
+        for i in 1..MAX_OUTPOSTS {
+            let id = engine.depots.len();
+            engine.depots.push(Depot::new(id, 0, DepotKind::Avantposte, Position::new(1, 15 * i)));
+        }
+        assert_ne!(engine.decide_actions()[3], RobotAction::BuildOutpost);
+        engine.apply_actions(&[RobotAction::Wait, RobotAction::Wait, RobotAction::Wait, RobotAction::BuildOutpost]).unwrap();
+        assert_eq!(engine.depots.len(), 1 + MAX_OUTPOSTS);
+        assert_eq!(engine.robots[3].carried(ResourceType::Mineraux), carried[3]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn only_stations_repair_modules() {
+        let map = Grid::new(10, 5, Cell::new(TerrainType::Plaine));
+        let (station, outpost) = (Position::new(1, 1), Position::new(8, 3));
+        let robots = [station, outpost].into_iter().enumerate().map(|(id, pos)| {
+            let mut robot = Robot::new(id, pos, RobotBehavior::Explorateur);
+            robot.wear(RobotModule::Deplacement, 40);
+            robot
+        });
+        let mut engine = SimulationEngine::builder()
+            .map(map)
+            .depot(0, DepotKind::Station, station)
+            .depot(0, DepotKind::Avantposte, outpost)
+            .robots(robots)
+            .build()
+            .unwrap();
+
+        engine.apply_actions(&[RobotAction::Wait, RobotAction::Wait]).unwrap();
+        assert!(engine.robots[0].condition(RobotModule::Deplacement).health > 60);
+        assert_eq!(engine.robots[1].condition(RobotModule::Deplacement).health, 60);
+    }
+}
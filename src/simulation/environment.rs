@@ -0,0 +1,389 @@
+// src/simulation/environment.rs - Environnement dynamique : tempêtes, météorites, jour et nuit
+use crate::{Cell, Position, ResourceType, TerrainType};
+use crate::{Direction, Grid};
+use crate::robot::{Robot, METEOR_WEAR, STORM_WEAR};
+use crate::simulation::events::{EventKind, EventLog};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Énergie perdue à chaque tour passé dans une tempête
+pub const STORM_DAMAGE: u32 = 1;
+/// Tours entre deux déplacements d'une tempête
+pub const STORM_DRIFT_INTERVAL: usize = 2;
+/// Énergie perdue par un robot touché par une météorite
+pub const METEOR_DAMAGE: u32 = 20;
+/// Probabilité qu'une case voisine de l'impact devienne un obstacle (éjecta)
+pub const EJECTA_CHANCE: f64 = 0.35;
+/// Profondeur creusée par un impact
+pub const IMPACT_DEPTH: u8 = 15;
+/// La nuit, le chauffage coûte 1 énergie tous les N tours (sauf sur un dépôt)
+pub const COLD_DRAIN_INTERVAL: usize = 10;
+
+/// Réglages de l'environnement ; tout à zéro, la planète reste statique
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnvironmentConfig {
+    /// Graine des événements ; celle de la carte si absente
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Durée d'un jour complet en tours (0 = toujours jour)
+    #[serde(default)]
+    pub day_length: usize,
+    /// Probabilité par tour qu'une tempête se forme
+    #[serde(default)]
+    pub storm_chance: f64,
+    #[serde(default = "default_storm_radius")]
+    pub storm_radius: usize,
+    #[serde(default = "default_storm_duration")]
+    pub storm_duration: usize,
+    /// Probabilité par tour d'un impact de météorite
+    #[serde(default)]
+    pub meteor_chance: f64,
+}
+
+impl Default for EnvironmentConfig {
+    fn default() -> Self {
+        EnvironmentConfig {
+            seed: None,
+            day_length: 0,
+            storm_chance: 0.0,
+            storm_radius: default_storm_radius(),
+            storm_duration: default_storm_duration(),
+            meteor_chance: 0.0,
+        }
+    }
+}
+
+fn default_storm_radius() -> usize {
+    3
+}
+
+fn default_storm_duration() -> usize {
+    40
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DustStorm {
+    pub id: usize,
+    pub center: Position,
+    pub radius: usize,
+    pub drift: Direction,
+    pub remaining_turns: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Environment {
+    pub config: EnvironmentConfig,
+    pub seed: u64,
+    pub storms: Vec<DustStorm>,
+    pub storms_formed: usize,
+    pub meteor_impacts: usize,
+}
+
+impl Environment {
+    pub fn new(config: EnvironmentConfig, fallback_seed: u64) -> Self {
+        let seed = config.seed.unwrap_or(fallback_seed);
+        Environment { config, seed, ..Environment::default() }
+    }
+
+    pub fn is_night(&self, turn: usize) -> bool {
+        let day = self.config.day_length;
+        day > 0 && turn % day >= day / 2
+    }
+
+    pub fn in_storm(&self, map: &Grid<Cell>, pos: Position) -> bool {
+        self.storms
+            .iter()
+            .any(|storm| map.chebyshev_distance(storm.center, pos) <= storm.radius)
+    }
+
+    /// Les panneaux solaires ne rechargent ni la nuit ni sous la poussière
+    pub fn solar_available(&self, map: &Grid<Cell>, turn: usize, pos: Position) -> bool {
+        !self.is_night(turn) && !self.in_storm(map, pos)
+    }
+
+    /// Fait évoluer l'environnement à la fin du tour `turn`
+    pub fn update(
+        &mut self,
+        turn: usize,
+        map: &mut Grid<Cell>,
+        robots: &mut [Robot],
+        depots: &[Position],
+        events: &mut EventLog,
+    ) {
+        // Un générateur par tour : l'état se rejoue à l'identique depuis n'importe quel tour
+        let mut rng = StdRng::seed_from_u64(self.seed ^ (turn as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+        self.update_day_cycle(turn, robots, depots, events);
+        self.update_storms(turn, map, robots, events);
+
+        if self.config.storm_chance > 0.0 && rng.gen_bool(self.config.storm_chance.min(1.0)) {
+            self.spawn_storm(turn, map, &mut rng, events);
+        }
+        if self.config.meteor_chance > 0.0 && rng.gen_bool(self.config.meteor_chance.min(1.0)) {
+            self.meteor_impact(turn, map, robots, depots, &mut rng, events);
+        }
+    }
+
+    fn update_day_cycle(&self, turn: usize, robots: &mut [Robot], depots: &[Position], events: &mut EventLog) {
+        if self.config.day_length == 0 {
+            return;
+        }
+
+        match (self.is_night(turn), self.is_night(turn + 1)) {
+            (false, true) => events.push(turn, EventKind::Cycle, "La nuit tombe : plus de recharge solaire"),
+            (true, false) => events.push(turn, EventKind::Cycle, "Lever du jour"),
+            _ => {}
+        }
+
+        // Froid nocturne : les robots hors des dépôts puisent dans leurs batteries
+        if self.is_night(turn) && turn.is_multiple_of(COLD_DRAIN_INTERVAL) {
+            for robot in robots.iter_mut().filter(|r| r.is_operational() && !depots.contains(&r.position)) {
+                robot.energy = robot.energy.saturating_sub(1);
+                if !robot.is_operational() {
+                    events.push(turn, EventKind::Perte, format!("Robot {} vidé par le froid nocturne", robot.id));
+                }
+            }
+        }
+    }
+
+    fn update_storms(&mut self, turn: usize, map: &Grid<Cell>, robots: &mut [Robot], events: &mut EventLog) {
+        for storm in &self.storms {
+            for robot in robots.iter_mut().filter(|r| r.is_operational()) {
+                if map.chebyshev_distance(storm.center, robot.position) <= storm.radius {
+                    robot.energy = robot.energy.saturating_sub(STORM_DAMAGE);
+                    // La poussière use tous les modules exposés
+                    for module in robot.modules.clone() {
+                        if robot.wear(module, STORM_WEAR) {
+                            events.push(turn, EventKind::Robot, format!("Robot {} : module {:?} grippé par la poussière", robot.id, module));
+                        }
+                    }
+                    if !robot.is_operational() {
+                        events.push(turn, EventKind::Perte, format!("Robot {} mis hors service par la tempête {}", robot.id, storm.id));
+                    }
+                }
+            }
+        }
+
+        // Dérive ; une tempête qui sort d'une carte bornée ou s'essouffle se dissipe
+        self.storms.retain_mut(|storm| {
+            storm.remaining_turns = storm.remaining_turns.saturating_sub(1);
+            if turn.is_multiple_of(STORM_DRIFT_INTERVAL) {
+                match map.neighbor(storm.center, storm.drift) {
+                    Some(center) => storm.center = center,
+                    None => storm.remaining_turns = 0,
+                }
+            }
+
+            if storm.remaining_turns == 0 {
+                events.push(turn, EventKind::Tempete, format!("Tempête {} dissipée", storm.id));
+            }
+            storm.remaining_turns > 0
+        });
+    }
+
+    fn spawn_storm(&mut self, turn: usize, map: &Grid<Cell>, rng: &mut StdRng, events: &mut EventLog) {
+        let center = random_position(map, rng);
+        let drift = Direction::TOUTES[rng.gen_range(0..Direction::TOUTES.len())];
+        self.storms_formed += 1;
+
+        let storm = DustStorm {
+            id: self.storms_formed,
+            center,
+            radius: self.config.storm_radius,
+            drift,
+            remaining_turns: self.config.storm_duration.max(1),
+        };
+        events.push(
+            turn,
+            EventKind::Tempete,
+            format!("Tempête de poussière {} formée en ({},{}), vers {:?}", storm.id, center.x, center.y, drift),
+        );
+        self.storms.push(storm);
+    }
+
+    fn meteor_impact(
+        &mut self,
+        turn: usize,
+        map: &mut Grid<Cell>,
+        robots: &mut [Robot],
+        depots: &[Position],
+        rng: &mut StdRng,
+        events: &mut EventLog,
+    ) {
+        let target = random_position(map, rng);
+        // Les dépôts sont protégés : pas d'impact à leur contact
+        if depots.iter().any(|depot| map.chebyshev_distance(target, *depot) <= 1) {
+            return;
+        }
+        self.meteor_impacts += 1;
+
+        // Cratère frais au point d'impact, avec un site à étudier
+        let crater = &mut map[target];
+        crater.terrain = TerrainType::Cratere;
+        crater.elevation = crater.elevation.saturating_sub(IMPACT_DEPTH);
+        crater.resources = Default::default();
+        crater.add_resource(ResourceType::LieuxInteret, 1);
+        events.push(turn, EventKind::Meteorite, format!("Impact de météorite en ({},{}) : nouveau cratère", target.x, target.y));
+
+        // Éjecta autour, jamais sous un robot ni sur un dépôt
+        let ring: Vec<Position> = map.neighbors8(target).collect();
+        for pos in ring {
+            let occupied = robots.iter().any(|robot| robot.position == pos);
+            if !occupied && !depots.contains(&pos) && rng.gen_bool(EJECTA_CHANCE) {
+                map[pos].terrain = TerrainType::Obstacle;
+            }
+        }
+
+        for robot in robots.iter_mut().filter(|r| r.is_operational()) {
+            if map.chebyshev_distance(target, robot.position) <= 1 {
+                robot.energy = robot.energy.saturating_sub(METEOR_DAMAGE);
+                if robot.is_operational() {
+                    events.push(turn, EventKind::Robot, format!("Robot {} touché par la météorite ({} énergie restante)", robot.id, robot.energy));
+                } else {
+                    events.push(turn, EventKind::Perte, format!("Robot {} mis hors service par la météorite", robot.id));
+                }
+
+                // Un module pris au hasard encaisse le choc
+                if !robot.modules.is_empty() {
+                    let module = robot.modules[rng.gen_range(0..robot.modules.len())];
+                    if robot.wear(module, METEOR_WEAR) {
+                        events.push(turn, EventKind::Robot, format!("Robot {} : module {:?} détruit par l'impact", robot.id, module));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn random_position(map: &Grid<Cell>, rng: &mut StdRng) -> Position {
+    Position::new(rng.gen_range(0..map.width()), rng.gen_range(0..map.height()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RobotBehavior;
+
+    fn plain(width: usize, height: usize) -> Grid<Cell> {
+        let mut cell = Cell::new(TerrainType::Plaine);
+        cell.elevation = 50;
+        Grid::new(width, height, cell)
+    }
+
+    fn robot_at(id: usize, x: usize, y: usize) -> Robot {
+        Robot::new(id, Position::new(x, y), RobotBehavior::Explorateur)
+    }
+
+    fn storm(center: Position, drift: Direction) -> DustStorm {
+        DustStorm { id: 1, center, radius: 1, drift, remaining_turns: 10 }
+    }
+
+    #[test]
+    fn storms_drift_and_only_hurt_inside_their_radius() {
+        let map = plain(10, 10);
+        let mut environment = Environment::default();
+        environment.storms.push(storm(Position::new(5, 5), Direction::Est));
+        let mut robots = vec![robot_at(1, 6, 5), robot_at(2, 8, 5)];
+        let mut events = EventLog::default();
+
+        // Dégâts au centre de départ, puis dérive d'une case tous les STORM_DRIFT_INTERVAL tours
+        environment.update(0, &mut map.clone(), &mut robots, &[], &mut events);
+        assert_eq!(robots[0].energy, 100 - STORM_DAMAGE);
+        assert_eq!(robots[1].energy, 100);
+        assert_eq!(environment.storms[0].center, Position::new(6, 5));
+        environment.update(1, &mut map.clone(), &mut robots, &[], &mut events);
+        assert_eq!(environment.storms[0].center, Position::new(6, 5));
+        assert_eq!(robots[1].energy, 100);
+
+        // Le bord d'une carte bornée dissipe la tempête
+        environment.storms[0].center = Position::new(9, 5);
+        environment.update(2, &mut map.clone(), &mut robots, &[], &mut events);
+        assert!(environment.storms.is_empty());
+        assert!(events.iter().any(|event| event.kind == EventKind::Tempete));
+    }
+
+    #[test]
+    fn meteors_dig_a_fresh_crater_and_scatter_ejecta() {
+        let mut ejecta_seen = false;
+        for seed in 0..20 {
+            let mut map = plain(6, 6);
+            // Robots en damier : toute case d'impact en a au contact, et les éjecta tombent entre eux
+            let mut robots: Vec<Robot> = map
+                .enumerate()
+                .map(|(pos, _)| pos)
+                .filter(|pos| (pos.x + pos.y).is_multiple_of(2))
+                .enumerate()
+                .map(|(id, pos)| robot_at(id, pos.x, pos.y))
+                .collect();
+            let config = EnvironmentConfig { meteor_chance: 1.0, ..EnvironmentConfig::default() };
+            let mut environment = Environment::new(config, seed);
+            environment.update(0, &mut map, &mut robots, &[], &mut EventLog::default());
+            assert_eq!(environment.meteor_impacts, 1);
+
+            let terrain_at = |terrain: TerrainType| -> Vec<Position> {
+                map.enumerate().filter(|(_, cell)| cell.terrain == terrain).map(|(pos, _)| pos).collect()
+            };
+            let target = terrain_at(TerrainType::Cratere)[0];
+            assert_eq!(map[target].elevation, 50 - IMPACT_DEPTH);
+            assert_eq!(map[target].resources.amount(ResourceType::LieuxInteret), 1);
+
+            for pos in terrain_at(TerrainType::Obstacle) {
+                assert_eq!(map.chebyshev_distance(pos, target), 1);
+                assert!(robots.iter().all(|robot| robot.position != pos));
+                ejecta_seen = true;
+            }
+            for robot in &robots {
+                let hit = map.chebyshev_distance(robot.position, target) <= 1;
+                assert_eq!(robot.energy, if hit { 100 - METEOR_DAMAGE } else { 100 });
+            }
+        }
+        assert!(ejecta_seen);
+    }
+
+    #[test]
+    fn night_cold_drains_robots_away_from_depots() {
+        let config = EnvironmentConfig { day_length: 20, ..EnvironmentConfig::default() };
+        let mut environment = Environment::new(config, 0);
+        let mut map = plain(10, 10);
+        let mut robots = vec![robot_at(1, 2, 2), robot_at(2, 1, 1)];
+        let depots = [Position::new(1, 1)];
+        let mut events = EventLog::default();
+
+        // Nuit aux tours 10-19 et 30-39 : le froid frappe aux tours 10 et 30
+        for turn in 0..=30 {
+            environment.update(turn, &mut map, &mut robots, &depots, &mut events);
+            let expected = 100 - (turn / 10).div_ceil(2) as u32;
+            assert_eq!(robots[0].energy, expected, "tour {}", turn);
+        }
+        assert_eq!(robots[1].energy, 100);
+        assert!(environment.is_night(10) && !environment.is_night(20));
+        assert_eq!(events.iter().filter(|event| event.kind == EventKind::Cycle).count(), 3);
+    }
+
+    #[test]
+    fn same_seed_replays_the_same_events() {
+        let run = |seed: u64| {
+            let config = EnvironmentConfig {
+                seed: Some(seed),
+                day_length: 20,
+                storm_chance: 0.2,
+                meteor_chance: 0.1,
+                ..EnvironmentConfig::default()
+            };
+            let mut environment = Environment::new(config, 0);
+            let mut map = plain(20, 20);
+            let mut robots: Vec<Robot> = (0..5).map(|id| robot_at(id, id * 4, 10)).collect();
+            let mut events = EventLog::default();
+            for turn in 0..100 {
+                environment.update(turn, &mut map, &mut robots, &[], &mut events);
+            }
+            let messages: Vec<String> = events.iter().map(|event| event.message.clone()).collect();
+            (messages, serde_json::to_string(&map).unwrap())
+        };
+
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42).0, run(43).0);
+    }
+}
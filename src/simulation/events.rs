@@ -0,0 +1,90 @@
+// src/simulation/events.rs - Journal des événements de la simulation
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Nombre d'événements conservés (les plus anciens sont oubliés)
+pub const EVENT_LOG_CAPACITY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventKind {
+    Tempete,
+    Meteorite,
+    Cycle,
+    Robot,
+    /// Robot mis hors service (batterie vide)
+    Perte,
+    /// Site scientifique analysé jusqu'au bout
+    Science,
+    Mission,
+    /// Intervention extérieure (code appelant, téléopération)
+    Commande,
+}
+
+impl EventKind {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            EventKind::Tempete => "🌪️",
+            EventKind::Meteorite => "☄️",
+            EventKind::Cycle => "🌗",
+            EventKind::Robot => "🤖",
+            EventKind::Perte => "💀",
+            EventKind::Science => "🔬",
+            EventKind::Mission => "🎯",
+            EventKind::Commande => "🕹️",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationEvent {
+    pub turn: usize,
+    pub kind: EventKind,
+    pub message: String,
+}
+
+impl SimulationEvent {
+    pub fn describe(&self) -> String {
+        format!("[T{:>4}] {} {}", self.turn, self.kind.icon(), self.message)
+    }
+}
+
+/// Journal borné, du plus ancien au plus récent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventLog {
+    entries: VecDeque<SimulationEvent>,
+    total: usize,
+    // Événements pas encore remis aux observateurs du moteur
+    #[serde(skip)]
+    fresh: Vec<SimulationEvent>,
+}
+
+impl EventLog {
+    pub fn push(&mut self, turn: usize, kind: EventKind, message: impl Into<String>) {
+        if self.entries.len() == EVENT_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        let event = SimulationEvent { turn, kind, message: message.into() };
+        self.fresh.push(event.clone());
+        self.entries.push_back(event);
+        self.total += 1;
+    }
+
+    /// Événements émis depuis le dernier appel
+    pub fn take_fresh(&mut self) -> Vec<SimulationEvent> {
+        std::mem::take(&mut self.fresh)
+    }
+
+    /// Les `count` derniers événements, du plus ancien au plus récent
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &SimulationEvent> {
+        self.entries.iter().skip(self.entries.len().saturating_sub(count))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SimulationEvent> {
+        self.entries.iter()
+    }
+
+    /// Nombre d'événements émis depuis le début, y compris ceux oubliés
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
@@ -0,0 +1,257 @@
+// src/simulation/heatmap.rs - Cartes de chaleur : traces des robots case par case et couches de diagnostic
+use crate::{Cell, Position};
+use crate::Grid;
+use crate::RobotModule;
+use crate::map::line_of_sight;
+use crate::robot::Robot;
+use crate::simulation::engine::SimulationEngine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Début du nom des fichiers exportés, suivi du nom de la couche : `ereea_heatmap_passages.csv`
+pub const HEATMAP_FILE_PREFIX: &str = "ereea_heatmap_";
+
+/// Traces laissées par les robots, cumulées depuis le début de la partie
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Traces {
+    /// Arrivées d'un robot sur la case
+    pub visits: Grid<u32>,
+    /// Énergie dépensée par les robots pendant les tours qu'ils finissent sur la case
+    pub energy_spent: Grid<u32>,
+    /// Dernier tour où un robot en service s'y trouvait
+    pub last_seen: Grid<Option<usize>>,
+}
+
+impl Traces {
+    pub fn new(width: usize, height: usize) -> Self {
+        Traces {
+            visits: Grid::new(width, height, 0),
+            energy_spent: Grid::new(width, height, 0),
+            last_seen: Grid::new(width, height, None),
+        }
+    }
+
+    /// Traces aux dimensions de la carte (un replay ancien peut ne pas en avoir)
+    pub fn fits(&self, map: &Grid<Cell>) -> bool {
+        self.visits.width() == map.width() && self.visits.height() == map.height()
+    }
+
+    /// Bilan d'un tour : `before` donne position et énergie de chaque robot avant ses actions
+    pub fn record(&mut self, turn: usize, before: &[(Position, u32)], robots: &[Robot]) {
+        for (&(from, energy), robot) in before.iter().zip(robots) {
+            let pos = robot.position;
+            if !self.visits.contains(pos) {
+                continue;
+            }
+            if pos != from {
+                self.visits[pos] += 1;
+            }
+            self.energy_spent[pos] += energy.saturating_sub(robot.energy);
+            if robot.is_operational() {
+                self.last_seen[pos] = Some(turn);
+            }
+        }
+    }
+}
+
+/// Couche de diagnostic superposée à la carte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatLayer {
+    /// Fréquence de passage des robots
+    Passages,
+    /// Énergie dépensée sur place
+    Energie,
+    /// Ressources encore présentes
+    Ressources,
+    /// Tours écoulés depuis le dernier passage d'un robot en service
+    DernierPassage,
+    /// Robots joignables par radio depuis la case
+    Couverture,
+}
+
+impl HeatLayer {
+    pub const ALL: [HeatLayer; 5] =
+        [HeatLayer::Passages, HeatLayer::Energie, HeatLayer::Ressources, HeatLayer::DernierPassage, HeatLayer::Couverture];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeatLayer::Passages => "Passages des robots",
+            HeatLayer::Energie => "Énergie dépensée",
+            HeatLayer::Ressources => "Ressources restantes",
+            HeatLayer::DernierPassage => "Dernier passage (tours écoulés)",
+            HeatLayer::Couverture => "Couverture radio (robots à portée)",
+        }
+    }
+
+    /// Nom court, utilisé pour les fichiers exportés
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeatLayer::Passages => "passages",
+            HeatLayer::Energie => "energie",
+            HeatLayer::Ressources => "ressources",
+            HeatLayer::DernierPassage => "dernier_passage",
+            HeatLayer::Couverture => "couverture",
+        }
+    }
+
+    /// Couche suivante dans `ALL`, `None` après la dernière (retour à la carte seule)
+    pub fn next(layer: Option<HeatLayer>) -> Option<HeatLayer> {
+        match layer {
+            None => Some(HeatLayer::ALL[0]),
+            Some(layer) => HeatLayer::ALL.iter().position(|&l| l == layer).and_then(|i| HeatLayer::ALL.get(i + 1)).copied(),
+        }
+    }
+}
+
+/// Valeurs d'une couche sur toute la carte ; `None` pour une case sans donnée (jamais visitée)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatMap {
+    pub layer: HeatLayer,
+    pub values: Grid<Option<u32>>,
+    /// Plus grande valeur de la couche (0 si elle est vide)
+    pub max: u32,
+}
+
+impl HeatMap {
+    pub fn compute(engine: &SimulationEngine, layer: HeatLayer) -> HeatMap {
+        let map = &engine.map;
+        let traces = &engine.traces;
+        let values = match layer {
+            HeatLayer::Passages => Grid::from_fn(map.width(), map.height(), |pos| traces.visits.get(pos).copied()),
+            HeatLayer::Energie => Grid::from_fn(map.width(), map.height(), |pos| traces.energy_spent.get(pos).copied()),
+            HeatLayer::Ressources => {
+                Grid::from_fn(map.width(), map.height(), |pos| Some(map[pos].resources.iter().map(|(_, amount)| amount).sum()))
+            },
+            HeatLayer::DernierPassage => Grid::from_fn(map.width(), map.height(), |pos| {
+                traces.last_seen.get(pos).copied().flatten().map(|seen| engine.turn.saturating_sub(seen) as u32)
+            }),
+            HeatLayer::Couverture => radio_coverage(engine),
+        };
+        let max = values.iter().flatten().copied().max().unwrap_or(0);
+        HeatMap { layer, values, max }
+    }
+
+    /// Une ligne par rangée de la carte, valeurs séparées par des virgules, champ vide sans donnée
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for row in self.values.rows() {
+            let fields: Vec<String> = row.iter().map(|value| value.map(|v| v.to_string()).unwrap_or_default()).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+}
+
+/// Exporte des couches en CSV dans `dir` et rend les chemins écrits
+pub fn export_heat_maps(engine: &SimulationEngine, layers: &[HeatLayer], dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for &layer in layers {
+        let path = dir.join(format!("{}{}.csv", HEATMAP_FILE_PREFIX, layer.name()));
+        HeatMap::compute(engine, layer).save(&path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+// Pour chaque case, robots en service dont la radio porte jusqu'à elle (portée divisée par deux
+// dans une tempête, ligne de vue exigée, comme pour `SimulationEngine::can_communicate`)
+fn radio_coverage(engine: &SimulationEngine) -> Grid<Option<u32>> {
+    let map = &engine.map;
+    let mut coverage = Grid::new(map.width(), map.height(), Some(0));
+    let topology = map.topology();
+    for robot in &engine.robots {
+        if !robot.is_operational() || !robot.module_working(RobotModule::Communication) {
+            continue;
+        }
+        let range = robot.communication_range;
+        let storm = engine.environment.in_storm(map, robot.position);
+        for dy in span(range, map.height(), topology.wraps_y()) {
+            for dx in span(range, map.width(), topology.wraps_x()) {
+                let Some(pos) = map.offset(robot.position, (dx, dy)) else { continue };
+                let reach = if storm || engine.environment.in_storm(map, pos) { range / 2 } else { range };
+                if map.chebyshev_distance(robot.position, pos) <= reach
+                    && line_of_sight(map, robot.position, pos)
+                    && let Some(Some(count)) = coverage.get_mut(pos)
+                {
+                    *count += 1;
+                }
+            }
+        }
+    }
+    coverage
+}
+
+// Décalages à parcourir sur un axe ; une carte bouclée plus étroite que la portée n'est vue qu'une fois
+fn span(range: usize, len: usize, wraps: bool) -> std::ops::RangeInclusive<isize> {
+    if wraps && 2 * range + 1 > len {
+        0..=len as isize - 1
+    } else {
+        -(range as isize)..=range as isize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RobotBehavior, TerrainType};
+    use crate::robot::RobotAction;
+    use crate::simulation::control::Command;
+
+    fn open_engine() -> SimulationEngine {
+        let map = Grid::new(20, 8, Cell::new(TerrainType::Plaine));
+        let robot = Robot::new(1, Position::new(2, 2), RobotBehavior::Explorateur);
+        SimulationEngine::new(map, vec![robot])
+    }
+
+    #[test]
+    fn moves_leave_visits_energy_and_fresh_knowledge() {
+        let mut engine = open_engine();
+        engine.command(Command::TakeControl(1)).unwrap();
+        for x in [3, 4, 3] {
+            engine.command(Command::Order { id: 1, action: RobotAction::Move(Position::new(x, 2)) }).unwrap();
+            engine.step();
+        }
+
+        let visits = HeatMap::compute(&engine, HeatLayer::Passages);
+        assert_eq!(visits.values[Position::new(3, 2)], Some(2));
+        assert_eq!(visits.values[Position::new(2, 2)], Some(0));
+        assert_eq!(visits.max, 2);
+        assert!(HeatMap::compute(&engine, HeatLayer::Energie).values[Position::new(4, 2)].is_some_and(|spent| spent > 0));
+
+        let age = HeatMap::compute(&engine, HeatLayer::DernierPassage);
+        assert_eq!(age.values[Position::new(3, 2)], Some(1));
+        assert_eq!(age.values[Position::new(4, 2)], Some(2));
+        // Case de départ quittée au premier tour, avant tout relevé
+        assert_eq!(age.values[Position::new(2, 2)], None);
+
+        // Une rangée par ligne de CSV, une colonne par case
+        let csv = age.to_csv();
+        assert_eq!(csv.lines().count(), 8);
+        let row: Vec<&str> = csv.lines().nth(2).unwrap().split(',').collect();
+        assert_eq!(row.len(), 20);
+        assert_eq!(row[..6], ["", "", "", "1", "2", ""]);
+    }
+
+    #[test]
+    fn coverage_counts_robots_within_radio_range() {
+        let mut engine = open_engine();
+        engine.robots.push(Robot::new(2, Position::new(8, 2), RobotBehavior::Explorateur));
+        let range = engine.robots[0].communication_range;
+
+        let coverage = HeatMap::compute(&engine, HeatLayer::Couverture);
+        assert_eq!(coverage.values[Position::new(5, 2)], Some(2));
+        assert_eq!(coverage.values[Position::new(2 + range + 1, 7)], Some(1));
+        assert_eq!(coverage.values[Position::new(19, 7)], Some(0));
+        assert_eq!(coverage.max, 2);
+
+        assert_eq!(HeatLayer::next(None), Some(HeatLayer::Passages));
+        assert_eq!(HeatLayer::next(Some(HeatLayer::Couverture)), None);
+    }
+}
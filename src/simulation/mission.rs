@@ -0,0 +1,268 @@
+// src/simulation/mission.rs - Objectifs de mission et conditions de fin
+use crate::ResourceType;
+use crate::simulation::engine::{SimulationEngine, DEFAULT_MAX_TURNS};
+use serde::{Deserialize, Serialize};
+
+/// Objectifs d'un scénario ; chaque objectif absent est ignoré
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Objectives {
+    #[serde(default = "default_max_turns")]
+    pub max_turns: usize,
+    /// Pourcentage de la carte à explorer
+    pub exploration_percent: Option<f32>,
+    /// Mineraux à livrer à la station
+    pub minerals_delivered: Option<u32>,
+    /// Sites scientifiques à analyser
+    pub science_sites: Option<usize>,
+    /// Nombre minimal de robots opérationnels
+    pub min_robots_alive: Option<usize>,
+}
+
+fn default_max_turns() -> usize {
+    DEFAULT_MAX_TURNS
+}
+
+impl Default for Objectives {
+    fn default() -> Self {
+        Objectives {
+            max_turns: default_max_turns(),
+            exploration_percent: None,
+            minerals_delivered: None,
+            science_sites: None,
+            min_robots_alive: None,
+        }
+    }
+}
+
+impl Objectives {
+    pub fn is_empty(&self) -> bool {
+        self.exploration_percent.is_none()
+            && self.minerals_delivered.is_none()
+            && self.science_sites.is_none()
+            && self.min_robots_alive.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum MissionStatus {
+    #[default]
+    EnCours,
+    Reussie,
+    Echouee(String),
+}
+
+impl MissionStatus {
+    pub fn is_finished(&self) -> bool {
+        *self != MissionStatus::EnCours
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            MissionStatus::EnCours => "EN COURS".to_string(),
+            MissionStatus::Reussie => "RÉUSSIE".to_string(),
+            MissionStatus::Echouee(reason) => format!("ÉCHOUÉE ({})", reason),
+        }
+    }
+}
+
+/// Avancement d'un objectif pour le résumé de mission
+#[derive(Debug, Clone)]
+pub struct ObjectiveProgress {
+    pub label: &'static str,
+    pub current: f64,
+    pub target: f64,
+    pub met: bool,
+    /// Plafond atteignable compte tenu de ce qui reste sur la carte
+    pub reachable: bool,
+    /// Contrainte à maintenir plutôt que but à atteindre
+    pub constraint: bool,
+}
+
+impl ObjectiveProgress {
+    pub fn describe(&self) -> String {
+        let mark = if self.met { "✅" } else if self.reachable { "⏳" } else { "❌" };
+        format!("{} {}: {:.0}/{:.0}", mark, self.label, self.current, self.target)
+    }
+}
+
+/// Calcule l'avancement de chaque objectif défini
+pub fn objective_progress(objectives: &Objectives, engine: &SimulationEngine) -> Vec<ObjectiveProgress> {
+    let mut progress = Vec::new();
+
+    if let Some(target) = objectives.exploration_percent {
+        let total = (engine.width * engine.height).max(1) as f64;
+        let explored = engine.explored_cell_count() as f64;
+        // Les obstacles ne sont jamais parcourus : ils ne peuvent pas être explorés
+        let reachable_cells = engine
+            .map
+            .iter()
+            .filter(|cell| cell.explored || cell.is_passable())
+            .count() as f64;
+        let current = explored / total * 100.0;
+        progress.push(ObjectiveProgress {
+            label: "Exploration (%)",
+            current,
+            target: target as f64,
+            met: current >= target as f64,
+            reachable: engine.alive_robot_count() > 0 && reachable_cells / total * 100.0 >= target as f64,
+            constraint: false,
+        });
+    }
+
+    if let Some(target) = objectives.minerals_delivered {
+        let delivered = engine.delivered(ResourceType::Mineraux);
+        let carried: u32 = engine
+            .robots
+            .iter()
+            .filter(|robot| robot.is_operational())
+            .map(|robot| robot.carried(ResourceType::Mineraux))
+            .sum();
+        let on_map = engine.remaining_on_map(ResourceType::Mineraux);
+        progress.push(ObjectiveProgress {
+            label: "Mineraux livrés",
+            current: delivered as f64,
+            target: target as f64,
+            met: delivered >= target,
+            reachable: delivered + carried + on_map >= target,
+            constraint: false,
+        });
+    }
+
+    if let Some(target) = objectives.science_sites {
+        let completed = engine.science_sites_completed;
+        let remaining = engine
+            .map
+            .iter()
+            .filter(|cell| cell.resources.contains(ResourceType::LieuxInteret))
+            .count();
+        progress.push(ObjectiveProgress {
+            label: "Sites scientifiques",
+            current: completed as f64,
+            target: target as f64,
+            met: completed >= target,
+            // Les météorites laissent de nouveaux sites dans leurs cratères
+            reachable: completed + remaining >= target || engine.environment.config.meteor_chance > 0.0,
+            constraint: false,
+        });
+    }
+
+    if let Some(target) = objectives.min_robots_alive {
+        let alive = engine.alive_robot_count();
+        progress.push(ObjectiveProgress {
+            label: "Robots opérationnels",
+            current: alive as f64,
+            target: target as f64,
+            met: alive >= target,
+            // Un robot hors service ne revient pas
+            reachable: alive >= target,
+            constraint: true,
+        });
+    }
+
+    progress
+}
+
+/// Évalue la mission après un tour
+pub fn evaluate(objectives: &Objectives, engine: &SimulationEngine) -> MissionStatus {
+    let progress = objective_progress(objectives, engine);
+
+    // Échec dès qu'un objectif ne peut plus être atteint (ou qu'une contrainte est violée)
+    if let Some(lost) = progress.iter().find(|p| !p.reachable) {
+        return MissionStatus::Echouee(format!("objectif impossible: {}", lost.label));
+    }
+
+    let goals: Vec<&ObjectiveProgress> = progress.iter().filter(|p| !p.constraint).collect();
+    if !goals.is_empty() && goals.iter().all(|p| p.met) {
+        return MissionStatus::Reussie;
+    }
+
+    if engine.turn >= objectives.max_turns {
+        // Sans but à atteindre, aller au bout de la limite suffit
+        return if goals.is_empty() {
+            MissionStatus::Reussie
+        } else {
+            MissionStatus::Echouee(format!("limite de {} tours atteinte", objectives.max_turns))
+        };
+    }
+
+    MissionStatus::EnCours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Grid, Position, RobotBehavior, TerrainType};
+    use crate::robot::Robot;
+    use crate::simulation::events::EventKind;
+
+    // Deux robots sur une plaine avec un seul site scientifique
+    fn engine_with_one_site() -> SimulationEngine {
+        let mut map = Grid::new(12, 8, Cell::new(TerrainType::Plaine));
+        map[Position::new(6, 4)].add_resource(ResourceType::LieuxInteret, 1);
+        let robots = vec![
+            Robot::new(1, Position::new(2, 2), RobotBehavior::Scientifique),
+            Robot::new(2, Position::new(3, 2), RobotBehavior::Explorateur),
+        ];
+        SimulationEngine::new(map, robots)
+    }
+
+    fn sites(target: usize) -> Objectives {
+        Objectives { science_sites: Some(target), ..Objectives::default() }
+    }
+
+    #[test]
+    fn mission_succeeds_once_every_goal_is_met() {
+        let mut engine = engine_with_one_site();
+        assert_eq!(evaluate(&sites(1), &engine), MissionStatus::EnCours);
+
+        engine.map[Position::new(6, 4)].resources.remove(ResourceType::LieuxInteret);
+        engine.science_sites_completed = 1;
+        assert_eq!(evaluate(&sites(1), &engine), MissionStatus::Reussie);
+
+        // Sans but, seule la limite de tours termine la mission
+        let open = Objectives { max_turns: 10, ..Objectives::default() };
+        assert_eq!(evaluate(&open, &engine), MissionStatus::EnCours);
+        engine.turn = 10;
+        assert_eq!(evaluate(&open, &engine), MissionStatus::Reussie);
+        assert!(matches!(evaluate(&sites(2), &engine), MissionStatus::Echouee(_)));
+    }
+
+    #[test]
+    fn missing_sites_fail_unless_meteors_may_bring_more() {
+        let mut engine = engine_with_one_site();
+        assert_eq!(
+            evaluate(&sites(2), &engine),
+            MissionStatus::Echouee("objectif impossible: Sites scientifiques".to_string())
+        );
+
+        engine.environment.config.meteor_chance = 0.01;
+        assert_eq!(evaluate(&sites(2), &engine), MissionStatus::EnCours);
+    }
+
+    #[test]
+    fn losing_a_robot_below_the_minimum_fails_the_mission() {
+        let mut engine = engine_with_one_site();
+        let objectives = Objectives { min_robots_alive: Some(2), ..sites(1) };
+        assert_eq!(evaluate(&objectives, &engine), MissionStatus::EnCours);
+
+        engine.robots[1].energy = 0;
+        assert_eq!(
+            evaluate(&objectives, &engine),
+            MissionStatus::Echouee("objectif impossible: Robots opérationnels".to_string())
+        );
+    }
+
+    #[test]
+    fn end_of_mission_is_announced_once() {
+        let mut engine = engine_with_one_site();
+        engine.objectives = Objectives { max_turns: 3, ..Objectives::default() };
+        for _ in 0..8 {
+            engine.step();
+        }
+
+        assert_eq!(engine.mission_status, MissionStatus::Reussie);
+        let announced = engine.events.iter().filter(|event| event.kind == EventKind::Mission).count();
+        assert_eq!(announced, 1);
+    }
+}
@@ -1,5 +1,31 @@
 // src/simulation/mod.rs - Module de simulation
+pub mod benchmark;
+pub mod builder;
+pub mod control;
+pub mod coordinator;
+pub mod depot;
 pub mod engine;
+pub mod environment;
+pub mod events;
+pub mod heatmap;
+pub mod mission;
+pub mod relay;
+pub mod replay;
+pub mod scenario;
+pub mod team;
 
 // Réexporter les types publics
-pub use engine::*;
\ No newline at end of file
+pub use benchmark::*;
+pub use builder::*;
+pub use control::*;
+pub use coordinator::*;
+pub use depot::*;
+pub use engine::*;
+pub use environment::*;
+pub use events::*;
+pub use heatmap::*;
+pub use mission::*;
+pub use relay::*;
+pub use replay::*;
+pub use scenario::*;
+pub use team::*;
\ No newline at end of file
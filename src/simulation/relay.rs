@@ -0,0 +1,264 @@
+// src/simulation/relay.rs - Chaînes de relais entre un gisement éloigné et la station
+use crate::{Cell, Position, ResourceType, RobotBehavior};
+use crate::map::find_path_to_any;
+use crate::robot::{RelayAssignment, Robot};
+use crate::simulation::depot::team_depot_positions;
+use crate::simulation::events::EventKind;
+use crate::simulation::engine::SimulationEngine;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+/// Tours entre deux passes du planificateur
+pub const RELAY_PLAN_INTERVAL: usize = 20;
+/// Pas entre deux postes de relais le long du chemin
+pub const RELAY_SPACING: usize = 8;
+/// Mineraux repérés nécessaires pour justifier une chaîne
+pub const RELAY_MIN_DEPOSIT: u32 = 30;
+/// Chaînes actives en même temps
+pub const MAX_RELAY_CHAINS: usize = 2;
+
+/// Chaîne formée : postes du gisement vers le dépôt le plus proche, un robot par poste
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayChain {
+    pub id: usize,
+    #[serde(default)]
+    pub team: usize,
+    pub deposit: Position,
+    pub posts: Vec<Position>,
+    pub members: Vec<usize>,
+}
+
+impl RelayChain {
+    pub fn describe(&self) -> String {
+        let members: Vec<String> = self.members.iter().map(|id| format!("R{}", id)).collect();
+        format!("Relais {} : ({},{}) → {} → dépôt", self.id, self.deposit.x, self.deposit.y, members.join(" → "))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RelayNetwork {
+    pub chains: Vec<RelayChain>,
+    pub chains_formed: usize,
+}
+
+impl RelayNetwork {
+    /// Entretient les chaînes existantes puis en forme au plus une nouvelle par équipe
+    pub fn update(engine: &mut SimulationEngine) {
+        if !engine.turn.is_multiple_of(RELAY_PLAN_INTERVAL) {
+            return;
+        }
+
+        let mut network = std::mem::take(&mut engine.relays);
+        network.dissolve_broken(engine);
+        for team in 0..engine.teams.len() {
+            let active = network.chains.iter().filter(|chain| chain.team == team).count();
+            if !engine.teams[team].tactics.relays || active >= MAX_RELAY_CHAINS {
+                continue;
+            }
+            let Some(chain) = network.plan_chain(engine, team) else {
+                continue;
+            };
+
+            network.chains_formed += 1;
+            let chain = RelayChain { id: network.chains_formed, ..chain };
+            assign(&chain, &mut engine.robots);
+            let message = format!(
+                "Chaîne de relais {} formée : {} robots entre ({},{}) et le dépôt le plus proche",
+                chain.id,
+                chain.members.len(),
+                chain.deposit.x,
+                chain.deposit.y
+            );
+            engine.events.push(engine.turn, EventKind::Robot, message);
+            network.chains.push(chain);
+        }
+        engine.relays = network;
+    }
+
+    /// Une chaîne tombe dès qu'un maillon manque ou que le gisement est vidé et le chargement livré
+    fn dissolve_broken(&mut self, engine: &mut SimulationEngine) {
+        let (robots, map, events, turn) = (&mut engine.robots, &engine.map, &mut engine.events, engine.turn);
+
+        self.chains.retain(|chain| {
+            let members: Vec<&Robot> = chain
+                .members
+                .iter()
+                .filter_map(|id| robots.iter().find(|robot| robot.id == *id))
+                .collect();
+            let intact = members.len() == chain.members.len()
+                && members.iter().all(|robot| robot.is_operational() && robot.behavior == RobotBehavior::Collecteur);
+            let exhausted = map[chain.deposit].resources.amount(ResourceType::Mineraux) == 0
+                && members.iter().all(|robot| robot.inventory.is_empty());
+            if intact && !exhausted {
+                return true;
+            }
+
+            for robot in robots.iter_mut().filter(|robot| chain.members.contains(&robot.id)) {
+                robot.relay = None;
+            }
+            let reason = if exhausted { "gisement épuisé" } else { "maillon perdu" };
+            events.push(turn, EventKind::Robot, format!("Chaîne de relais {} dissoute ({})", chain.id, reason));
+            false
+        });
+    }
+
+    /// Gisement repéré par l'équipe le plus riche, assez loin de ses dépôts pour qu'un relais vaille la peine
+    fn plan_chain(&self, engine: &SimulationEngine, team: usize) -> Option<RelayChain> {
+        let depots = team_depot_positions(&engine.depots, team, None);
+        let knowledge = &engine.teams[team];
+        let minerals = |cell: &Cell| cell.resources.amount(ResourceType::Mineraux);
+        let (deposit, _) = engine
+            .map
+            .enumerate()
+            .filter(|(pos, cell)| knowledge.knows(*pos) && cell.is_passable() && minerals(cell) >= RELAY_MIN_DEPOSIT)
+            .filter(|(pos, _)| !self.chains.iter().any(|chain| chain.team == team && chain.deposit == *pos))
+            .filter(|(pos, _)| depots.iter().all(|depot| engine.map.chebyshev_distance(*depot, *pos) >= 2 * RELAY_SPACING))
+            .max_by_key(|(pos, cell)| (minerals(cell), Reverse((pos.y, pos.x))))?;
+
+        // Postes tous les RELAY_SPACING pas, du gisement vers le dépôt (exclu)
+        let path = find_path_to_any(&engine.map, deposit, &depots)?;
+        let posts: Vec<Position> = (0..)
+            .map(|i| i * RELAY_SPACING)
+            .take_while(|offset| *offset < path.len())
+            .map(|offset| if offset == 0 { deposit } else { path[offset - 1] })
+            .collect();
+
+        // Un collecteur libre par poste, le plus proche d'après l'index spatial ; la tête doit savoir extraire
+        let mut members: Vec<usize> = Vec::with_capacity(posts.len());
+        for (index, post) in posts.iter().enumerate() {
+            let (recruit, _) = *engine
+                .robot_positions
+                .nearest_k(*post, 1, usize::MAX, |rank, _| {
+                    let robot = &engine.robots[rank];
+                    robot.team == team
+                        && robot.is_operational()
+                        && robot.behavior == RobotBehavior::Collecteur
+                        && robot.relay.is_none()
+                        && !members.contains(&robot.id)
+                        && (index > 0 || robot.can_collect(ResourceType::Mineraux))
+                })
+                .first()?;
+            members.push(engine.robots[recruit].id);
+        }
+
+        Some(RelayChain { id: 0, team, deposit, posts, members })
+    }
+}
+
+/// Donne à chaque maillon son poste et le robot suivant vers la station
+fn assign(chain: &RelayChain, robots: &mut [Robot]) {
+    for (index, member) in chain.members.iter().enumerate() {
+        let receiver = chain.members.get(index + 1).map(|next| (*next, chain.posts[index + 1]));
+        if let Some(robot) = robots.iter_mut().find(|robot| robot.id == *member) {
+            robot.relay = Some(RelayAssignment {
+                chain: chain.id,
+                post: chain.posts[index],
+                receiver,
+                collects: index == 0,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainType;
+    use crate::robot::RobotAction;
+    use crate::simulation::engine::WAIT_ENERGY_RECOVERY;
+    use crate::Grid;
+
+    fn collectors(positions: &[(usize, usize)]) -> SimulationEngine {
+        let map = Grid::new(40, 5, Cell::new(TerrainType::Plaine));
+        let robots = positions
+            .iter()
+            .enumerate()
+            .map(|(id, (x, y))| Robot::new(id, Position::new(*x, *y), RobotBehavior::Collecteur))
+            .collect();
+        SimulationEngine::new(map, robots)
+    }
+
+    // Mineraux sur la carte, à bord des robots et livrés à la station
+    fn total_minerals(engine: &SimulationEngine) -> u32 {
+        engine.remaining_on_map(ResourceType::Mineraux) + engine.collected_total(ResourceType::Mineraux)
+    }
+
+    #[test]
+    fn transfer_is_capped_by_receiver_room_and_conserves_cargo() {
+        let mut engine = collectors(&[(10, 2), (11, 2)]);
+        engine.robots[0].load(ResourceType::Mineraux, 20);
+        engine.robots[1].load(ResourceType::Mineraux, 15);
+
+        engine.apply_actions(&[
+            RobotAction::Transfer { to: 1, resource: ResourceType::Mineraux, amount: 20 },
+            RobotAction::Wait,
+        ])
+        .unwrap();
+
+        // 15 unités de 2 de masse : plus que 10 unités de place sur 50
+        assert_eq!(engine.robots[0].carried(ResourceType::Mineraux), 10);
+        assert_eq!(engine.robots[1].carried(ResourceType::Mineraux), 25);
+        assert_eq!(total_minerals(&engine), 35);
+    }
+
+    #[test]
+    fn transfer_requires_an_adjacent_receiver() {
+        let mut engine = collectors(&[(10, 2), (12, 2)]);
+        engine.robots[0].load(ResourceType::Mineraux, 20);
+
+        engine.apply_actions(&[
+            RobotAction::Transfer { to: 1, resource: ResourceType::Mineraux, amount: 20 },
+            RobotAction::Wait,
+        ])
+        .unwrap();
+
+        assert_eq!(engine.robots[0].carried(ResourceType::Mineraux), 20);
+        assert!(engine.robots[1].inventory.is_empty());
+    }
+
+    #[test]
+    fn dropped_cargo_stays_on_the_cell() {
+        let mut engine = collectors(&[(10, 2)]);
+        engine.robots[0].load(ResourceType::Mineraux, 12);
+
+        engine.apply_actions(&[RobotAction::Drop(ResourceType::Mineraux)]).unwrap();
+
+        assert!(engine.robots[0].inventory.is_empty());
+        assert_eq!(engine.map[Position::new(10, 2)].resources.amount(ResourceType::Mineraux), 12);
+        assert_eq!(total_minerals(&engine), 12);
+    }
+
+    #[test]
+    fn shared_energy_is_conserved_and_keeps_the_reserve() {
+        let mut engine = collectors(&[(10, 2), (11, 3)]);
+        engine.robots[0].energy = 25;
+        engine.robots[1].energy = 5;
+
+        engine.apply_actions(&[RobotAction::ShareEnergy { to: 1, amount: 40 }, RobotAction::Wait]).unwrap();
+
+        assert_eq!(engine.robots[0].energy, 10);
+        // 15 reçus, plus le repos du receveur pendant son tour
+        assert_eq!(engine.robots[1].energy, 20 + WAIT_ENERGY_RECOVERY);
+    }
+
+    #[test]
+    fn relay_chain_delivers_without_losing_minerals() {
+        let mut engine = collectors(&[(2, 1), (3, 1), (2, 2), (3, 2)]);
+        let deposit = Position::new(30, 2);
+        engine.map[deposit].add_resource(ResourceType::Mineraux, 60);
+        engine.map[deposit].explored = true;
+        engine.teams[0].discover(deposit);
+        engine.reindex();
+        engine.coordinator.enabled = false;
+
+        let initial = total_minerals(&engine);
+        for _ in 0..400 {
+            engine.execute_turn();
+            engine.end_turn();
+            assert_eq!(total_minerals(&engine), initial, "tour {}", engine.turn);
+        }
+
+        assert_eq!(engine.relays.chains_formed, 1);
+        assert!(engine.delivered(ResourceType::Mineraux) > 0);
+    }
+}
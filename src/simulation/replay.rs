@@ -0,0 +1,469 @@
+// src/simulation/replay.rs - Enregistrement et relecture déterministe des simulations
+use crate::{Cell, TerrainType};
+use crate::Grid;
+use crate::map::{CLIMB_PER_ENERGY, FREE_DESCENT, SENSOR_HEIGHT};
+use crate::robot::{
+    Robot, RobotAction, FIELD_REPAIR, MALFUNCTION_RATE, MALFUNCTION_TURNS, MASS_PER_EXTRA_ENERGY, METEOR_WEAR,
+    MOVE_ENERGY_COST, REPAIR_MINERAL_COST, STATION_REPAIR, STORM_WEAR, WEAR_CHANCE,
+};
+use crate::simulation::control::{Command, CommandError};
+use crate::simulation::coordinator::{CoordinatorConfig, DEPOSIT_SATURATION, SITE_SATURATION};
+use crate::simulation::depot::{Depot, MAX_OUTPOSTS, OUTPOST_MINERAL_COST, OUTPOST_MIN_SPACING};
+use crate::simulation::environment::{
+    Environment, COLD_DRAIN_INTERVAL, EJECTA_CHANCE, IMPACT_DEPTH, METEOR_DAMAGE, STORM_DAMAGE, STORM_DRIFT_INTERVAL,
+};
+use crate::simulation::relay::{RelayNetwork, RELAY_MIN_DEPOSIT, RELAY_PLAN_INTERVAL, RELAY_SPACING};
+use crate::simulation::events::EventLog;
+use crate::simulation::heatmap::Traces;
+use crate::simulation::mission::{MissionStatus, Objectives};
+use crate::simulation::team::Team;
+use crate::simulation::engine::{
+    SimulationEngine, ANALYZE_ENERGY_COST, COMMUNICATE_ENERGY_COST, MAX_ENERGY, REPAIR_ENERGY_COST,
+    SHARE_ENERGY_RESERVE, WAIT_ENERGY_RECOVERY,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+/// Version du moteur inscrite dans chaque replay
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// À incrémenter à chaque changement de règles non couvert par les constantes hachées par `rules_hash`.
+///
+/// Le replay garde les actions décidées, pas les décisions : modifier `BehaviorEngine` ou l'interface
+/// n'oblige à rien. En revanche, tout ce qui transforme des actions et un état en un nouvel état doit
+/// l'incrémenter :
+/// - effet d'une action dans `SimulationEngine::execute_robot_action` (déplacement, collecte, analyse,
+///   transfert, partage, construction, réparation) et service aux dépôts ;
+/// - effet d'une `Command` ;
+/// - fin de tour : environnement (tempêtes, météorites, nuit), usure et pannes (`roll`, `tick_malfunctions`),
+///   coordinateur des rôles, réseau de relais ;
+/// - terrain : franchissabilité, coût de montée, ligne de vue, topologie des bords ;
+/// - évaluation de la mission.
+pub const RULES_REVISION: u32 = 12;
+
+/// Nombre de tours entre deux images clés lors de la relecture
+pub const KEYFRAME_INTERVAL: usize = 50;
+
+/// État complet de la simulation à un tour donné
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationSnapshot {
+    pub turn: usize,
+    pub map: Grid<Cell>,
+    pub robots: Vec<Robot>,
+    /// Stations et avant-postes avec leurs stocks
+    #[serde(default)]
+    pub depots: Vec<Depot>,
+    #[serde(default)]
+    pub science_sites_completed: usize,
+    /// Objectifs du scénario, pour juger la mission comme en direct
+    #[serde(default)]
+    pub objectives: Objectives,
+    #[serde(default)]
+    pub mission_status: MissionStatus,
+    #[serde(default)]
+    pub messages_delivered: usize,
+    #[serde(default)]
+    pub environment: Environment,
+    #[serde(default)]
+    pub coordinator: CoordinatorConfig,
+    #[serde(default)]
+    pub relays: RelayNetwork,
+    /// Équipes avec leur connaissance de la carte ; une seule si absent
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    #[serde(default)]
+    pub events: EventLog,
+    /// Traces des robots pour les cartes de chaleur
+    #[serde(default)]
+    pub traces: Option<Traces>,
+}
+
+/// Fichier de replay : état initial + commandes extérieures et actions de chaque robot à chaque tour
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub engine_version: String,
+    pub rules_hash: u64,
+    pub initial_state: SimulationSnapshot,
+    pub turns: Vec<Vec<RobotAction>>,
+    /// Commandes passées avant chaque tour (même indice que `turns`)
+    #[serde(default)]
+    pub commands: Vec<Vec<Command>>,
+    // Commandes passées depuis le dernier tour enregistré ; perdues si aucun tour ne suit
+    #[serde(skip)]
+    pending: Vec<Command>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Format(serde_json::Error),
+    VersionMismatch { expected: String, found: String },
+    RulesMismatch { expected: u64, found: u64 },
+    /// Nombre d'actions différent du nombre de robots : le replay ne correspond plus au monde rejoué
+    ActionCount { turn: usize, expected: usize, found: usize },
+    /// Commande enregistrée refusée à la relecture
+    Command { turn: usize, error: CommandError },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "erreur d'accès au fichier: {}", e),
+            ReplayError::Format(e) => write!(f, "replay illisible: {}", e),
+            ReplayError::VersionMismatch { expected, found } => write!(
+                f,
+                "version du moteur incompatible (attendue {}, trouvée {})",
+                expected, found
+            ),
+            ReplayError::RulesMismatch { expected, found } => write!(
+                f,
+                "règles de simulation différentes (hash attendu {:016x}, trouvé {:016x})",
+                expected, found
+            ),
+            ReplayError::ActionCount { turn, expected, found } => write!(
+                f,
+                "tour {}: {} actions pour {} robots",
+                turn, found, expected
+            ),
+            ReplayError::Command { turn, error } => write!(f, "tour {}: commande refusée ({})", turn, error),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        ReplayError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ReplayError {
+    fn from(e: serde_json::Error) -> Self {
+        ReplayError::Format(e)
+    }
+}
+
+/// Empreinte des règles qui influencent le résultat d'une action
+pub fn rules_hash() -> u64 {
+    let terrains = [
+        TerrainType::Plaine,
+        TerrainType::Montagne,
+        TerrainType::Cratere,
+        TerrainType::Obstacle,
+    ];
+    let passable: Vec<bool> = terrains.iter().map(|t| Cell::new(*t).is_passable()).collect();
+
+    let wear = format!(
+        "wear={};malfunction={}x{};storm_wear={};meteor_wear={};station_repair={};field_repair={}:{}:{}",
+        WEAR_CHANCE,
+        MALFUNCTION_RATE,
+        MALFUNCTION_TURNS,
+        STORM_WEAR,
+        METEOR_WEAR,
+        STATION_REPAIR,
+        FIELD_REPAIR,
+        REPAIR_MINERAL_COST,
+        REPAIR_ENERGY_COST,
+    );
+
+    let environment = format!(
+        "storm={}:{};meteor={}:{}:{};cold={}",
+        STORM_DAMAGE,
+        STORM_DRIFT_INTERVAL,
+        METEOR_DAMAGE,
+        EJECTA_CHANCE,
+        IMPACT_DEPTH,
+        COLD_DRAIN_INTERVAL,
+    );
+
+    let description = format!(
+        "rev={};move={};climb={};free_descent={};sensor={};analyze={};communicate={};wait={};max_energy={};passable={:?};{};{};roles={}:{};mass_per_energy={};relay={}:{}:{};share_reserve={};outpost={}:{}:{}",
+        RULES_REVISION,
+        MOVE_ENERGY_COST,
+        CLIMB_PER_ENERGY,
+        FREE_DESCENT,
+        SENSOR_HEIGHT,
+        ANALYZE_ENERGY_COST,
+        COMMUNICATE_ENERGY_COST,
+        WAIT_ENERGY_RECOVERY,
+        MAX_ENERGY,
+        passable,
+        wear,
+        environment,
+        DEPOSIT_SATURATION,
+        SITE_SATURATION,
+        MASS_PER_EXTRA_ENERGY,
+        RELAY_PLAN_INTERVAL,
+        RELAY_SPACING,
+        RELAY_MIN_DEPOSIT,
+        SHARE_ENERGY_RESERVE,
+        OUTPOST_MINERAL_COST,
+        OUTPOST_MIN_SPACING,
+        MAX_OUTPOSTS,
+    );
+
+    fnv1a(description.as_bytes())
+}
+
+// Hash FNV-1a 64 bits : stable entre compilations, contrairement à DefaultHasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+impl Replay {
+    pub fn new(initial_state: SimulationSnapshot) -> Self {
+        Replay {
+            engine_version: ENGINE_VERSION.to_string(),
+            rules_hash: rules_hash(),
+            initial_state,
+            turns: Vec::new(),
+            commands: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Commande appliquée au moteur, rejouée avant le prochain tour enregistré
+    pub fn record_command(&mut self, command: Command) {
+        self.pending.push(command);
+    }
+
+    pub fn record_turn(&mut self, actions: Vec<RobotAction>) {
+        self.commands.push(std::mem::take(&mut self.pending));
+        self.turns.push(actions);
+    }
+
+    /// Commandes à rejouer avant le tour `index`
+    pub fn commands_before(&self, index: usize) -> &[Command] {
+        self.commands.get(index).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn turn_count(&self) -> usize {
+        self.turns.len()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Charge un replay, en refusant ceux produits par un autre moteur ou d'autres règles
+    pub fn load(path: &Path) -> Result<Replay, ReplayError> {
+        let reader = BufReader::new(File::open(path)?);
+        let replay: Replay = serde_json::from_reader(reader)?;
+        replay.check_compatibility()?;
+        Ok(replay)
+    }
+
+    pub fn check_compatibility(&self) -> Result<(), ReplayError> {
+        if self.engine_version != ENGINE_VERSION {
+            return Err(ReplayError::VersionMismatch {
+                expected: ENGINE_VERSION.to_string(),
+                found: self.engine_version.clone(),
+            });
+        }
+
+        let expected = rules_hash();
+        if self.rules_hash != expected {
+            return Err(ReplayError::RulesMismatch {
+                expected,
+                found: self.rules_hash,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Lecteur de replay avec déplacement rapide grâce aux images clés
+pub struct ReplayPlayer {
+    replay: Replay,
+    keyframes: Vec<SimulationSnapshot>, // keyframes[k] = état après k * KEYFRAME_INTERVAL tours
+    engine: SimulationEngine,
+    position: usize, // nombre de tours rejoués
+}
+
+impl ReplayPlayer {
+    /// Rejoue tout le replay une première fois pour construire les images clés ; refuse un replay
+    /// qui ne se rejoue pas (commande refusée, actions en nombre différent des robots)
+    pub fn new(replay: Replay) -> Result<Self, ReplayError> {
+        let mut engine = SimulationEngine::from_snapshot(replay.initial_state.clone());
+        let mut keyframes = vec![engine.snapshot()];
+
+        for (index, actions) in replay.turns.iter().enumerate() {
+            engine.replay_turn(replay.commands_before(index), actions)?;
+
+            if (index + 1).is_multiple_of(KEYFRAME_INTERVAL) {
+                keyframes.push(engine.snapshot());
+            }
+        }
+
+        let engine = SimulationEngine::from_snapshot(replay.initial_state.clone());
+
+        Ok(ReplayPlayer {
+            replay,
+            keyframes,
+            engine,
+            position: 0,
+        })
+    }
+
+    pub fn engine(&self) -> &SimulationEngine {
+        &self.engine
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn total_turns(&self) -> usize {
+        self.replay.turn_count()
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.total_turns()
+    }
+
+    pub fn step_forward(&mut self) -> bool {
+        if self.is_at_end() {
+            return false;
+        }
+
+        let (commands, actions) = (self.replay.commands_before(self.position), &self.replay.turns[self.position]);
+        self.engine.replay_turn(commands, actions).expect("replay vérifié à l'ouverture");
+        self.position += 1;
+        true
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+
+        self.seek(self.position - 1);
+        true
+    }
+
+    /// Se place après `target` tours (borné à la longueur du replay)
+    pub fn seek(&mut self, target: usize) {
+        let target = target.min(self.total_turns());
+        let keyframe_index = target / KEYFRAME_INTERVAL;
+        let keyframe_turn = keyframe_index * KEYFRAME_INTERVAL;
+
+        // Repartir de l'image clé sauf si l'état courant est déjà plus proche
+        if self.position > target || self.position < keyframe_turn {
+            self.engine = SimulationEngine::from_snapshot(self.keyframes[keyframe_index].clone());
+            self.position = keyframe_turn;
+        }
+
+        while self.position < target {
+            self.step_forward();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::benchmark::bench_engine;
+
+    // Partie enregistrée de `turns` tours, avec le moteur dans son état final
+    fn recorded_run(turns: usize) -> (Replay, SimulationEngine) {
+        let mut engine = bench_engine(30, 6);
+        engine.start_recording();
+        for _ in 0..turns {
+            engine.step();
+        }
+        (engine.take_recording().unwrap(), engine)
+    }
+
+    fn fingerprint(engine: &SimulationEngine) -> String {
+        serde_json::to_string(&engine.snapshot()).unwrap()
+    }
+
+    #[test]
+    fn saved_replay_loads_back_identical() {
+        let (replay, _) = recorded_run(20);
+        let path = std::env::temp_dir().join(format!("ereea_replay_test_{}.json", std::process::id()));
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), serde_json::to_string(&replay).unwrap());
+        assert_eq!(loaded.turn_count(), 20);
+    }
+
+    #[test]
+    fn other_engine_version_or_rules_are_refused() {
+        let (replay, _) = recorded_run(1);
+        assert!(replay.check_compatibility().is_ok());
+
+        let other_version = Replay { engine_version: "0.0.0-autre".to_string(), ..replay.clone() };
+        assert!(matches!(other_version.check_compatibility(), Err(ReplayError::VersionMismatch { .. })));
+
+        let other_rules = Replay { rules_hash: replay.rules_hash ^ 1, ..replay };
+        assert!(matches!(other_rules.check_compatibility(), Err(ReplayError::RulesMismatch { .. })));
+    }
+
+    #[test]
+    fn seeking_matches_stepping_forward() {
+        let (replay, _) = recorded_run(2 * KEYFRAME_INTERVAL + 20);
+        let mut stepped = ReplayPlayer::new(replay.clone()).unwrap();
+        let mut seeking = ReplayPlayer::new(replay).unwrap();
+
+        // En avant sans image clé, en avant au-delà de deux images clés, puis en arrière
+        for target in [10, 2 * KEYFRAME_INTERVAL + 15, KEYFRAME_INTERVAL + 5, 3] {
+            seeking.seek(target);
+            let mut reference = ReplayPlayer::new(stepped.replay.clone()).unwrap();
+            while reference.position() < target {
+                reference.step_forward();
+            }
+            assert_eq!(seeking.position(), target);
+            assert_eq!(fingerprint(seeking.engine()), fingerprint(reference.engine()), "tour {}", target);
+        }
+
+        assert!(stepped.step_forward());
+        assert!(!ReplayPlayer::new(stepped.replay.clone()).unwrap().step_back());
+        stepped.seek(usize::MAX);
+        assert!(stepped.is_at_end());
+    }
+
+    #[test]
+    fn replay_ends_in_the_recorded_state() {
+        let (replay, engine) = recorded_run(80);
+        let mut player = ReplayPlayer::new(replay).unwrap();
+        player.seek(player.total_turns());
+
+        assert_eq!(player.engine().turn, engine.turn);
+        assert_eq!(player.engine().mission_status, engine.mission_status);
+        assert_eq!(fingerprint(player.engine()), fingerprint(&engine));
+    }
+
+    #[test]
+    fn replay_keeps_the_scenario_objectives() {
+        // Les besoins du coordinateur et la fin de mission dépendent des objectifs
+        let mut engine = bench_engine(30, 6);
+        engine.objectives = Objectives { max_turns: 60, minerals_delivered: Some(500), ..Objectives::default() };
+        engine.start_recording();
+        for _ in 0..70 {
+            engine.step();
+        }
+        assert!(engine.mission_status.is_finished());
+
+        let mut player = ReplayPlayer::new(engine.take_recording().unwrap()).unwrap();
+        player.seek(player.total_turns());
+        assert_eq!(player.engine().objectives.max_turns, 60);
+        assert_eq!(player.engine().mission_status, engine.mission_status);
+        assert_eq!(fingerprint(player.engine()), fingerprint(&engine));
+    }
+}
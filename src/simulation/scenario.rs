@@ -0,0 +1,383 @@
+// src/simulation/scenario.rs - Scénarios : carte, essaim, règles et objectifs
+use crate::{Position, ResourceType};
+use crate::{RobotBehavior, RobotModule, Topology};
+use crate::{Cell, Grid};
+use crate::map::{load_map, parse_ascii_map, MapFileError};
+use crate::robot::{CargoRules, Robot};
+use crate::simulation::builder::{BuildError, DEFAULT_STATION};
+use crate::simulation::engine::SimulationEngine;
+use crate::simulation::coordinator::CoordinatorConfig;
+use crate::simulation::depot::DepotKind;
+use crate::simulation::environment::EnvironmentConfig;
+use crate::simulation::mission::Objectives;
+use crate::simulation::team::{Tactics, TeamColor};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Scénario par défaut (carte générée 80x20, 12 robots)
+pub const DEFAULT_SCENARIO: &str = include_str!("../../scenarios/default.toml");
+/// Scénario de démonstration (petite carte avec ressources pré-placées)
+pub const DEMO_SCENARIO: &str = include_str!("../../scenarios/demo.toml");
+/// Carte du scénario de démonstration, embarquée avec lui
+pub const DEMO_MAP: &str = include_str!("../../maps/demo.txt");
+
+// Cartes des scénarios livrés, par chemin tel qu'écrit dans le scénario
+const BUNDLED_MAPS: [(&str, &str); 1] = [("../maps/demo.txt", DEMO_MAP)];
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub map: MapSource,
+    /// Bords de la carte : "bornee", "cylindre" (bouclage est-ouest) ou "tore" ;
+    /// ceux du fichier de carte si absent (bornée pour une carte générée)
+    #[serde(default)]
+    pub topology: Option<Topology>,
+    /// Station principale, (1,1) par défaut ; chaque équipe a la sienne en compétition
+    #[serde(default)]
+    pub station: Option<Position>,
+    /// Essaims en compétition ; un seul essaim si absent
+    #[serde(default)]
+    pub teams: Vec<TeamSpec>,
+    /// Stations et avant-postes supplémentaires présents dès le départ
+    #[serde(default)]
+    pub bases: Vec<BaseSpec>,
+    pub robots: Vec<RobotSpec>,
+    #[serde(default)]
+    pub resources: ResourceRules,
+    #[serde(default)]
+    pub objectives: Objectives,
+    /// Tempêtes, météorites et cycle jour/nuit ; planète statique si absent
+    #[serde(default)]
+    pub environment: EnvironmentConfig,
+    /// Réaffectation des rôles selon les besoins de l'essaim (active par défaut)
+    #[serde(default)]
+    pub coordinator: CoordinatorConfig,
+    /// Touches de l'interface temps réel : action = [touches] (voir `gui::KeyMap`)
+    #[serde(default)]
+    pub keys: BTreeMap<String, Vec<String>>,
+    // Dossier de référence pour les chemins relatifs (carte)
+    #[serde(skip)]
+    base_dir: PathBuf,
+    // Scénario livré : ses cartes sont embarquées dans l'exécutable
+    #[serde(skip)]
+    bundled: bool,
+}
+
+/// Origine de la carte : générateur procédural ou fichier (ASCII ou JSON)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase", deny_unknown_fields)]
+pub enum MapSource {
+    Generator { seed: u64, width: usize, height: usize },
+    File { path: PathBuf },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RobotSpec {
+    pub id: usize,
+    pub behavior: RobotBehavior,
+    pub x: usize,
+    pub y: usize,
+    /// Modules du robot ; ceux du comportement par défaut si absent
+    pub modules: Option<Vec<RobotModule>>,
+    pub energy: Option<u32>,
+    /// Index de l'équipe dans `teams`
+    #[serde(default)]
+    pub team: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TeamSpec {
+    pub name: String,
+    /// Couleur à l'écran ; attribuée selon le rang de l'équipe si absente
+    pub color: Option<TeamColor>,
+    pub station: Position,
+    /// Variante de comportement à comparer aux autres équipes
+    #[serde(default)]
+    pub tactics: Tactics,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaseSpec {
+    pub x: usize,
+    pub y: usize,
+    /// "station" (par défaut) ou "avantposte"
+    #[serde(default)]
+    pub kind: DepotKind,
+    #[serde(default)]
+    pub team: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceRules {
+    /// Masse totale qu'un robot peut transporter
+    #[serde(default = "default_max_inventory")]
+    pub max_inventory: u32,
+    /// Capacité et masse de chaque ressource
+    #[serde(default)]
+    pub cargo: CargoRules,
+    /// Gisements ajoutés à la carte après génération/chargement
+    #[serde(default)]
+    pub deposits: Vec<Deposit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Deposit {
+    pub x: usize,
+    pub y: usize,
+    pub resource: ResourceType,
+    pub amount: u32,
+}
+
+fn default_max_inventory() -> u32 {
+    50
+}
+
+impl Default for ResourceRules {
+    fn default() -> Self {
+        ResourceRules {
+            max_inventory: default_max_inventory(),
+            cargo: CargoRules::default(),
+            deposits: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Map(MapFileError),
+    Invalid(String),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::Io(e) => write!(f, "erreur d'accès au fichier: {}", e),
+            ScenarioError::Parse(e) => write!(f, "scénario illisible: {}", e),
+            ScenarioError::Map(e) => write!(f, "carte du scénario invalide: {}", e),
+            ScenarioError::Invalid(message) => write!(f, "scénario incohérent: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl From<io::Error> for ScenarioError {
+    fn from(e: io::Error) -> Self {
+        ScenarioError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ScenarioError {
+    fn from(e: toml::de::Error) -> Self {
+        ScenarioError::Parse(e)
+    }
+}
+
+impl From<BuildError> for ScenarioError {
+    fn from(e: BuildError) -> Self {
+        ScenarioError::Invalid(e.to_string())
+    }
+}
+
+impl From<MapFileError> for ScenarioError {
+    fn from(e: MapFileError) -> Self {
+        ScenarioError::Map(e)
+    }
+}
+
+impl Scenario {
+    /// Charge un scénario TOML ; les chemins de carte sont relatifs au fichier
+    pub fn load(path: &Path) -> Result<Scenario, ScenarioError> {
+        let content = fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        Self::parse(&content, base_dir)
+    }
+
+    pub fn parse(content: &str, base_dir: PathBuf) -> Result<Scenario, ScenarioError> {
+        let mut scenario: Scenario = toml::from_str(content)?;
+        scenario.base_dir = base_dir;
+        Ok(scenario)
+    }
+
+    /// Scénarios livrés avec le projet, utilisables hors du dossier des sources
+    pub fn bundled_default() -> Scenario {
+        Self::bundled(DEFAULT_SCENARIO).expect("scénario par défaut invalide")
+    }
+
+    pub fn bundled_demo() -> Scenario {
+        Self::bundled(DEMO_SCENARIO).expect("scénario de démonstration invalide")
+    }
+
+    fn bundled(content: &str) -> Result<Scenario, ScenarioError> {
+        let mut scenario = Self::parse(content, PathBuf::new())?;
+        scenario.bundled = true;
+        Ok(scenario)
+    }
+
+    // Carte embarquée pour un scénario livré, lue à côté du scénario sinon
+    fn load_map(&self, path: &Path) -> Result<Grid<Cell>, MapFileError> {
+        match BUNDLED_MAPS.iter().find(|(bundled, _)| self.bundled && Path::new(bundled) == path) {
+            Some((_, content)) => parse_ascii_map(content),
+            None => load_map(&self.base_dir.join(path)),
+        }
+    }
+
+    /// Construit le moteur prêt à lancer : carte, gisements, robots, dépôts et limites
+    pub fn build_engine(&self) -> Result<SimulationEngine, ScenarioError> {
+        let mut builder = match &self.map {
+            MapSource::Generator { seed, width, height } => {
+                if *width == 0 || *height == 0 {
+                    return Err(ScenarioError::Invalid(format!("carte générée vide ({}x{})", width, height)));
+                }
+                SimulationEngine::builder().generated_map(*seed, *width, *height)
+            }
+            MapSource::File { path } => SimulationEngine::builder().map(self.load_map(path)?),
+        };
+
+        if !self.teams.is_empty() && self.station.is_some() {
+            return Err(ScenarioError::Invalid(
+                "station principale et équipes déclarées ensemble : chaque équipe a sa station".to_string(),
+            ));
+        }
+
+        // Les stations des équipes (ou la station principale) d'abord, puis les bases supplémentaires
+        if self.teams.is_empty() {
+            builder = builder.depot(0, DepotKind::Station, self.station.unwrap_or(DEFAULT_STATION));
+        }
+        for (id, spec) in self.teams.iter().enumerate() {
+            let color = spec.color.unwrap_or_else(|| TeamColor::for_team(id));
+            builder = builder.team(&spec.name, color, spec.tactics).depot(id, DepotKind::Station, spec.station);
+        }
+        for base in &self.bases {
+            builder = builder.depot(base.team, base.kind, Position::new(base.x, base.y));
+        }
+        for deposit in &self.resources.deposits {
+            builder = builder.deposit(Position::new(deposit.x, deposit.y), deposit.resource, deposit.amount);
+        }
+
+        for spec in &self.robots {
+            let mut robot = Robot::new(spec.id, Position::new(spec.x, spec.y), spec.behavior);
+            robot.team = spec.team;
+            if let Some(modules) = &spec.modules {
+                robot.modules = modules.clone();
+            }
+            if let Some(energy) = spec.energy {
+                robot.energy = energy;
+            }
+            robot.max_inventory = self.resources.max_inventory;
+            robot.cargo = self.resources.cargo;
+            builder = builder.robot(robot);
+        }
+
+        if let Some(topology) = self.topology {
+            builder = builder.topology(topology);
+        }
+        let engine = builder
+            .objectives(self.objectives.clone())
+            .environment(self.environment.clone())
+            .coordinator(self.coordinator.clone())
+            .build()?;
+        Ok(engine)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"
+name = "Essai"
+
+[map]
+source = "generator"
+seed = 3
+width = 20
+height = 10
+
+[[robots]]
+id = 1
+behavior = "Explorateur"
+x = 2
+y = 2
+"#;
+
+    fn invalid_message(result: Result<SimulationEngine, ScenarioError>) -> String {
+        match result {
+            Err(ScenarioError::Invalid(message)) => message,
+            other => panic!("scénario incohérent attendu, obtenu {:?}", other.map(|engine| engine.turn)),
+        }
+    }
+
+    #[test]
+    fn syntax_errors_and_unknown_fields_are_refused() {
+        assert!(Scenario::parse(MINIMAL, PathBuf::new()).is_ok());
+        assert!(matches!(Scenario::parse("name = \"Essai\"\n[map\n", PathBuf::new()), Err(ScenarioError::Parse(_))));
+
+        let unknown = MINIMAL.replace("name = \"Essai\"", "name = \"Essai\"\nturbo = true");
+        match Scenario::parse(&unknown, PathBuf::new()) {
+            Err(ScenarioError::Parse(e)) => assert!(e.to_string().contains("turbo")),
+            other => panic!("champ inconnu accepté: {:?}", other.map(|scenario| scenario.name)),
+        }
+    }
+
+    #[test]
+    fn map_path_is_relative_to_the_scenario() {
+        let dir = std::env::temp_dir().join(format!("ereea_scenario_test_{}", std::process::id()));
+        fs::create_dir_all(dir.join("cartes")).unwrap();
+        fs::write(dir.join("cartes/petite.txt"), "[terrain]\n#####\n#...#\n#####\n").unwrap();
+        let content = MINIMAL
+            .replace("source = \"generator\"\nseed = 3\nwidth = 20\nheight = 10", "source = \"file\"\npath = \"cartes/petite.txt\"")
+            .replace("x = 2\ny = 2", "x = 3\ny = 1");
+        fs::write(dir.join("essai.toml"), content).unwrap();
+
+        let loaded = Scenario::load(&dir.join("essai.toml")).and_then(|scenario| scenario.build_engine());
+        fs::remove_dir_all(&dir).unwrap();
+
+        let engine = loaded.unwrap();
+        assert_eq!((engine.map.width(), engine.map.height()), (5, 3));
+        assert_eq!(engine.robots[0].position, Position::new(3, 1));
+    }
+
+    #[test]
+    fn robots_and_stations_must_fit_together() {
+        let with_teams = format!(
+            "station = {{ x = 1, y = 1 }}\n{}\n[[teams]]\nname = \"Alpha\"\nstation = {{ x = 4, y = 4 }}\n",
+            MINIMAL
+        );
+        let scenario = Scenario::parse(&with_teams, PathBuf::new()).unwrap();
+        assert!(invalid_message(scenario.build_engine()).contains("station principale et équipes"));
+
+        let foreign = MINIMAL.replace("y = 2\n", "y = 2\nteam = 1\n");
+        let scenario = Scenario::parse(&foreign, PathBuf::new()).unwrap();
+        assert!(invalid_message(scenario.build_engine()).contains("équipe 1 inexistante"));
+
+        // Le mur d'enceinte de la carte de démonstration
+        let mut scenario = Scenario::bundled_demo();
+        scenario.robots[0].x = 0;
+        assert!(invalid_message(scenario.build_engine()).contains("case invalide"));
+    }
+
+    #[test]
+    fn bundled_scenarios_build() {
+        let engine = Scenario::bundled_default().build_engine().unwrap();
+        assert_eq!(engine.robots.len(), Scenario::bundled_default().robots.len());
+
+        let demo = Scenario::bundled_demo().build_engine().unwrap();
+        assert_eq!((demo.map.width(), demo.map.height()), (15, 10));
+        assert_eq!(demo.robots.len(), 3);
+    }
+}
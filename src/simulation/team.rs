@@ -0,0 +1,229 @@
+// src/simulation/team.rs - Essaims concurrents : connaissance, tactiques et score de chaque équipe
+use crate::{Cell, Position, ResourceType};
+use crate::Grid;
+use crate::simulation::engine::SimulationEngine;
+use serde::{Deserialize, Serialize};
+
+/// Points rapportés par une case cartographiée
+pub const POINTS_PER_CELL: u32 = 1;
+/// Points rapportés par une unité de mineraux livrée
+pub const POINTS_PER_MINERAL: u32 = 2;
+/// Points rapportés par un site scientifique analysé
+pub const POINTS_PER_SITE: u32 = 50;
+
+/// Couleur d'une équipe à l'écran
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamColor {
+    Vert,
+    Rouge,
+    Bleu,
+    Jaune,
+    Cyan,
+    Magenta,
+}
+
+const PALETTE: [TeamColor; 6] = [
+    TeamColor::Vert,
+    TeamColor::Rouge,
+    TeamColor::Bleu,
+    TeamColor::Jaune,
+    TeamColor::Cyan,
+    TeamColor::Magenta,
+];
+
+impl TeamColor {
+    /// Couleur attribuée d'office à la n-ième équipe
+    pub fn for_team(index: usize) -> TeamColor {
+        PALETTE[index % PALETTE.len()]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TeamColor::Vert => "vert",
+            TeamColor::Rouge => "rouge",
+            TeamColor::Bleu => "bleu",
+            TeamColor::Jaune => "jaune",
+            TeamColor::Cyan => "cyan",
+            TeamColor::Magenta => "magenta",
+        }
+    }
+}
+
+/// Comportements collectifs qu'une équipe s'autorise ; tout est actif par défaut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tactics {
+    /// Les robots bien chargés dépannent leurs voisins à sec
+    #[serde(default = "enabled")]
+    pub share_energy: bool,
+    /// Les collecteurs réparent leurs voisins avec des mineraux
+    #[serde(default = "enabled")]
+    pub field_repairs: bool,
+    /// Les collecteurs pleins bâtissent des avant-postes loin des dépôts
+    #[serde(default = "enabled")]
+    pub build_outposts: bool,
+    /// Des chaînes de relais desservent les gisements éloignés
+    #[serde(default = "enabled")]
+    pub relays: bool,
+    /// Le coordinateur réaffecte les rôles selon les besoins de l'équipe
+    #[serde(default = "enabled")]
+    pub reassign_roles: bool,
+}
+
+impl Default for Tactics {
+    fn default() -> Self {
+        Tactics {
+            share_energy: true,
+            field_repairs: true,
+            build_outposts: true,
+            relays: true,
+            reassign_roles: true,
+        }
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub id: usize,
+    pub name: String,
+    pub color: TeamColor,
+    #[serde(default)]
+    pub tactics: Tactics,
+    /// Cases cartographiées par les robots de l'équipe
+    pub known: Grid<bool>,
+    /// Sites scientifiques analysés par l'équipe
+    #[serde(default)]
+    pub science_sites: usize,
+}
+
+impl Team {
+    /// Équipe qui connaît déjà les cases explorées de la carte
+    pub fn new(id: usize, name: &str, color: TeamColor, map: &Grid<Cell>) -> Self {
+        Team {
+            id,
+            name: name.to_string(),
+            color,
+            tactics: Tactics::default(),
+            known: Grid::from_fn(map.width(), map.height(), |pos| map[pos].explored),
+            science_sites: 0,
+        }
+    }
+
+    pub fn discover(&mut self, pos: Position) {
+        if let Some(known) = self.known.get_mut(pos) {
+            *known = true;
+        }
+    }
+
+    pub fn knows(&self, pos: Position) -> bool {
+        self.known.get(pos).copied().unwrap_or(false)
+    }
+
+    pub fn known_cell_count(&self) -> usize {
+        self.known.iter().filter(|known| **known).count()
+    }
+}
+
+/// Bilan d'une équipe pour le classement
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamScore {
+    pub team: usize,
+    pub explored: usize,
+    pub minerals: u32,
+    pub science_sites: usize,
+    pub alive: usize,
+    pub points: u32,
+}
+
+/// Scores de toutes les équipes, du meilleur au moins bon
+pub fn team_scores(engine: &SimulationEngine) -> Vec<TeamScore> {
+    let mut scores: Vec<TeamScore> = engine
+        .teams
+        .iter()
+        .map(|team| {
+            let explored = team.known_cell_count();
+            let minerals = engine.delivered_by(team.id, ResourceType::Mineraux);
+            let alive = engine
+                .robots
+                .iter()
+                .filter(|robot| robot.team == team.id && robot.is_operational())
+                .count();
+            let points = explored as u32 * POINTS_PER_CELL
+                + minerals * POINTS_PER_MINERAL
+                + team.science_sites as u32 * POINTS_PER_SITE;
+            TeamScore { team: team.id, explored, minerals, science_sites: team.science_sites, alive, points }
+        })
+        .collect();
+    scores.sort_by_key(|score| (std::cmp::Reverse(score.points), score.team));
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RobotBehavior, TerrainType};
+    use crate::robot::Robot;
+    use crate::simulation::depot::DepotKind;
+
+    // Deux équipes sur une plaine 10x10 : un robot pour Alpha, deux pour Beta dont un à plat
+    fn duel() -> SimulationEngine {
+        let mut robots = vec![Robot::new(1, Position::new(2, 2), RobotBehavior::Explorateur)];
+        for (id, energy) in [(2, 100), (3, 0)] {
+            let mut robot = Robot::new(id, Position::new(7, id + 4), RobotBehavior::Collecteur);
+            robot.team = 1;
+            robot.energy = energy;
+            robots.push(robot);
+        }
+        SimulationEngine::builder()
+            .map(Grid::new(10, 10, Cell::new(TerrainType::Plaine)))
+            .team("Alpha", TeamColor::Vert, Tactics::default())
+            .team("Beta", TeamColor::Rouge, Tactics::default())
+            .depot(0, DepotKind::Station, Position::new(1, 1))
+            .depot(1, DepotKind::Station, Position::new(8, 8))
+            .robots(robots)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn scores_count_cells_minerals_and_sites_of_each_team() {
+        let mut engine = duel();
+        for x in 0..10 {
+            engine.teams[0].discover(Position::new(x, 0));
+        }
+        // Une case connue deux fois ne compte qu'une fois
+        engine.teams[0].discover(Position::new(0, 0));
+        engine.depots[0].store(ResourceType::Mineraux, 5);
+        engine.teams[1].science_sites = 1;
+        // L'énergie livrée ne rapporte rien
+        engine.depots[1].store(ResourceType::Energie, 30);
+
+        let scores = team_scores(&engine);
+        assert_eq!(
+            scores,
+            vec![
+                TeamScore { team: 1, explored: 0, minerals: 0, science_sites: 1, alive: 1, points: POINTS_PER_SITE },
+                TeamScore {
+                    team: 0,
+                    explored: 10,
+                    minerals: 5,
+                    science_sites: 0,
+                    alive: 1,
+                    points: 10 * POINTS_PER_CELL + 5 * POINTS_PER_MINERAL,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ties_are_ranked_by_team_order() {
+        let engine = duel();
+        let ranking: Vec<usize> = team_scores(&engine).iter().map(|score| score.team).collect();
+        assert_eq!(ranking, vec![0, 1]);
+    }
+}
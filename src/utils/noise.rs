@@ -1,5 +1,5 @@
 // src/utils/noise.rs - Utilitaires pour génération procédurale
-use noise::{NoiseFn, Perlin, Seedable};
+use noise::{NoiseFn, Perlin};
 use crate::TerrainType;
 
 pub struct NoiseGenerator {